@@ -0,0 +1,359 @@
+use super::generator_argument::member_argument::{
+    DistributionOfMember, MemberGeneratorArg, PropertyTemplate, VersioningArg,
+};
+use super::query::filter::Filter;
+use super::sparql_converter::{NumberToSparqlConverter, SparqlConverter};
+use super::tree::member::Member;
+use super::tree::node::Node;
+use super::tree::shape::{NodeShape, PropertyShape};
+use super::tree::value::{Value, ValueType};
+use chrono::SecondsFormat;
+use rand::{Rng, RngCore};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::vec::Vec;
+
+/// Generate the members of `n_node` nodes from the generator argument.
+pub(super) fn generate_members<T: num::ToPrimitive + Debug>(
+    member_args: &MemberGeneratorArg<T>,
+    base_url: &str,
+    n_node: usize,
+) -> Result<Vec<Vec<Member>>, &'static str> {
+    let mut members: Vec<Vec<Member>> = Vec::new();
+    let mut n_generated: usize = 0;
+    for i in 0..n_node {
+        let n_member = match &member_args.distribution_of_member {
+            DistributionOfMember::Direct(dist) => dist.get(i).copied().unwrap_or(0),
+            DistributionOfMember::Random(range_fn) => range_fn.next(),
+        };
+        members.push(generate_n_members(
+            member_args,
+            base_url,
+            n_member,
+            &mut n_generated,
+        )?);
+    }
+    Ok(members)
+}
+
+/// Generate `page_size` members in each of the `n_page` pages, ignoring the distribution of the members.
+pub(super) fn generate_members_of_pages<T: num::ToPrimitive + Debug>(
+    member_args: &MemberGeneratorArg<T>,
+    base_url: &str,
+    n_page: usize,
+    page_size: usize,
+) -> Result<Vec<Vec<Member>>, &'static str> {
+    let mut n_generated: usize = 0;
+    (0..n_page)
+        .map(|_| generate_n_members(member_args, base_url, page_size, &mut n_generated))
+        .collect()
+}
+
+/// Generate the members of a single node, `n_generated` being the number of members generated before them.
+fn generate_n_members<T: num::ToPrimitive + Debug>(
+    member_args: &MemberGeneratorArg<T>,
+    base_url: &str,
+    n_member: usize,
+    n_generated: &mut usize,
+) -> Result<Vec<Member>, &'static str> {
+    let mut members_of_the_node: Vec<Member> = Vec::new();
+    for _ in 0..n_member {
+        let mut member = generate_a_member(
+            &member_args.properties,
+            format!("{}/member/{}", base_url, uuid::Uuid::new_v4()),
+            &mut rand::thread_rng(),
+        )?;
+        if let Some(versioning) = &member_args.versioning {
+            add_version(&mut member, versioning, base_url, *n_generated)?;
+        }
+        *n_generated += 1;
+        members_of_the_node.push(member);
+    }
+    Ok(members_of_the_node)
+}
+
+/// Make the member a version of a random entity, its timestamp following the ones of the `n_generated` members before it.
+fn add_version(
+    member: &mut Member,
+    versioning: &VersioningArg,
+    base_url: &str,
+    n_generated: usize,
+) -> Result<(), &'static str> {
+    if versioning.n_entity == 0 {
+        return Err("the members should be the versions of at least one entity");
+    }
+    let timestamp = i32::try_from(n_generated)
+        .ok()
+        .and_then(|n| versioning.interval.checked_mul(n))
+        .and_then(|offset| versioning.start.checked_add_signed(offset))
+        .ok_or("the timestamp of a version is out of range")?;
+    let entity = rand::thread_rng().gen_range(0..versioning.n_entity);
+    member.properties.insert(
+        versioning.version_of_path.clone(),
+        Value {
+            value: format!("{}/entity/{}", base_url, entity),
+            value_type: ValueType::Iri,
+        },
+    );
+    member.properties.insert(
+        versioning.timestamp_path.clone(),
+        Value {
+            value: timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            value_type: ValueType::DateTime,
+        },
+    );
+    Ok(())
+}
+
+/// The [`NodeShape`] of the members generated from the generator argument.
+pub(super) fn member_shape<T>(member_args: &MemberGeneratorArg<T>, url: String) -> NodeShape {
+    NodeShape::new(
+        url,
+        member_args
+            .properties
+            .iter()
+            .map(|property| PropertyShape::new(property.path.clone(), property.value_type.clone()))
+            .chain(member_args.versioning.iter().flat_map(|versioning| {
+                [
+                    PropertyShape::new(versioning.version_of_path.clone(), ValueType::Iri),
+                    PropertyShape::new(versioning.timestamp_path.clone(), ValueType::DateTime),
+                ]
+            }))
+            .collect(),
+    )
+}
+
+/// Generate a single member with a value for every property of the schema,
+/// the values being drawn from `rng`.
+pub(super) fn generate_a_member<T: num::ToPrimitive + Debug>(
+    properties: &[PropertyTemplate<T>],
+    url: String,
+    rng: &mut dyn RngCore,
+) -> Result<Member, &'static str> {
+    let mut member_properties = HashMap::new();
+    for property in properties {
+        member_properties.insert(property.path.clone(), generate_a_value(property, rng)?);
+    }
+    Ok(Member {
+        url,
+        properties: member_properties,
+    })
+}
+
+/// The filters of the relations on the breadth-first path from the root to every node.
+pub(super) fn filters_of_the_paths(nodes: &[Node], root_url: &str) -> HashMap<String, Vec<Filter>> {
+    let nodes_by_url: HashMap<&String, &Node> =
+        nodes.iter().map(|node| (node.node_url(), node)).collect();
+    let mut filters: HashMap<String, Vec<Filter>> =
+        HashMap::from([(String::from(root_url), Vec::new())]);
+    let mut pending_url: VecDeque<String> = VecDeque::from([String::from(root_url)]);
+    while let Some(node_url) = pending_url.pop_front() {
+        let node = match nodes_by_url.get(&node_url) {
+            Some(v) => v,
+            None => continue,
+        };
+        for relation in node.relation() {
+            if filters.contains_key(relation.node()) {
+                continue;
+            }
+            let mut filters_of_the_child = filters[&node_url].clone();
+            filters_of_the_child.extend(Filter::of_relation(relation));
+            filters.insert(relation.node().clone(), filters_of_the_child);
+            pending_url.push_back(relation.node().clone());
+        }
+    }
+    filters
+}
+
+/// Make the values of the member satisfy the filters of the relations leading to its node,
+/// a property on the path of a filter being drawn again from `rng` until it satisfies the filters
+/// and, when no draw does, replaced by a number bordering their values.
+pub(super) fn constrain_member<T: num::ToPrimitive + Debug>(
+    member: &mut Member,
+    properties: &[PropertyTemplate<T>],
+    filters: &[Filter],
+    rng: &mut dyn RngCore,
+) -> Result<(), &'static str> {
+    for property in properties {
+        let filters_of_the_property: Vec<&Filter> = filters
+            .iter()
+            .filter(|filter| filter.paths() == [&property.path])
+            .collect();
+        let satisfies = |member: &Member| {
+            filters_of_the_property
+                .iter()
+                .all(|filter| filter.matches(member))
+        };
+        let mut n_draw = 0;
+        while !satisfies(member) && n_draw < MAX_DRAWS {
+            member
+                .properties
+                .insert(property.path.clone(), generate_a_value(property, rng)?);
+            n_draw += 1;
+        }
+        if satisfies(member) {
+            continue;
+        }
+        let sparql_converter =
+            NumberToSparqlConverter::new(property.out_of_range_strategy, property.temporal_format);
+        let bordering_value = bordering_numbers(&filters_of_the_property)
+            .into_iter()
+            .filter_map(|number| sparql_converter.convert(number, &property.value_type).ok())
+            .map(|value| Value {
+                value,
+                value_type: property.value_type.clone(),
+            })
+            .find(|value| {
+                let mut candidate = member.clone();
+                candidate
+                    .properties
+                    .insert(property.path.clone(), value.clone());
+                satisfies(&candidate)
+            })
+            .ok_or(
+                "the relations leading to a node leave no value to the properties of its members",
+            )?;
+        member
+            .properties
+            .insert(property.path.clone(), bordering_value);
+    }
+    Ok(())
+}
+
+/// Number of times a value is drawn before falling back to the values bordering the filters.
+const MAX_DRAWS: usize = 100;
+
+/// The numbers equal or next to the values of the comparisons and halfway between two of them.
+fn bordering_numbers(filters: &[&Filter]) -> Vec<f64> {
+    let values: Vec<f64> = filters
+        .iter()
+        .filter_map(|filter| match filter {
+            Filter::Comparison { value, .. } => value.value.parse::<f64>().ok(),
+            _ => None,
+        })
+        .filter(|value| value.is_finite())
+        .collect();
+    let mut numbers: Vec<f64> = values
+        .iter()
+        .flat_map(|value| [*value, value.floor() + 1.0, value.ceil() - 1.0])
+        .collect();
+    for (i, a) in values.iter().enumerate() {
+        numbers.extend(values[i + 1..].iter().map(|b| (a + b) / 2.0));
+    }
+    numbers
+}
+
+/// Draw the value of a property from `rng`.
+fn generate_a_value<T: num::ToPrimitive + Debug>(
+    property: &PropertyTemplate<T>,
+    rng: &mut dyn RngCore,
+) -> Result<Value, &'static str> {
+    let sparql_converter =
+        NumberToSparqlConverter::new(property.out_of_range_strategy, property.temporal_format);
+    Ok(Value {
+        value: sparql_converter.convert(property.range.next_with(rng), &property.value_type)?,
+        value_type: property.value_type.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{constrain_member, generate_a_member, generate_members};
+    use crate::generator_argument::member_argument::{
+        DistributionOfMember, MemberGeneratorArg, PropertyTemplate,
+    };
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::query::filter::{ComparisonOperator, Filter};
+    use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+    use crate::tree::value::{Value, ValueType};
+
+    fn a_property(path: &str, value_type: ValueType) -> PropertyTemplate<i32> {
+        PropertyTemplate {
+            path: String::from(path),
+            range: Box::new(RandomBoundedNumberRange::new(0, 10)),
+            value_type,
+            out_of_range_strategy: OutOfRangeStrategy::Reject,
+            temporal_format: TemporalFormat::default(),
+        }
+    }
+
+    #[test]
+    fn given_a_direct_distribution_should_return_members_with_every_property() {
+        let arg = MemberGeneratorArg {
+            properties: vec![
+                a_property("ex:a", ValueType::Int),
+                a_property("ex:b", ValueType::DateTime),
+            ],
+            distribution_of_member: DistributionOfMember::Direct(vec![2, 0, 3]),
+            versioning: None,
+        };
+
+        let members = generate_members(&arg, "https://example.com", 4).unwrap();
+
+        let n_members: Vec<usize> = members.iter().map(|v| v.len()).collect();
+        assert_eq!(n_members, vec![2, 0, 3, 0]);
+        for member in members.iter().flatten() {
+            assert!(member.url.starts_with("https://example.com/member/"));
+            assert_eq!(member.properties.len(), 2);
+            assert_eq!(member.properties["ex:a"].value_type, ValueType::Int);
+            assert_eq!(member.properties["ex:b"].value_type, ValueType::DateTime);
+        }
+    }
+
+    #[test]
+    fn given_an_incompatible_value_type_should_return_an_error() {
+        let arg = MemberGeneratorArg {
+            properties: vec![a_property("ex:a", ValueType::String)],
+            distribution_of_member: DistributionOfMember::Direct(vec![1]),
+            versioning: None,
+        };
+
+        generate_members(&arg, "https://example.com", 1)
+            .expect_err("a string cannot be generated from a number");
+    }
+
+    #[test]
+    fn should_constrain_the_values_on_the_path_of_the_filters() {
+        let properties = vec![
+            a_property("ex:a", ValueType::Int),
+            a_property("ex:b", ValueType::Int),
+        ];
+        let a_filter = |path: &str, operator, value: &str| Filter::Comparison {
+            path: String::from(path),
+            operator,
+            value: Value {
+                value: String::from(value),
+                value_type: ValueType::Int,
+            },
+        };
+        let mut rng = rand::thread_rng();
+        let mut member = generate_a_member(&properties, String::from("ex:m"), &mut rng).unwrap();
+        let b = member.properties["ex:b"].clone();
+
+        // no value of the range is greater than 50
+        constrain_member(
+            &mut member,
+            &properties,
+            &[
+                a_filter("ex:a", ComparisonOperator::GreaterThan, "5"),
+                a_filter("ex:a", ComparisonOperator::GreaterThan, "50"),
+                a_filter("ex:c", ComparisonOperator::Equal, "0"),
+            ],
+            &mut rng,
+        )
+        .unwrap();
+
+        assert_eq!(member.properties["ex:a"].value, "51");
+        assert_eq!(member.properties["ex:b"], b);
+        constrain_member(
+            &mut member,
+            &properties,
+            &[
+                a_filter("ex:b", ComparisonOperator::GreaterThan, "50"),
+                a_filter("ex:b", ComparisonOperator::LessThan, "20"),
+            ],
+            &mut rng,
+        )
+        .expect_err("no value is greater than 50 and lower than 20");
+    }
+}
@@ -0,0 +1,102 @@
+use super::tree::node::Node;
+use super::tree::relation::Relation;
+use std::collections::{HashSet, VecDeque};
+use std::vec::Vec;
+
+/// Assemble the [nodes](`Node`) from the [relations](`Relation`) of each node.
+/// The first set of relations belongs to the root node and the following sets are attributed
+/// to the nodes targeted by the relations in breadth-first order.
+/// A set of relations that cannot be attributed to a targeted node is given to a new node
+/// unreachable from the root and the targeted nodes without a set of relations are leaves.
+pub(super) fn generate_nodes(
+    relations: Vec<Vec<Relation>>,
+    root_url: &str,
+    base_url: &str,
+) -> Vec<Node> {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut known_url: HashSet<String> = HashSet::from([String::from(root_url)]);
+    let mut pending_url: VecDeque<String> = VecDeque::from([String::from(root_url)]);
+
+    for relations_of_the_node in relations {
+        let node_url = pending_url
+            .pop_front()
+            .unwrap_or_else(|| format!("{}/{}", base_url, uuid::Uuid::new_v4()));
+        for relation in relations_of_the_node.iter() {
+            if known_url.insert(relation.node().clone()) {
+                pending_url.push_back(relation.node().clone());
+            }
+        }
+        nodes.push(Node::new(relations_of_the_node, node_url, Vec::new()));
+    }
+
+    for node_url in pending_url {
+        nodes.push(Node::new(Vec::new(), node_url, Vec::new()));
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_nodes;
+    use crate::tree::relation::Relation;
+
+    fn a_relation(node: &str) -> Relation {
+        Relation::new(None, None, None, String::from(node), None)
+    }
+
+    #[test]
+    fn should_attribute_the_relations_in_breadth_first_order() {
+        let relations = vec![
+            vec![
+                a_relation("https://example.com/a"),
+                a_relation("https://example.com/b"),
+            ],
+            vec![a_relation("https://example.com/c")],
+            vec![],
+        ];
+
+        let nodes = generate_nodes(relations, "https://example.com/root", "https://example.com");
+
+        let urls: Vec<&String> = nodes.iter().map(|node| node.node_url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/root",
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://example.com/c"
+            ]
+        );
+        assert_eq!(nodes[0].relation().len(), 2);
+        assert_eq!(nodes[1].relation().len(), 1);
+        assert!(nodes[2].relation().is_empty());
+        assert!(nodes[3].relation().is_empty());
+    }
+
+    #[test]
+    fn should_not_create_a_node_twice_when_it_is_targeted_twice() {
+        let relations = vec![
+            vec![
+                a_relation("https://example.com/a"),
+                a_relation("https://example.com/a"),
+            ],
+            vec![a_relation("https://example.com/root")],
+        ];
+
+        let nodes = generate_nodes(relations, "https://example.com/root", "https://example.com");
+
+        assert_eq!(nodes.len(), 2);
+    }
+
+    #[test]
+    fn should_create_an_unreachable_node_when_no_node_is_targeted() {
+        let relations = vec![vec![], vec![a_relation("https://example.com/a")]];
+
+        let nodes = generate_nodes(relations, "https://example.com/root", "https://example.com");
+
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(nodes[0].node_url(), "https://example.com/root");
+        assert!(nodes[1].node_url().starts_with("https://example.com/"));
+        assert_eq!(nodes[2].node_url(), "https://example.com/a");
+    }
+}
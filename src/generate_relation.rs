@@ -1,14 +1,14 @@
 use super::generator_argument::relation_argument::{
-    DistributionOfRelation, RelationGeneratorArg, RelationTemplate, TemplateRangeVariationRelation,
+    DistributionOfRelation, LevelDistribution, PaginationArg, RelationGeneratorArg,
+    RelationTemplate, TemplateRangeVariationRelation, TemplateStringVariationRelation, TreeShape,
 };
 use super::generator_argument::RangeParameter;
-use super::sparql_converter::{NumberToSparqlConverter, SparqlConverter};
+use super::sparql_converter::{NumberToSparqlConverter, SparqlConverter, StringToSparqlConverter};
 use super::tree::relation::Relation;
 use super::tree::value::{Value, ValueType};
-use num;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::vec::Vec;
-use uuid;
 
 /// Generate the relations from the generator argument.
 pub(super) fn generate_relations<T: num::ToPrimitive + Debug>(
@@ -21,7 +21,51 @@ pub(super) fn generate_relations<T: num::ToPrimitive + Debug>(
         RelationGeneratorArg::ValueVariation(template) => {
             handle_the_distribution_of_the_relation(template, base_url)
         }
+
+        RelationGeneratorArg::StringVariation(template) => {
+            handle_the_distribution_of_the_string_relation(template, base_url)
+        }
+
+        RelationGeneratorArg::Pagination(pagination) => generate_pagination(pagination, base_url),
+
+        RelationGeneratorArg::StringIndex(_) => {
+            Err("the relations of a string index are generated with its nodes")
+        }
+
+        RelationGeneratorArg::Lazy(_) => {
+            Err("the relations of a lazily generated tree are generated with its nodes")
+        }
+    }
+}
+
+/// Generate a linked list of pages, each page linking to the next one and, when requested, to the previous one.
+fn generate_pagination(
+    pagination: &PaginationArg,
+    base_url: &String,
+) -> Result<Vec<Vec<Relation>>, &'static str> {
+    if pagination.n_page == 0 {
+        return Err("a pagination should have at least one page");
     }
+    let page_url = |i: usize| {
+        if i == 0 {
+            format!("{}/root", base_url)
+        } else {
+            format!("{}/page/{}", base_url, i)
+        }
+    };
+    let link = |i: usize| Relation::new(None, None, None, page_url(i), None);
+    Ok((0..pagination.n_page)
+        .map(|i| {
+            let mut relations: Vec<Relation> = Vec::new();
+            if i + 1 < pagination.n_page {
+                relations.push(link(i + 1));
+            }
+            if pagination.backward_links && i > 0 {
+                relations.push(link(i - 1));
+            }
+            relations
+        })
+        .collect())
 }
 
 /// Select the right function to handle the distribution of the relations.
@@ -29,50 +73,112 @@ fn handle_the_distribution_of_the_relation<T: num::ToPrimitive + Debug>(
     template: &TemplateRangeVariationRelation<T>,
     base_url: &String,
 ) -> Result<Vec<Vec<Relation>>, &'static str> {
-    let mut relations: Vec<Vec<Relation>> = Vec::new();
-    match &template.distribution_of_relation {
-        DistributionOfRelation::Direct(dist) => {
-            for n in dist.iter() {
-                match generate_n_relation_from_a_template(
-                    *n,
-                    &template.template,
-                    &base_url,
-                    template.value_type,
-                    template.range.as_ref(),
-                ) {
-                    Ok(v) => relations.push(v),
-                    Err(e) => return Err(e),
-                };
-            }
-            Ok(relations)
-        }
-
-        DistributionOfRelation::Random(range_fn, n) => {
-            for _ in 0..*n {
-                let n_relation: usize = range_fn.next();
-                match generate_n_relation_from_a_template(
-                    n_relation,
-                    &template.template,
-                    &base_url,
-                    template.value_type,
-                    template.range.as_ref(),
-                ) {
-                    Ok(v) => relations.push(v),
-                    Err(e) => return Err(e),
-                };
+    let sparql_converter =
+        NumberToSparqlConverter::new(template.out_of_range_strategy, template.temporal_format);
+    distribute_relations(
+        &template.template,
+        &template.distribution_of_relation,
+        base_url,
+        &template.value_type,
+        template.range.as_ref(),
+        &sparql_converter,
+    )
+}
+
+/// Handle the distribution of the relations whose value is a string.
+fn handle_the_distribution_of_the_string_relation(
+    template: &TemplateStringVariationRelation,
+    base_url: &String,
+) -> Result<Vec<Vec<Relation>>, &'static str> {
+    distribute_relations(
+        &template.template,
+        &template.distribution_of_relation,
+        base_url,
+        &template.value_type,
+        template.range.as_ref(),
+        &StringToSparqlConverter,
+    )
+}
+
+/// Generate the relations of every node following the distribution of the relations.
+fn distribute_relations<T>(
+    template_relation: &RelationTemplate,
+    distribution_of_relation: &DistributionOfRelation,
+    base_url: &String,
+    value_type: &ValueType,
+    range_value_fn: &dyn RangeParameter<T>,
+    sparql_converter: &dyn SparqlConverter<T>,
+) -> Result<Vec<Vec<Relation>>, &'static str> {
+    let n_relation_by_node: Vec<usize> = match distribution_of_relation {
+        DistributionOfRelation::Direct(dist) => dist.clone(),
+        DistributionOfRelation::Random(range_fn, n) => (0..*n).map(|_| range_fn.next()).collect(),
+        DistributionOfRelation::ByLevel(level_distribution) => fan_out_by_node(level_distribution)?,
+    };
+    n_relation_by_node
+        .into_iter()
+        .map(|n_relation| {
+            generate_n_relation_from_a_template(
+                n_relation,
+                template_relation,
+                base_url,
+                value_type,
+                range_value_fn,
+                sparql_converter,
+            )
+        })
+        .collect()
+}
+
+/// Maximum number of nodes of a tree distributed by level, so that a deep tree with a large
+/// fan-out is rejected instead of exhausting the memory.
+const MAX_NODES: usize = 1_000_000;
+
+/// The number of relation of every node of the tree in breadth-first order, the leaves included.
+/// Return an error when the tree would have more than `MAX_NODES` nodes.
+fn fan_out_by_node(level_distribution: &LevelDistribution) -> Result<Vec<usize>, &'static str> {
+    let fan_out_by_level = &level_distribution.fan_out_by_level;
+    if fan_out_by_level.is_empty() {
+        return Err("the fan-out of at least one level should be defined");
+    }
+    let mut fan_outs: Vec<usize> = Vec::new();
+    let mut balanced_fan_outs: Vec<usize> = Vec::new();
+    // the depth of the pending nodes and whether they can have children
+    let mut pending: VecDeque<(usize, bool)> = VecDeque::from([(0, true)]);
+    while let Some((depth, can_have_children)) = pending.pop_front() {
+        if depth >= level_distribution.max_depth || !can_have_children {
+            fan_outs.push(0);
+            continue;
+        }
+        let range = &fan_out_by_level[depth.min(fan_out_by_level.len() - 1)];
+        let fan_out = match level_distribution.shape {
+            TreeShape::Balanced => {
+                if balanced_fan_outs.len() == depth {
+                    balanced_fan_outs.push(range.next());
+                }
+                balanced_fan_outs[depth]
             }
-            Ok(relations)
+            TreeShape::Unbalanced | TreeShape::Skewed => range.next(),
+        };
+        fan_outs.push(fan_out);
+        if fan_outs.len() + pending.len() + fan_out > MAX_NODES {
+            return Err("the tree distributed by level has too many nodes");
+        }
+        for i in 0..fan_out {
+            let can_have_children = level_distribution.shape != TreeShape::Skewed || i == 0;
+            pending.push_back((depth + 1, can_have_children));
         }
     }
+    Ok(fan_outs)
 }
 
 /// Helper function to avoid repetition to generate n relation from a template
-fn generate_n_relation_from_a_template<T: num::ToPrimitive + Debug>(
+fn generate_n_relation_from_a_template<T>(
     n: usize,
     template_relation: &RelationTemplate,
     base_url: &String,
-    value_type: ValueType,
+    value_type: &ValueType,
     range_value_fn: &dyn RangeParameter<T>,
+    sparql_converter: &dyn SparqlConverter<T>,
 ) -> Result<Vec<Relation>, &'static str> {
     let mut current_relation: Vec<Relation> = Vec::new();
     for _ in 0..n {
@@ -81,7 +187,7 @@ fn generate_n_relation_from_a_template<T: num::ToPrimitive + Debug>(
             base_url,
             value_type,
             range_value_fn,
-            &NumberToSparqlConverter,
+            sparql_converter,
         ) {
             Ok(v) => current_relation.push(v),
             Err(e) => return Err(e),
@@ -91,10 +197,10 @@ fn generate_n_relation_from_a_template<T: num::ToPrimitive + Debug>(
 }
 
 /// Generate the single relation from the template and the [range generator](`RangeParameter`)
-fn generate_a_relation_from_template<T: num::ToPrimitive + Debug>(
+fn generate_a_relation_from_template<T>(
     template_relation: &RelationTemplate,
     base_url: &String,
-    value_type: ValueType,
+    value_type: &ValueType,
     range_value_fn: &dyn RangeParameter<T>,
     sparql_converter: &dyn SparqlConverter<T>,
 ) -> Result<Relation, &'static str> {
@@ -102,7 +208,7 @@ fn generate_a_relation_from_template<T: num::ToPrimitive + Debug>(
     let relation_value = match sparql_converter.convert(value, value_type) {
         Ok(v) => Value {
             value: v,
-            value_type: value_type,
+            value_type: value_type.clone(),
         },
         Err(e) => return Err(e),
     };
@@ -132,7 +238,6 @@ mod tests {
         use crate::tree::relation_operator::RelationOperator;
         use crate::tree::shacl_path::ShaclPath;
         use crate::tree::value::ValueType;
-        use lazy_static;
         use std::collections::HashSet;
 
         use super::super::generate_a_relation_from_template;
@@ -154,7 +259,7 @@ mod tests {
             let relation = generate_a_relation_from_template(
                 &A_TEMPLATE_RELATION,
                 &A_BASE_URL,
-                value_type,
+                &value_type,
                 &MockRangeGenerator { val: 8 },
                 &MockSparqlConverter { success: true },
             )
@@ -171,7 +276,7 @@ mod tests {
             generate_a_relation_from_template(
                 &A_TEMPLATE_RELATION,
                 &A_BASE_URL,
-                value_type,
+                &value_type,
                 &MockRangeGenerator { val: 8 },
                 &MockSparqlConverter { success: false },
             )
@@ -189,12 +294,12 @@ mod tests {
                 let relation = generate_a_relation_from_template(
                     &A_TEMPLATE_RELATION,
                     &A_BASE_URL,
-                    value_type,
+                    &value_type,
                     &MockRangeGenerator { val: i },
                     &MockSparqlConverter { success: true },
                 )
                 .unwrap();
-                assert!(used_url.get(relation.node()).is_none());
+                assert!(!used_url.contains(relation.node()));
                 used_url.insert(relation.node().clone());
             }
         }
@@ -205,10 +310,10 @@ mod tests {
         use super::super::generate_n_relation_from_a_template;
         use super::MockRangeGenerator;
         use crate::generator_argument::relation_argument::RelationTemplate;
+        use crate::sparql_converter::NumberToSparqlConverter;
         use crate::tree::relation_operator::RelationOperator;
         use crate::tree::shacl_path::ShaclPath;
         use crate::tree::value::ValueType;
-        use lazy_static;
 
         lazy_static::lazy_static! {
             static ref A_PATH: ShaclPath = String::from("ex:path");
@@ -227,8 +332,9 @@ mod tests {
                 n,
                 &A_TEMPLATE_RELATION,
                 &A_BASE_URL,
-                value_type,
+                &value_type,
                 &MockRangeGenerator { val: 3 },
+                &NumberToSparqlConverter::default(),
             )
             .unwrap();
             assert_eq!(response.len(), n);
@@ -248,8 +354,9 @@ mod tests {
                 n,
                 &A_TEMPLATE_RELATION,
                 &A_BASE_URL,
-                value_type,
+                &value_type,
                 &MockRangeGenerator { val: 3 },
+                &NumberToSparqlConverter::default(),
             )
             .expect_err("should return an error because the value type are not compatible");
         }
@@ -261,10 +368,10 @@ mod tests {
         use crate::generate_relation::TemplateRangeVariationRelation;
         use crate::generator_argument::relation_argument::DistributionOfRelation;
         use crate::generator_argument::relation_argument::RelationTemplate;
+        use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
         use crate::tree::relation_operator::RelationOperator;
         use crate::tree::shacl_path::ShaclPath;
         use crate::tree::value::ValueType;
-        use lazy_static;
 
         lazy_static::lazy_static! {
             static ref A_PATH: ShaclPath = String::from("ex:path");
@@ -286,7 +393,9 @@ mod tests {
                 distribution_of_relation: DistributionOfRelation::Direct(
                     distribution_of_relation.clone(),
                 ),
-                value_type: value_type,
+                value_type,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
 
             let response =
@@ -310,7 +419,9 @@ mod tests {
                 distribution_of_relation: DistributionOfRelation::Direct(
                     distribution_of_relation.clone(),
                 ),
-                value_type: value_type,
+                value_type,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
 
             handle_the_distribution_of_the_relation(&template_arg, &A_BASE_URL)
@@ -330,7 +441,9 @@ mod tests {
                     }),
                     n,
                 ),
-                value_type: value_type,
+                value_type,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
 
             let response =
@@ -348,7 +461,7 @@ mod tests {
         ) {
             let n: usize = 30;
             let range_generator_val = 2;
-            let value_type = ValueType::UnsignedByte;
+            let value_type = ValueType::String;
             let template_arg = TemplateRangeVariationRelation {
                 template: A_TEMPLATE_RELATION.clone(),
                 range: Box::new(MockRangeGenerator { val: 2 }),
@@ -358,7 +471,9 @@ mod tests {
                     }),
                     n,
                 ),
-                value_type: value_type,
+                value_type,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
 
             handle_the_distribution_of_the_relation(&template_arg, &A_BASE_URL).expect_err(
@@ -367,11 +482,246 @@ mod tests {
         }
     }
 
+    mod tests_generate_pagination {
+        use super::super::generate_pagination;
+        use crate::generator_argument::relation_argument::PaginationArg;
+
+        fn targets(relations: &[Vec<crate::tree::relation::Relation>]) -> Vec<Vec<&str>> {
+            relations
+                .iter()
+                .map(|page| {
+                    page.iter()
+                        .map(|relation| relation.node().as_str())
+                        .collect()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn given_a_pagination_should_link_the_pages() {
+            let pagination = PaginationArg {
+                n_page: 3,
+                page_size: 10,
+                backward_links: false,
+            };
+
+            let relations =
+                generate_pagination(&pagination, &String::from("https://example.com")).unwrap();
+
+            assert_eq!(
+                targets(&relations),
+                vec![
+                    vec!["https://example.com/page/1"],
+                    vec!["https://example.com/page/2"],
+                    vec![]
+                ]
+            );
+            for relation in relations.iter().flatten() {
+                assert!(relation.path().is_none());
+                assert!(relation.value().is_none());
+                assert!(relation.relation_type().is_none());
+            }
+        }
+
+        #[test]
+        fn given_backward_links_should_link_the_previous_page() {
+            let pagination = PaginationArg {
+                n_page: 3,
+                page_size: 10,
+                backward_links: true,
+            };
+
+            let relations =
+                generate_pagination(&pagination, &String::from("https://example.com")).unwrap();
+
+            assert_eq!(
+                targets(&relations),
+                vec![
+                    vec!["https://example.com/page/1"],
+                    vec!["https://example.com/page/2", "https://example.com/root"],
+                    vec!["https://example.com/page/1"]
+                ]
+            );
+        }
+
+        #[test]
+        fn given_no_page_should_return_an_error() {
+            let pagination = PaginationArg {
+                n_page: 0,
+                page_size: 10,
+                backward_links: true,
+            };
+
+            generate_pagination(&pagination, &String::from("https://example.com"))
+                .expect_err("should return an error without page");
+        }
+    }
+
+    mod tests_fan_out_by_node {
+        use super::super::fan_out_by_node;
+        use super::MockRangeGenerator;
+        use crate::generator_argument::relation_argument::{LevelDistribution, TreeShape};
+        use crate::generator_argument::RangeParameter;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Return 1, 2, 3, ...
+        struct Counter(AtomicUsize);
+
+        impl RangeParameter<usize> for Counter {
+            fn next(&self) -> usize {
+                self.0.fetch_add(1, Ordering::SeqCst) + 1
+            }
+        }
+
+        fn a_counter_distribution(shape: TreeShape) -> LevelDistribution {
+            LevelDistribution {
+                fan_out_by_level: vec![Box::new(Counter(AtomicUsize::new(0)))],
+                max_depth: 3,
+                shape,
+            }
+        }
+
+        #[test]
+        fn given_a_fan_out_by_level_should_reuse_the_last_one_until_the_max_depth() {
+            let level_distribution = LevelDistribution {
+                fan_out_by_level: vec![
+                    Box::new(MockRangeGenerator { val: 2 }),
+                    Box::new(MockRangeGenerator { val: 3 }),
+                ],
+                max_depth: 3,
+                shape: TreeShape::Balanced,
+            };
+
+            let fan_outs = fan_out_by_node(&level_distribution).unwrap();
+
+            // the root, its 2 children, their 6 children and the 18 leaves
+            assert_eq!(fan_outs.len(), 27);
+            assert_eq!(fan_outs[..9], [2, 3, 3, 3, 3, 3, 3, 3, 3]);
+            assert!(fan_outs[9..].iter().all(|n| *n == 0));
+        }
+
+        #[test]
+        fn given_a_balanced_shape_should_draw_the_fan_out_once_by_level() {
+            assert_eq!(
+                fan_out_by_node(&a_counter_distribution(TreeShape::Balanced)).unwrap(),
+                vec![1, 2, 3, 3, 0, 0, 0, 0, 0, 0]
+            );
+        }
+
+        #[test]
+        fn given_an_unbalanced_shape_should_draw_the_fan_out_of_every_node() {
+            assert_eq!(
+                fan_out_by_node(&a_counter_distribution(TreeShape::Unbalanced)).unwrap(),
+                vec![1, 2, 3, 4, 0, 0, 0, 0, 0, 0, 0]
+            );
+        }
+
+        #[test]
+        fn given_a_skewed_shape_should_only_expand_the_first_child() {
+            let level_distribution = LevelDistribution {
+                fan_out_by_level: vec![Box::new(MockRangeGenerator { val: 2 })],
+                max_depth: 3,
+                shape: TreeShape::Skewed,
+            };
+
+            assert_eq!(
+                fan_out_by_node(&level_distribution).unwrap(),
+                vec![2, 2, 0, 2, 0, 0, 0]
+            );
+        }
+
+        #[test]
+        fn given_no_fan_out_should_return_an_error() {
+            let level_distribution = LevelDistribution {
+                fan_out_by_level: Vec::new(),
+                max_depth: 3,
+                shape: TreeShape::Balanced,
+            };
+
+            fan_out_by_node(&level_distribution)
+                .expect_err("should return an error when no fan-out is defined");
+        }
+
+        #[test]
+        fn given_too_many_nodes_should_return_an_error() {
+            let level_distribution = LevelDistribution {
+                fan_out_by_level: vec![Box::new(MockRangeGenerator { val: 2 })],
+                max_depth: 64,
+                shape: TreeShape::Balanced,
+            };
+
+            fan_out_by_node(&level_distribution)
+                .expect_err("should return an error when the tree has too many nodes");
+        }
+    }
+
+    mod tests_handle_the_distribution_of_the_string_relation {
+        use super::super::handle_the_distribution_of_the_string_relation;
+        use super::MockRangeGenerator;
+        use crate::generator_argument::relation_argument::{
+            DistributionOfRelation, RelationTemplate, TemplateStringVariationRelation,
+        };
+        use crate::tree::relation_operator::RelationOperator;
+        use crate::tree::value::ValueType;
+
+        lazy_static::lazy_static! {
+            static ref A_TEMPLATE_RELATION: RelationTemplate = RelationTemplate {
+                path: String::from("ex:path"),
+                relation_type: RelationOperator::EqualThanRelation
+            };
+            static ref A_BASE_URL: String = String::from("https://example.com");
+        }
+
+        #[test]
+        fn given_a_string_range_should_return_relations_with_string_values() {
+            for (value_type, value) in [
+                (ValueType::Iri, "https://example.com/a"),
+                (ValueType::LangString(String::from("en-GB")), "a"),
+            ] {
+                let template_arg = TemplateStringVariationRelation {
+                    template: A_TEMPLATE_RELATION.clone(),
+                    range: Box::new(MockRangeGenerator {
+                        val: String::from(value),
+                    }),
+                    distribution_of_relation: DistributionOfRelation::Direct(vec![2, 1]),
+                    value_type: value_type.clone(),
+                };
+
+                let response =
+                    handle_the_distribution_of_the_string_relation(&template_arg, &A_BASE_URL)
+                        .unwrap();
+
+                assert_eq!(response.len(), 2);
+                assert_eq!(response[0].len(), 2);
+                for relation in response.iter().flatten() {
+                    let relation_value = relation.value().clone().unwrap();
+                    assert_eq!(relation_value.value_type, value_type);
+                    assert_eq!(relation_value.value, value);
+                }
+            }
+        }
+
+        #[test]
+        fn given_an_invalid_string_should_return_an_error() {
+            let template_arg = TemplateStringVariationRelation {
+                template: A_TEMPLATE_RELATION.clone(),
+                range: Box::new(MockRangeGenerator {
+                    val: String::from("a b"),
+                }),
+                distribution_of_relation: DistributionOfRelation::Direct(vec![1]),
+                value_type: ValueType::Iri,
+            };
+
+            handle_the_distribution_of_the_string_relation(&template_arg, &A_BASE_URL)
+                .expect_err("should return an error when the string is not a valid IRI");
+        }
+    }
+
     pub struct MockRangeGenerator<T> {
         pub val: T,
     }
 
-    impl<T: Clone> RangeParameter<T> for MockRangeGenerator<T> {
+    impl<T: Clone + Send + Sync> RangeParameter<T> for MockRangeGenerator<T> {
         fn next(&self) -> T {
             self.val.clone()
         }
@@ -385,7 +735,7 @@ mod tests {
         fn convert(
             &self,
             _number_value: T,
-            _value_type: ValueType,
+            _value_type: &ValueType,
         ) -> Result<String, &'static str> {
             if self.success {
                 Ok(String::from("valid"))
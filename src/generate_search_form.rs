@@ -0,0 +1,183 @@
+use super::tree::node::Node;
+use super::tree::relation::Relation;
+use super::tree::search_form::SearchForm;
+use std::collections::{HashMap, HashSet};
+use std::vec::Vec;
+
+/// Rename the nodes reached by a relation over the property of the search form, so that
+/// their url is the expansion of the template with the value of the relation.
+/// A node reached by several relations, or whose value leads to several nodes, keeps its url
+/// because the template could not identify it.
+pub(super) fn apply_search_form(nodes: Vec<Node>, search_form: &SearchForm) -> Vec<Node> {
+    let mut values_of_the_target: HashMap<&String, Vec<&String>> = HashMap::new();
+    let mut targets_of_the_value: HashMap<&String, HashSet<&String>> = HashMap::new();
+    for relation in nodes.iter().flat_map(|node| node.relation().iter()) {
+        let value = match (relation.path(), relation.value()) {
+            (Some(path), Some(value)) if path == search_form.property() => &value.value,
+            _ => continue,
+        };
+        values_of_the_target
+            .entry(relation.node())
+            .or_default()
+            .push(value);
+        targets_of_the_value
+            .entry(value)
+            .or_default()
+            .insert(relation.node());
+    }
+    let inbound_relations = count_inbound_relations(&nodes);
+
+    let existing_url: HashSet<&String> = nodes.iter().map(|node| node.node_url()).collect();
+    let mut new_url: HashMap<String, String> = HashMap::new();
+    for (target, values) in values_of_the_target.iter() {
+        let value = values[0];
+        let url = search_form.expand(value);
+        if values.len() == 1
+            && inbound_relations.get(target) == Some(&1)
+            && targets_of_the_value[value].len() == 1
+            && !existing_url.contains(&url)
+        {
+            new_url.insert((*target).clone(), url);
+        }
+    }
+
+    let rename = |url: &String| new_url.get(url).unwrap_or(url).clone();
+    nodes
+        .iter()
+        .map(|node| {
+            let relations = node
+                .relation()
+                .iter()
+                .map(|relation| {
+                    Relation::new(
+                        *relation.remaning_items(),
+                        relation.path().clone(),
+                        relation.value().clone(),
+                        rename(relation.node()),
+                        relation.relation_type().clone(),
+                    )
+                })
+                .collect();
+            Node::new(relations, rename(node.node_url()), node.members().clone())
+        })
+        .collect()
+}
+
+/// Count the number of relations leading to each node.
+fn count_inbound_relations(nodes: &[Node]) -> HashMap<&String, usize> {
+    let mut inbound_relations: HashMap<&String, usize> = HashMap::new();
+    for relation in nodes.iter().flat_map(|node| node.relation().iter()) {
+        *inbound_relations.entry(relation.node()).or_default() += 1;
+    }
+    inbound_relations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_search_form;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::search_form::SearchForm;
+    use crate::tree::value::{Value, ValueType};
+
+    fn a_relation(path: &str, value: &str, node: &str) -> Relation {
+        Relation::new(
+            None,
+            Some(String::from(path)),
+            Some(Value {
+                value: String::from(value),
+                value_type: ValueType::Int,
+            }),
+            String::from(node),
+            Some(RelationOperator::GreaterThanRelation),
+        )
+    }
+
+    fn a_search_form() -> SearchForm {
+        SearchForm::new(
+            String::from("https://example.com/node"),
+            String::from("value"),
+            String::from("ex:p"),
+        )
+    }
+
+    #[test]
+    fn should_rename_the_nodes_reached_by_a_relation_over_the_property() {
+        let nodes = vec![
+            Node::new(
+                vec![
+                    a_relation("ex:p", "1", "https://example.com/a"),
+                    a_relation("ex:other", "2", "https://example.com/b"),
+                ],
+                String::from("https://example.com/root"),
+                Vec::new(),
+            ),
+            Node::new(
+                Vec::new(),
+                String::from("https://example.com/a"),
+                Vec::new(),
+            ),
+            Node::new(
+                Vec::new(),
+                String::from("https://example.com/b"),
+                Vec::new(),
+            ),
+        ];
+
+        let nodes = apply_search_form(nodes, &a_search_form());
+
+        assert_eq!(nodes[0].node_url(), "https://example.com/root");
+        assert_eq!(nodes[1].node_url(), "https://example.com/node?value=1");
+        assert_eq!(nodes[2].node_url(), "https://example.com/b");
+        assert_eq!(
+            nodes[0].relation()[0].node(),
+            "https://example.com/node?value=1"
+        );
+        assert_eq!(nodes[0].relation()[1].node(), "https://example.com/b");
+    }
+
+    #[test]
+    fn should_not_rename_the_nodes_when_the_value_is_ambiguous() {
+        let nodes = vec![
+            Node::new(
+                vec![
+                    a_relation("ex:p", "1", "https://example.com/a"),
+                    a_relation("ex:p", "1", "https://example.com/b"),
+                    a_relation("ex:p", "2", "https://example.com/c"),
+                    a_relation("ex:p", "3", "https://example.com/c"),
+                ],
+                String::from("https://example.com/root"),
+                Vec::new(),
+            ),
+            Node::new(
+                Vec::new(),
+                String::from("https://example.com/a"),
+                Vec::new(),
+            ),
+            Node::new(
+                Vec::new(),
+                String::from("https://example.com/b"),
+                Vec::new(),
+            ),
+            Node::new(
+                Vec::new(),
+                String::from("https://example.com/c"),
+                Vec::new(),
+            ),
+        ];
+
+        let nodes = apply_search_form(nodes, &a_search_form());
+
+        let urls: Vec<&String> = nodes.iter().map(|node| node.node_url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/root",
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://example.com/c"
+            ]
+        );
+    }
+}
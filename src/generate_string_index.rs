@@ -0,0 +1,293 @@
+use super::generator_argument::string_index_argument::{
+    StringIndexArg, StringIndexStrategy, StringSource,
+};
+use super::tree::member::Member;
+use super::tree::node::Node;
+use super::tree::relation::Relation;
+use super::tree::relation_operator::RelationOperator;
+use super::tree::shacl_path::ShaclPath;
+use super::tree::value::{Value, ValueType};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::vec::Vec;
+
+/// Generate the nodes of a string index with their members, the root being `{base_url}/root`,
+/// the other nodes `{base_url}/suffix/{i}` or `{base_url}/ngram/{i}` and the members `{base_url}/member/{i}`.
+/// Every member reachable through a relation satisfies the relation and the `tree:remainingItems`
+/// of the relation is the number of those members.
+pub(super) fn generate_string_index(
+    index_args: &StringIndexArg,
+    base_url: &str,
+) -> Result<Vec<Node>, &'static str> {
+    let strings = indexed_strings(&index_args.strings)?;
+    let members: Vec<Member> = strings
+        .iter()
+        .enumerate()
+        .map(|(i, string)| Member {
+            url: format!("{}/member/{}", base_url, i),
+            properties: HashMap::from([(index_args.path.clone(), a_string(string))]),
+        })
+        .collect();
+    let index = StringIndex {
+        path: &index_args.path,
+        strings: &strings,
+        members: &members,
+        base_url,
+    };
+    match index_args.strategy {
+        StringIndexStrategy::SuffixTrie { capacity } => Ok(index.suffix_trie(capacity)),
+        StringIndexStrategy::NGram { n } => index.n_gram(n),
+    }
+}
+
+/// The strings supplied by the user or drawn at random.
+fn indexed_strings(source: &StringSource) -> Result<Vec<String>, &'static str> {
+    match source {
+        StringSource::Supplied(strings) => Ok(strings.clone()),
+        StringSource::Random {
+            n_string,
+            length,
+            alphabet,
+            seed,
+        } => {
+            if alphabet.is_empty() {
+                return Err("the alphabet of the strings should not be empty");
+            }
+            let mut rng = StdRng::seed_from_u64(*seed);
+            Ok((0..*n_string)
+                .map(|_| {
+                    let length = length.next_with(&mut rng);
+                    (0..length)
+                        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+                        .collect()
+                })
+                .collect())
+        }
+    }
+}
+
+fn a_string(value: &str) -> Value {
+    Value {
+        value: String::from(value),
+        value_type: ValueType::String,
+    }
+}
+
+/// The members to index, the member `i` holding the string `i`.
+struct StringIndex<'a> {
+    path: &'a ShaclPath,
+    strings: &'a [String],
+    members: &'a [Member],
+    base_url: &'a str,
+}
+
+impl StringIndex<'_> {
+    fn relation(
+        &self,
+        operator: RelationOperator,
+        value: &str,
+        node_url: &str,
+        n_member: usize,
+    ) -> Relation {
+        Relation::new(
+            Some(n_member as i32),
+            Some(self.path.clone()),
+            Some(a_string(value)),
+            String::from(node_url),
+            Some(operator),
+        )
+    }
+
+    fn members_of(&self, indexes: &[usize]) -> Vec<Member> {
+        indexes.iter().map(|i| self.members[*i].clone()).collect()
+    }
+
+    /// A node holds the members ending with its suffix when there are no more than `capacity` of them,
+    /// otherwise it keeps the members equal to its suffix and has a child for every longer suffix.
+    fn suffix_trie(&self, capacity: usize) -> Vec<Node> {
+        let chars: Vec<Vec<char>> = self.strings.iter().map(|v| v.chars().collect()).collect();
+        let mut nodes: Vec<Node> = Vec::new();
+        let mut n_node: usize = 0;
+        // the url of the node, its suffix in reverse order and the members ending with it
+        let mut pending: VecDeque<(String, Vec<char>, Vec<usize>)> = VecDeque::from([(
+            format!("{}/root", self.base_url),
+            Vec::new(),
+            (0..self.strings.len()).collect(),
+        )]);
+        while let Some((node_url, reversed_suffix, indexes)) = pending.pop_front() {
+            if indexes.len() <= capacity {
+                nodes.push(Node::new(Vec::new(), node_url, self.members_of(&indexes)));
+                continue;
+            }
+            let mut kept: Vec<usize> = Vec::new();
+            let mut children: BTreeMap<char, Vec<usize>> = BTreeMap::new();
+            for i in indexes {
+                let string = &chars[i];
+                if string.len() == reversed_suffix.len() {
+                    kept.push(i);
+                } else {
+                    let next = string[string.len() - reversed_suffix.len() - 1];
+                    children.entry(next).or_default().push(i);
+                }
+            }
+            let mut relations: Vec<Relation> = Vec::new();
+            for (next, indexes_of_the_child) in children {
+                n_node += 1;
+                let child_url = format!("{}/suffix/{}", self.base_url, n_node);
+                let mut child_suffix = reversed_suffix.clone();
+                child_suffix.push(next);
+                let suffix: String = child_suffix.iter().rev().collect();
+                relations.push(self.relation(
+                    RelationOperator::SuffixRelation,
+                    &suffix,
+                    &child_url,
+                    indexes_of_the_child.len(),
+                ));
+                pending.push_back((child_url, child_suffix, indexes_of_the_child));
+            }
+            nodes.push(Node::new(relations, node_url, self.members_of(&kept)));
+        }
+        nodes
+    }
+
+    /// The root links to a node for every n-gram, a member being in the node of each of its n-grams.
+    fn n_gram(&self, n: usize) -> Result<Vec<Node>, &'static str> {
+        if n == 0 {
+            return Err("the n-grams should have at least one character");
+        }
+        let mut short_strings: Vec<usize> = Vec::new();
+        let mut n_grams: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (i, string) in self.strings.iter().enumerate() {
+            let chars: Vec<char> = string.chars().collect();
+            if chars.len() < n {
+                short_strings.push(i);
+                continue;
+            }
+            for window in chars.windows(n) {
+                let indexes = n_grams.entry(window.iter().collect()).or_default();
+                if indexes.last() != Some(&i) {
+                    indexes.push(i);
+                }
+            }
+        }
+
+        let mut relations: Vec<Relation> = Vec::new();
+        let mut leaves: Vec<Node> = Vec::new();
+        for (i, (n_gram, indexes)) in n_grams.iter().enumerate() {
+            let node_url = format!("{}/ngram/{}", self.base_url, i + 1);
+            relations.push(self.relation(
+                RelationOperator::SubstringRelation,
+                n_gram,
+                &node_url,
+                indexes.len(),
+            ));
+            leaves.push(Node::new(Vec::new(), node_url, self.members_of(indexes)));
+        }
+        let root = Node::new(
+            relations,
+            format!("{}/root", self.base_url),
+            self.members_of(&short_strings),
+        );
+        Ok(std::iter::once(root).chain(leaves).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_string_index;
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::generator_argument::string_index_argument::{
+        StringIndexArg, StringIndexStrategy, StringSource,
+    };
+    use crate::tree::node::Node;
+    use crate::validator::validate;
+
+    const ROOT_URL: &str = "https://example.com/root";
+
+    fn an_index(strings: &[&str], strategy: StringIndexStrategy) -> Vec<Node> {
+        generate_string_index(
+            &StringIndexArg {
+                path: String::from("ex:name"),
+                strings: StringSource::Supplied(strings.iter().map(|v| String::from(*v)).collect()),
+                strategy,
+            },
+            "https://example.com",
+        )
+        .unwrap()
+    }
+
+    fn relation_values(node: &Node) -> Vec<&str> {
+        node.relation()
+            .iter()
+            .map(|relation| relation.value().as_ref().unwrap().value.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_build_a_suffix_trie() {
+        let nodes = an_index(
+            &["ring", "sing", "song", "g", "sea"],
+            StringIndexStrategy::SuffixTrie { capacity: 2 },
+        );
+
+        assert_eq!(relation_values(&nodes[0]), vec!["a", "g"]);
+        // "g" holds the member "g" and has the children "ng"
+        assert_eq!(relation_values(&nodes[2]), vec!["ng"]);
+        assert_eq!(nodes[2].members().len(), 1);
+        assert_eq!(relation_values(&nodes[3]), vec!["ing", "ong"]);
+        assert_eq!(
+            nodes.iter().map(|node| node.members().len()).sum::<usize>(),
+            5
+        );
+        assert!(validate(&nodes, ROOT_URL, true).is_valid());
+    }
+
+    #[test]
+    fn should_build_an_n_gram_index() {
+        let nodes = an_index(&["abc", "bcd", "a"], StringIndexStrategy::NGram { n: 2 });
+
+        assert_eq!(relation_values(&nodes[0]), vec!["ab", "bc", "cd"]);
+        assert_eq!(nodes[0].members().len(), 1);
+        assert_eq!(nodes[2].members().len(), 2);
+        assert!(validate(&nodes, ROOT_URL, true).is_valid());
+    }
+
+    #[test]
+    fn should_index_random_strings() {
+        for strategy in [
+            StringIndexStrategy::SuffixTrie { capacity: 3 },
+            StringIndexStrategy::NGram { n: 3 },
+        ] {
+            let nodes = generate_string_index(
+                &StringIndexArg {
+                    path: String::from("ex:name"),
+                    strings: StringSource::Random {
+                        n_string: 50,
+                        length: Box::new(RandomBoundedNumberRange::new(1, 8)),
+                        alphabet: vec!['a', 'b', 'c', 'é'],
+                        seed: 3,
+                    },
+                    strategy,
+                },
+                "https://example.com",
+            )
+            .unwrap();
+
+            assert!(validate(&nodes, ROOT_URL, true).is_valid());
+        }
+    }
+
+    #[test]
+    fn should_return_an_error_for_an_empty_n_gram() {
+        assert!(generate_string_index(
+            &StringIndexArg {
+                path: String::from("ex:name"),
+                strings: StringSource::Supplied(Vec::new()),
+                strategy: StringIndexStrategy::NGram { n: 0 },
+            },
+            "https://example.com",
+        )
+        .is_err());
+    }
+}
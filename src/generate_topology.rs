@@ -0,0 +1,258 @@
+use super::generator_argument::topology_argument::TopologyArg;
+use super::tree::node::Node;
+use super::tree::relation::Relation;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+
+/// Add relations to the tree of nodes following the [topology argument](`TopologyArg`).
+/// The breadth-first order and the parents are the ones of the tree before the addition, so a shared
+/// child or a sibling always comes after the node linking to it and only the back links create cycles.
+pub(super) fn apply_topology(
+    nodes: Vec<Node>,
+    root_url: &str,
+    topology: &TopologyArg,
+) -> Result<Vec<Node>, &'static str> {
+    let probabilities = [
+        topology.shared_child_probability,
+        topology.cycle_probability,
+        topology.sibling_link_probability,
+    ];
+    if probabilities
+        .iter()
+        .any(|probability| !(0.0..=1.0).contains(probability))
+    {
+        return Err("the probabilities of the topology should be between 0 and 1");
+    }
+    let mut rng = StdRng::seed_from_u64(topology.seed);
+    let (order, parent_of) = breadth_first_order(&nodes, root_url);
+    let nodes_by_url: HashMap<&str, &Node> = nodes
+        .iter()
+        .map(|node| (node.node_url().as_str(), node))
+        .collect();
+
+    let mut links: HashSet<(&str, &str)> = nodes
+        .iter()
+        .flat_map(|node| {
+            node.relation()
+                .iter()
+                .map(|relation| (node.node_url().as_str(), relation.node().as_str()))
+        })
+        .collect();
+    let mut shared_children = Vec::new();
+    for (j, url) in order.iter().enumerate().skip(1) {
+        if rng.gen_bool(topology.shared_child_probability) {
+            shared_children.push((order[rng.gen_range(0..j)], *url));
+        }
+    }
+
+    let mut back_links = Vec::new();
+    for url in order.iter() {
+        if !nodes_by_url[url].relation().is_empty() || !rng.gen_bool(topology.cycle_probability) {
+            continue;
+        }
+        let mut ancestors: Vec<&str> = Vec::new();
+        let mut current = parent_of.get(url);
+        while let Some(ancestor) = current {
+            ancestors.push(ancestor);
+            current = parent_of.get(ancestor);
+        }
+        if !ancestors.is_empty() {
+            back_links.push((*url, ancestors[rng.gen_range(0..ancestors.len())]));
+        }
+    }
+
+    let mut sibling_links = Vec::new();
+    for url in order.iter() {
+        let mut children: Vec<&str> = Vec::new();
+        for relation in nodes_by_url[url].relation() {
+            let child = relation.node().as_str();
+            if parent_of.get(child) == Some(url) && !children.contains(&child) {
+                children.push(child);
+            }
+        }
+        for siblings in children.windows(2) {
+            if rng.gen_bool(topology.sibling_link_probability) {
+                sibling_links.push((siblings[0], siblings[1]));
+            }
+        }
+    }
+
+    // the links are drawn in three passes so that the choices of a pass do not depend on the others
+    let mut added_targets: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (source, target) in shared_children
+        .into_iter()
+        .chain(back_links)
+        .chain(sibling_links)
+    {
+        if source != target && links.insert((source, target)) {
+            added_targets.entry(source).or_default().push(target);
+        }
+    }
+
+    Ok(nodes
+        .iter()
+        .map(|node| {
+            let mut relations = node.relation().clone();
+            if let Some(targets) = added_targets.get(node.node_url().as_str()) {
+                relations.extend(
+                    targets
+                        .iter()
+                        .map(|target| Relation::new(None, None, None, String::from(*target), None)),
+                );
+            }
+            Node::new(relations, node.node_url().clone(), node.members().clone())
+        })
+        .collect())
+}
+
+/// The urls of the nodes reachable from the root in breadth-first order and the parent of each of them.
+fn breadth_first_order<'a>(
+    nodes: &'a [Node],
+    root_url: &'a str,
+) -> (Vec<&'a str>, HashMap<&'a str, &'a str>) {
+    let nodes_by_url: HashMap<&str, &Node> = nodes
+        .iter()
+        .map(|node| (node.node_url().as_str(), node))
+        .collect();
+    let mut order: Vec<&str> = Vec::new();
+    let mut parent_of: HashMap<&str, &str> = HashMap::new();
+    if !nodes_by_url.contains_key(root_url) {
+        return (order, parent_of);
+    }
+    let mut visited: HashSet<&str> = HashSet::from([root_url]);
+    let mut pending: VecDeque<&str> = VecDeque::from([root_url]);
+    while let Some(url) = pending.pop_front() {
+        order.push(url);
+        for relation in nodes_by_url[url].relation() {
+            let target = relation.node().as_str();
+            if nodes_by_url.contains_key(target) && visited.insert(target) {
+                parent_of.insert(target, url);
+                pending.push_back(target);
+            }
+        }
+    }
+    (order, parent_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_topology;
+    use crate::generator_argument::topology_argument::TopologyArg;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::validator::{validate, Violation};
+
+    const ROOT_URL: &str = "https://example.com/root";
+
+    fn a_node(url: &str, targets: &[&str]) -> Node {
+        Node::new(
+            targets
+                .iter()
+                .map(|target| {
+                    Relation::new(
+                        None,
+                        None,
+                        None,
+                        format!("https://example.com/{}", target),
+                        None,
+                    )
+                })
+                .collect(),
+            format!("https://example.com/{}", url),
+            Vec::new(),
+        )
+    }
+
+    /// root -> (a, b, c), a -> (d, e), b -> f, c -> g.
+    fn a_tree() -> Vec<Node> {
+        vec![
+            a_node("root", &["a", "b", "c"]),
+            a_node("a", &["d", "e"]),
+            a_node("b", &["f"]),
+            a_node("c", &["g"]),
+            a_node("d", &[]),
+            a_node("e", &[]),
+            a_node("f", &[]),
+            a_node("g", &[]),
+        ]
+    }
+
+    fn a_topology(shared_child: f64, cycle: f64, sibling_link: f64) -> TopologyArg {
+        TopologyArg {
+            shared_child_probability: shared_child,
+            cycle_probability: cycle,
+            sibling_link_probability: sibling_link,
+            seed: 7,
+        }
+    }
+
+    fn targets(nodes: &[Node], i: usize) -> Vec<&str> {
+        nodes[i]
+            .relation()
+            .iter()
+            .map(|relation| relation.node().as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_share_children_without_cycle() {
+        let nodes = apply_topology(a_tree(), ROOT_URL, &a_topology(1.0, 0.0, 0.0)).unwrap();
+
+        assert!(validate(&nodes, ROOT_URL, false).is_valid());
+        let violations = validate(&nodes, ROOT_URL, true).violations;
+        assert!(!violations.is_empty());
+        assert!(violations
+            .iter()
+            .all(|violation| matches!(violation, Violation::SharedNode { .. })));
+    }
+
+    #[test]
+    fn should_link_every_leaf_to_an_ancestor() {
+        let nodes = apply_topology(a_tree(), ROOT_URL, &a_topology(0.0, 1.0, 0.0)).unwrap();
+
+        let violations = validate(&nodes, ROOT_URL, true).violations;
+        let n_cycle = violations
+            .iter()
+            .filter(|violation| matches!(violation, Violation::Cycle { .. }))
+            .count();
+        assert_eq!(n_cycle, 4);
+        for leaf in &nodes[4..] {
+            assert_eq!(leaf.relation().len(), 1);
+        }
+    }
+
+    #[test]
+    fn should_link_the_siblings() {
+        let nodes = apply_topology(a_tree(), ROOT_URL, &a_topology(0.0, 0.0, 1.0)).unwrap();
+
+        assert_eq!(
+            targets(&nodes, 1),
+            vec![
+                "https://example.com/d",
+                "https://example.com/e",
+                "https://example.com/b"
+            ]
+        );
+        assert_eq!(
+            targets(&nodes, 2),
+            vec!["https://example.com/f", "https://example.com/c"]
+        );
+        assert_eq!(targets(&nodes, 4), vec!["https://example.com/e"]);
+        assert!(targets(&nodes, 3).len() == 1 && targets(&nodes, 5).is_empty());
+        assert!(validate(&nodes, ROOT_URL, false).is_valid());
+    }
+
+    #[test]
+    fn should_be_reproducible() {
+        let a = apply_topology(a_tree(), ROOT_URL, &a_topology(0.5, 0.5, 0.5)).unwrap();
+        let b = apply_topology(a_tree(), ROOT_URL, &a_topology(0.5, 0.5, 0.5)).unwrap();
+
+        for i in 0..a.len() {
+            assert_eq!(a[i].relation(), b[i].relation());
+        }
+        assert!(apply_topology(a_tree(), ROOT_URL, &a_topology(1.5, 0.0, 0.0)).is_err());
+        assert!(apply_topology(a_tree(), ROOT_URL, &a_topology(f64::NAN, 0.0, 0.0)).is_err());
+    }
+}
@@ -0,0 +1,26 @@
+use super::relation_argument::RelationTemplate;
+use super::RangeParameter;
+use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+use crate::tree::value::ValueType;
+
+/// Argument of the relations of a tree whose nodes are derived from their url and a seed, so that the
+/// [lazy generator](`crate::lazy_tree::LazyTreeGenerator`) can generate any of them alone.
+/// The values of the [`RangeParameter`]s are drawn with [`RangeParameter::next_with`] for the nodes to be reproducible.
+pub struct LazyTreeArg<T> {
+    /// template of the relations of the nodes.
+    pub template: RelationTemplate,
+    /// range of the value of the relations.
+    pub range: Box<dyn RangeParameter<T>>,
+    /// Value type of the relations.
+    pub value_type: ValueType,
+    /// How to handle a value of the range outside of the value space of the value type.
+    pub out_of_range_strategy: OutOfRangeStrategy,
+    /// How to interpret the values of the range when the value type is temporal.
+    pub temporal_format: TemporalFormat,
+    /// Number of children of a node.
+    pub fan_out: Box<dyn RangeParameter<usize>>,
+    /// Depth of the leaves, the root having a depth of 0.
+    pub max_depth: usize,
+    /// Seed of the random values.
+    pub seed: u64,
+}
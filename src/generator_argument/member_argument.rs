@@ -0,0 +1,57 @@
+use super::RangeParameter;
+use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::value::ValueType;
+use chrono::{DateTime, Duration, Utc};
+
+use std::vec::Vec;
+
+/// Argument to generate the [members](`crate::tree::member::Member`) of the nodes.
+pub struct MemberGeneratorArg<T> {
+    /// The schema of the properties of every member.
+    pub properties: Vec<PropertyTemplate<T>>,
+    /// distribution of the members inside the [Node](`crate::tree::node::Node`).
+    pub distribution_of_member: DistributionOfMember,
+    /// Versioning argument, the members are not versions of entities when it is not defined.
+    /// It is ignored by a [lazily generated tree](`crate::lazy_tree::LazyTreeGenerator`).
+    pub versioning: Option<VersioningArg>,
+}
+
+/// Argument to generate the members as versions of entities, as in a
+/// [versioned LDES](https://semiceu.github.io/LinkedDataEventStreams/#version-materializations).
+/// The entities are `{base_url}/entity/{i}` and every member is a version of one of them drawn at random.
+pub struct VersioningArg {
+    /// Path linking a version to its entity, like `dct:isVersionOf`.
+    pub version_of_path: ShaclPath,
+    /// Path of the `xsd:dateTime` timestamp of the versions, like `dct:created`.
+    pub timestamp_path: ShaclPath,
+    /// Number of entities.
+    pub n_entity: usize,
+    /// Timestamp of the first generated member.
+    pub start: DateTime<Utc>,
+    /// Time between two members generated one after the other, so that the timestamps increase
+    /// in the order of the nodes.
+    pub interval: Duration,
+}
+
+/// The template of a property of the members.
+pub struct PropertyTemplate<T> {
+    /// A property path, as defined by SHACL, leading to the value of the property.
+    pub path: ShaclPath,
+    /// range of the value of the property.
+    pub range: Box<dyn RangeParameter<T>>,
+    /// Value type of the property.
+    pub value_type: ValueType,
+    /// How to handle a value of the range outside of the value space of the value type.
+    pub out_of_range_strategy: OutOfRangeStrategy,
+    /// How to interpret the values of the range when the value type is temporal.
+    pub temporal_format: TemporalFormat,
+}
+
+/// distribution of the members inside the [Node](`crate::tree::node::Node`).
+pub enum DistributionOfMember {
+    /// directly set the number of members by node, nodes without a number have no member.
+    Direct(Vec<usize>),
+    /// Set a random number of members in every node using a [`RangeParameter`].
+    Random(Box<dyn RangeParameter<usize>>),
+}
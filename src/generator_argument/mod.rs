@@ -1,15 +1,39 @@
+pub mod lazy_tree_argument;
+pub mod member_argument;
+pub mod query_workload_argument;
 pub mod range;
 pub mod relation_argument;
+pub mod search_form_argument;
+pub mod string_index_argument;
+pub mod topology_argument;
 
+use self::member_argument::MemberGeneratorArg;
+use rand::RngCore;
 use self::relation_argument::RelationGeneratorArg;
-pub trait RangeParameter<T> {
+use self::search_form_argument::SearchFormArg;
+use self::topology_argument::TopologyArg;
+/// A source of values, shared by the threads of the [server](`crate::server::Server`),
+/// hence the `Send + Sync` bound that an implementation holding e.g. a `RefCell` does not satisfy.
+pub trait RangeParameter<T>: Send + Sync {
     /// get the next value.
     fn next(&self) -> T;
+    /// get the next value drawn from `rng`, making it reproducible with a seeded `rng`.
+    /// The default implementation ignores `rng`, which makes the nodes of the
+    /// [lazy generator](`crate::lazy_tree::LazyTreeGenerator`) differ from one request to another.
+    fn next_with(&self, _rng: &mut dyn RngCore) -> T {
+        self.next()
+    }
 }
 /// Argument necessary to generate a TREE document at the user request.
 pub struct Args<T> {
     /// Relation argument.
     pub relation: RelationGeneratorArg<T>,
+    /// Member argument, the nodes have no member when it is not defined.
+    pub members: Option<MemberGeneratorArg<T>>,
+    /// Search form argument, the root node has no search form when it is not defined.
+    pub search_form: Option<SearchFormArg>,
+    /// Topology argument, the nodes form a tree when it is not defined.
+    pub topology: Option<TopologyArg>,
     /// Base url without the trailling "/".
     pub base_url: String,
 }
@@ -0,0 +1,12 @@
+use crate::query::workload::QueryKind;
+use std::vec::Vec;
+
+/// Argument to generate the [queries](`crate::query::workload::Query`) of a benchmark over a TREE document.
+pub struct QueryWorkloadArg {
+    /// The kinds of query to generate.
+    pub kinds: Vec<QueryKind>,
+    /// Number of queries generated for every kind.
+    pub n_query_by_kind: usize,
+    /// Seed of the random choices of paths and values.
+    pub seed: u64,
+}
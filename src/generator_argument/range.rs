@@ -1,5 +1,6 @@
 use super::RangeParameter;
-use rand::{distributions::uniform::SampleUniform, prelude::*};
+use rand::distributions::{uniform::SampleUniform, WeightedIndex};
+use rand::prelude::*;
 use std::cmp::PartialOrd;
 
 /// Generator of random number with a upper and lower bound.
@@ -11,6 +12,7 @@ pub struct RandomBoundedNumberRange<T: SampleUniform + PartialOrd + Copy> {
 }
 
 impl<T: SampleUniform + PartialOrd + Copy>  RandomBoundedNumberRange<T>{
+    #[allow(clippy::redundant_field_names)]
     pub fn new(lower:T, upper:T)->Self{
         if lower>upper{
             panic!(" \"lower\" variable should be lower or equal to \"upper\" variable");
@@ -19,11 +21,94 @@ impl<T: SampleUniform + PartialOrd + Copy>  RandomBoundedNumberRange<T>{
     }
 }
 
-impl<T: SampleUniform + PartialOrd + Copy> RangeParameter<T>
+impl<T: SampleUniform + PartialOrd + Copy + Send + Sync> RangeParameter<T>
     for RandomBoundedNumberRange<T>
 {
     fn next(&self) -> T {
-        let mut rng = thread_rng();
+        self.next_with(&mut thread_rng())
+    }
+
+    fn next_with(&self, rng: &mut dyn RngCore) -> T {
+        if self.lower == self.upper {
+            return self.lower;
+        }
         rng.gen_range(self.lower..self.upper)
     }
 }
+
+/// Generator of values drawn among observed values, each with a weight.
+pub struct WeightedChoice<T: Clone> {
+    values: Vec<T>,
+    weights: WeightedIndex<usize>,
+}
+
+impl<T: Clone> WeightedChoice<T> {
+    /// `None` if there is no value or if every weight is null.
+    pub fn new(values_and_weights: Vec<(T, usize)>) -> Option<Self> {
+        let weights =
+            WeightedIndex::new(values_and_weights.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(WeightedChoice {
+            values: values_and_weights
+                .into_iter()
+                .map(|(value, _)| value)
+                .collect(),
+            weights,
+        })
+    }
+}
+
+impl<T: Clone + Send + Sync> RangeParameter<T> for WeightedChoice<T> {
+    fn next(&self) -> T {
+        self.next_with(&mut thread_rng())
+    }
+
+    fn next_with(&self, mut rng: &mut dyn RngCore) -> T {
+        self.values[self.weights.sample(&mut rng)].clone()
+    }
+}
+
+/// Generator of numbers following a distribution described by its quantiles,
+/// a number being drawn uniformly between two consecutive quantiles chosen uniformly.
+pub struct QuantileRange {
+    /// The quantiles, sorted, from the minimum to the maximum.
+    quantiles: Vec<f64>,
+    /// Whether the numbers are rounded to integers.
+    is_integral: bool,
+}
+
+impl QuantileRange {
+    pub fn new(quantiles: Vec<f64>, is_integral: bool) -> Self {
+        if quantiles.is_empty() || quantiles.windows(2).any(|pair| pair[0] > pair[1]) {
+            panic!(" \"quantiles\" should not be empty and should be sorted");
+        }
+        QuantileRange {
+            quantiles,
+            is_integral,
+        }
+    }
+}
+
+impl RangeParameter<f64> for QuantileRange {
+    fn next(&self) -> f64 {
+        self.next_with(&mut thread_rng())
+    }
+
+    fn next_with(&self, rng: &mut dyn RngCore) -> f64 {
+        let value = if self.quantiles.len() == 1 {
+            self.quantiles[0]
+        } else {
+            let i = rng.gen_range(0..self.quantiles.len() - 1);
+            let (lower, upper) = (self.quantiles[i], self.quantiles[i + 1]);
+            if lower == upper {
+                lower
+            } else {
+                rng.gen_range(lower..=upper)
+            }
+        };
+        if self.is_integral {
+            value.round()
+        } else {
+            value
+        }
+    }
+}
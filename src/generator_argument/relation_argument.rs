@@ -1,4 +1,7 @@
+use super::lazy_tree_argument::LazyTreeArg;
+use super::string_index_argument::StringIndexArg;
 use super::RangeParameter;
+use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
 use crate::tree::relation::Relation;
 use crate::tree::relation_operator::RelationOperator;
 use crate::tree::shacl_path::ShaclPath;
@@ -13,6 +16,27 @@ pub enum RelationGeneratorArg<T> {
     /// Generate the [`Relation`] based on a template
     /// and make the [`Relation`] value vary following a [`RangeParameter`].
     ValueVariation(TemplateRangeVariationRelation<T>),
+    /// Generate the [`Relation`] based on a template with a string value,
+    /// like a language-tagged string or an IRI, following a [`RangeParameter`].
+    StringVariation(TemplateStringVariationRelation),
+    /// Paginate the members in a linked list of nodes with [`Relation`]s without path, value or operator.
+    Pagination(PaginationArg),
+    /// Index strings with suffix or substring relations, the nodes being generated with their members.
+    StringIndex(StringIndexArg),
+    /// Generate a tree whose nodes are derived from their url and a seed, the same nodes as the ones
+    /// of the [lazy generator](`crate::lazy_tree::LazyTreeGenerator`).
+    /// A direct distribution of the members sets their number by depth.
+    Lazy(LazyTreeArg<T>),
+}
+
+/// Argument of a pagination, the nodes being `{base_url}/root` followed by `{base_url}/page/{i}`.
+pub struct PaginationArg {
+    /// Number of pages, the root included.
+    pub n_page: usize,
+    /// Number of members of every page, replacing the distribution of the members.
+    pub page_size: usize,
+    /// Whether every page other than the root also links to the previous page.
+    pub backward_links: bool,
 }
 
 pub struct TemplateRangeVariationRelation<T> {
@@ -24,6 +48,21 @@ pub struct TemplateRangeVariationRelation<T> {
     pub distribution_of_relation: DistributionOfRelation,
     /// Value type of the [`Relation`]
     pub value_type: ValueType,
+    /// How to handle a value of the range outside of the value space of the value type.
+    pub out_of_range_strategy: OutOfRangeStrategy,
+    /// How to interpret the values of the range when the value type is temporal.
+    pub temporal_format: TemporalFormat,
+}
+
+pub struct TemplateStringVariationRelation {
+    /// template of the [`Relation`].
+    pub template: RelationTemplate,
+    /// range of the string value of the selected property.
+    pub range: Box<dyn RangeParameter<String>>,
+    /// distribution of  the [`Relation`] inside the [Node](`crate::tree:node::Node`).
+    pub distribution_of_relation: DistributionOfRelation,
+    /// Value type of the [`Relation`], which cannot be derived from a number.
+    pub value_type: ValueType,
 }
 
 /// distribution of the [`Relation`] inside the [Node](`crate::tree:node::Node`).
@@ -32,6 +71,31 @@ pub enum DistributionOfRelation {
     Direct(Vec<usize>),
     /// Set a random number of relation using a [`RangeParameter`] with a number of node.
     Random(Box<dyn RangeParameter<usize>>, usize),
+    /// Set the number of relation by node following the depth of the node in the tree.
+    ByLevel(LevelDistribution),
+}
+
+/// Number of relation of the nodes following their depth, the root being at depth 0.
+pub struct LevelDistribution {
+    /// Fan-out of the nodes at each depth, the last one applying to the deeper nodes.
+    pub fan_out_by_level: Vec<Box<dyn RangeParameter<usize>>>,
+    /// Depth of the deepest nodes, which have no relation.
+    pub max_depth: usize,
+    /// How the fan-out is shared between the nodes of a level.
+    pub shape: TreeShape,
+}
+
+/// The shape of a tree generated with a [`LevelDistribution`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreeShape {
+    /// The nodes of a level have the same fan-out, so every leaf is at the same depth
+    /// unless a fan-out of zero stops the tree early.
+    Balanced,
+    /// Every node draws its own fan-out.
+    Unbalanced,
+    /// Every node draws its own fan-out but only the first child of a node has children,
+    /// producing a spine with leaves hanging from it.
+    Skewed,
 }
 
 #[derive(Clone)]
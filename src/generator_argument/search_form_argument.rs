@@ -0,0 +1,10 @@
+use crate::tree::shacl_path::ShaclPath;
+
+/// Argument to add a [search form](`crate::tree::search_form::SearchForm`) on the root node
+/// over the values of the relations following a path.
+pub struct SearchFormArg {
+    /// Name of the variable of the template.
+    pub variable: String,
+    /// Path of the relations whose values are searchable.
+    pub path: ShaclPath,
+}
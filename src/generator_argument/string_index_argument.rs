@@ -0,0 +1,44 @@
+use super::RangeParameter;
+use crate::tree::shacl_path::ShaclPath;
+
+use std::vec::Vec;
+
+/// Argument to index strings, the members having a single property holding one of the strings
+/// and the relations constraining the same property.
+pub struct StringIndexArg {
+    /// Path of the property of the members holding the strings.
+    pub path: ShaclPath,
+    /// The indexed strings, one member by string.
+    pub strings: StringSource,
+    /// How the strings are fragmented.
+    pub strategy: StringIndexStrategy,
+}
+
+/// The source of the indexed strings.
+pub enum StringSource {
+    /// The strings given by the user.
+    Supplied(Vec<String>),
+    /// Strings made of random characters of an alphabet.
+    Random {
+        /// Number of strings.
+        n_string: usize,
+        /// Number of characters of a string.
+        length: Box<dyn RangeParameter<usize>>,
+        /// The characters of the strings.
+        alphabet: Vec<char>,
+        /// Seed of the random characters.
+        seed: u64,
+    },
+}
+
+/// The fragmentation of the strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringIndexStrategy {
+    /// A trie of the suffixes linked by [suffix relations](`crate::tree::relation_operator::RelationOperator::SuffixRelation`),
+    /// a node holding at most `capacity` members unless they all end with the same longer suffix.
+    SuffixTrie { capacity: usize },
+    /// A node for every n-gram of `n` characters holding the members containing it, linked from the root
+    /// by a [substring relation](`crate::tree::relation_operator::RelationOperator::SubstringRelation`).
+    /// The strings shorter than `n` are held by the root.
+    NGram { n: usize },
+}
@@ -0,0 +1,14 @@
+/// Argument to add relations to the generated tree so that it becomes a graph, every added relation
+/// being a `tree:Relation` without condition that a client has to follow.
+/// Each probability is between 0 and 1, a null probability leaving the tree as is.
+pub struct TopologyArg {
+    /// Probability for a node other than the root to also be the child of a node before it
+    /// in breadth-first order, making the tree a directed acyclic graph with shared children.
+    pub shared_child_probability: f64,
+    /// Probability for a leaf to link back to one of its ancestors, creating a cycle.
+    pub cycle_probability: f64,
+    /// Probability for a node to link to its next sibling.
+    pub sibling_link_probability: f64,
+    /// Seed of the random choices of the nodes and of their targets.
+    pub seed: u64,
+}
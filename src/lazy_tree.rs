@@ -0,0 +1,436 @@
+use super::generate_member::{constrain_member, generate_a_member, member_shape};
+use super::generator_argument::lazy_tree_argument::LazyTreeArg;
+use super::generator_argument::member_argument::DistributionOfMember;
+use super::generator_argument::relation_argument::RelationGeneratorArg;
+use super::generator_argument::Args;
+use super::query::filter::Filter;
+use super::rdf::triple::Triple;
+use super::seed::keyed_rng;
+use super::sparql_converter::{NumberToSparqlConverter, SparqlConverter};
+use super::tree::collection::Collection;
+use super::tree::member::Member;
+use super::tree::node::Node;
+use super::tree::relation::Relation;
+use super::tree::tree_document::TreeDocument;
+use super::tree::value::Value;
+use rand::RngCore;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::vec::Vec;
+
+/// Generator of the nodes of a TREE document on demand, without storing the document.
+/// A node is derived from its url and the seed of the [lazy relations](`LazyTreeArg`): the root is
+/// `{base_url}/root` and the i-th child of a node is `{node_url}/{i}`, so that a node generated alone
+/// is the same as the node of the whole document, generated by
+/// [`LazyTreeGenerator::generate_tree_document`] or [`generate_tree_document`](`crate::generate_tree_document`).
+pub struct LazyTreeGenerator<T> {
+    args: Args<T>,
+}
+
+impl<T: num::ToPrimitive + Debug> LazyTreeGenerator<T> {
+    /// Fail if the relations are not [lazy](`RelationGeneratorArg::Lazy`)
+    /// or if a topology or a search form is requested.
+    pub fn new(args: Args<T>) -> Result<Self, &'static str> {
+        LazyTree::of(&args)?;
+        Ok(LazyTreeGenerator { args })
+    }
+
+    /// Url of the root node.
+    pub fn root_url(&self) -> String {
+        self.tree().root_url()
+    }
+
+    /// Generate the node identified by the url, `None` if the url does not identify a node of the tree.
+    pub fn node(&self, node_url: &str) -> Result<Option<Node>, &'static str> {
+        self.tree().node(node_url)
+    }
+
+    /// The triples of the page of a node, `None` if the url does not identify a node of the tree.
+    /// The collection is only linked to the members of the page since the members of the whole
+    /// document are not known.
+    pub fn page(&self, node_url: &str) -> Result<Option<Vec<Triple>>, &'static str> {
+        self.tree().page(node_url)
+    }
+
+    /// Generate every node of the tree in breadth-first order.
+    pub fn generate_tree_document(&self) -> Result<TreeDocument, &'static str> {
+        self.tree().generate_tree_document()
+    }
+
+    fn tree(&self) -> LazyTree<'_, T> {
+        LazyTree::of(&self.args)
+            .expect("the arguments should be checked when creating the generator")
+    }
+}
+
+/// The tree whose nodes are derived from their url, described by borrowed arguments.
+pub(super) struct LazyTree<'a, T> {
+    lazy_args: &'a LazyTreeArg<T>,
+    args: &'a Args<T>,
+}
+
+impl<'a, T: num::ToPrimitive + Debug> LazyTree<'a, T> {
+    /// Fail if the relations are not lazy or if a topology or a search form is requested.
+    pub(super) fn of(args: &'a Args<T>) -> Result<Self, &'static str> {
+        let lazy_args = match &args.relation {
+            RelationGeneratorArg::Lazy(v) => v,
+            _ => return Err("the relations of a lazily generated tree should be lazy"),
+        };
+        if args.topology.is_some() || args.search_form.is_some() {
+            return Err("a lazily generated tree has neither a topology nor a search form");
+        }
+        Ok(LazyTree { lazy_args, args })
+    }
+
+    /// Url of the root node.
+    fn root_url(&self) -> String {
+        format!("{}/root", self.args.base_url)
+    }
+
+    /// Generate the node identified by the url, `None` if the url does not identify a node of the tree.
+    fn node(&self, node_url: &str) -> Result<Option<Node>, &'static str> {
+        if !self.exists(node_url) {
+            return Ok(None);
+        }
+        let depth = self.depth(node_url);
+        let mut rng = keyed_rng(self.lazy_args.seed, node_url);
+        let relations = self.relations(node_url, depth, &mut rng)?;
+
+        let mut members: Vec<Member> = Vec::new();
+        if let Some(member_args) = &self.args.members {
+            let filters = self.filters_of_the_path(node_url)?;
+            let n_member = match &member_args.distribution_of_member {
+                DistributionOfMember::Direct(dist) => dist.get(depth).copied().unwrap_or(0),
+                DistributionOfMember::Random(range_fn) => range_fn.next_with(&mut rng),
+            };
+            for k in 0..n_member {
+                let mut member = generate_a_member(
+                    &member_args.properties,
+                    format!("{}#member{}", node_url, k),
+                    &mut rng,
+                )?;
+                constrain_member(&mut member, &member_args.properties, &filters, &mut rng)?;
+                members.push(member);
+            }
+        }
+        Ok(Some(Node::new(relations, String::from(node_url), members)))
+    }
+
+    /// The triples of the page of a node, `None` if the url does not identify a node of the tree.
+    fn page(&self, node_url: &str) -> Result<Option<Vec<Triple>>, &'static str> {
+        let node = match self.node(node_url)? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let members = node
+            .members()
+            .iter()
+            .map(|member| member.url.clone())
+            .collect();
+        Ok(
+            TreeDocument::new(self.collection(members), vec![node], self.shape(), None)
+                .page(node_url),
+        )
+    }
+
+    /// Generate every node of the tree in breadth-first order.
+    pub(super) fn generate_tree_document(&self) -> Result<TreeDocument, &'static str> {
+        let mut nodes: Vec<Node> = Vec::new();
+        let mut pending_url: VecDeque<String> = VecDeque::from([self.root_url()]);
+        while let Some(node_url) = pending_url.pop_front() {
+            let node = self
+                .node(&node_url)?
+                .expect("the nodes targeted by a relation should exist");
+            pending_url.extend(
+                node.relation()
+                    .iter()
+                    .map(|relation| relation.node().clone()),
+            );
+            nodes.push(node);
+        }
+        let members = nodes
+            .iter()
+            .flat_map(|node| node.members().iter().map(|member| member.url.clone()))
+            .collect();
+        Ok(TreeDocument::new(
+            self.collection(members),
+            nodes,
+            self.shape(),
+            None,
+        ))
+    }
+
+    fn collection(&self, members: Vec<String>) -> Collection {
+        Collection::new(
+            format!("{}/collection", self.args.base_url),
+            self.root_url(),
+            members,
+            self.shape().map(|shape| shape.url().clone()),
+        )
+    }
+
+    fn shape(&self) -> Option<super::tree::shape::NodeShape> {
+        self.args
+            .members
+            .as_ref()
+            .map(|member_args| member_shape(member_args, format!("{}/shape", self.args.base_url)))
+    }
+
+    /// The relations of a node to its children, drawn from the rng of the node.
+    fn relations(
+        &self,
+        node_url: &str,
+        depth: usize,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<Relation>, &'static str> {
+        let fan_out = self.fan_out(depth, rng);
+        let sparql_converter = NumberToSparqlConverter::new(
+            self.lazy_args.out_of_range_strategy,
+            self.lazy_args.temporal_format,
+        );
+        let mut relations: Vec<Relation> = Vec::new();
+        for i in 0..fan_out {
+            let value = sparql_converter.convert(
+                self.lazy_args.range.next_with(rng),
+                &self.lazy_args.value_type,
+            )?;
+            relations.push(Relation::new(
+                None,
+                Some(self.lazy_args.template.path.clone()),
+                Some(Value {
+                    value,
+                    value_type: self.lazy_args.value_type.clone(),
+                }),
+                format!("{}/{}", node_url, i),
+                Some(self.lazy_args.template.relation_type.clone()),
+            ));
+        }
+        Ok(relations)
+    }
+
+    /// The filters of the relations from the root to a node, assumed to be in the tree.
+    fn filters_of_the_path(&self, node_url: &str) -> Result<Vec<Filter>, &'static str> {
+        let mut filters: Vec<Filter> = Vec::new();
+        let mut parent_url = self.root_url();
+        let indexes = node_url[parent_url.len()..].split('/').skip(1);
+        for (depth, index) in indexes.enumerate() {
+            let index: usize = index
+                .parse()
+                .expect("the indexes of a node in the tree should be numbers");
+            let relations = self.relations(
+                &parent_url,
+                depth,
+                &mut keyed_rng(self.lazy_args.seed, &parent_url),
+            )?;
+            filters.extend(Filter::of_relation(&relations[index]));
+            parent_url = format!("{}/{}", parent_url, index);
+        }
+        Ok(filters)
+    }
+
+    /// The number of children of a node, the first value drawn from the rng of the node.
+    fn fan_out(&self, depth: usize, rng: &mut dyn RngCore) -> usize {
+        if depth < self.lazy_args.max_depth {
+            self.lazy_args.fan_out.next_with(rng)
+        } else {
+            0
+        }
+    }
+
+    /// Depth of a node url, assumed to be in the tree.
+    fn depth(&self, node_url: &str) -> usize {
+        node_url[self.root_url().len()..].matches('/').count()
+    }
+
+    /// Whether every index of the url is lower than the fan-out of the parent node.
+    fn exists(&self, node_url: &str) -> bool {
+        let root_url = self.root_url();
+        let indexes = match node_url.strip_prefix(&root_url) {
+            Some("") => return true,
+            Some(v) => match v.strip_prefix('/') {
+                Some(v) => v.split('/'),
+                None => return false,
+            },
+            None => return false,
+        };
+        let mut parent_url = root_url;
+        for (depth, index) in indexes.enumerate() {
+            // reject the indexes that are not in their canonical form like "01"
+            let index: usize = match index.parse::<usize>() {
+                Ok(v) if v.to_string() == index => v,
+                _ => return false,
+            };
+            let fan_out = self.fan_out(depth, &mut keyed_rng(self.lazy_args.seed, &parent_url));
+            if index >= fan_out {
+                return false;
+            }
+            parent_url = format!("{}/{}", parent_url, index);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LazyTreeGenerator;
+    use crate::generate_tree_document;
+    use crate::generator_argument::lazy_tree_argument::LazyTreeArg;
+    use crate::generator_argument::member_argument::{
+        DistributionOfMember, MemberGeneratorArg, PropertyTemplate,
+    };
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::generator_argument::relation_argument::{RelationGeneratorArg, RelationTemplate};
+    use crate::generator_argument::Args;
+    use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::value::ValueType;
+
+    fn some_args(seed: u64) -> Args<i64> {
+        Args {
+            relation: RelationGeneratorArg::Lazy(LazyTreeArg {
+                template: RelationTemplate {
+                    path: String::from("ex:p"),
+                    relation_type: RelationOperator::GreaterThanRelation,
+                },
+                range: Box::new(RandomBoundedNumberRange::new(0, 1_000_000)),
+                value_type: ValueType::Int,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
+                fan_out: Box::new(RandomBoundedNumberRange::new(1, 4)),
+                max_depth: 3,
+                seed,
+            }),
+            members: Some(MemberGeneratorArg {
+                properties: vec![PropertyTemplate {
+                    path: String::from("ex:p"),
+                    range: Box::new(RandomBoundedNumberRange::new(0, 1_000_000)),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }],
+                distribution_of_member: DistributionOfMember::Random(Box::new(
+                    RandomBoundedNumberRange::new(1, 5),
+                )),
+                versioning: None,
+            }),
+            search_form: None,
+            topology: None,
+            base_url: String::from("https://example.com"),
+        }
+    }
+
+    fn a_generator(seed: u64) -> LazyTreeGenerator<i64> {
+        LazyTreeGenerator::new(some_args(seed)).unwrap()
+    }
+
+    #[test]
+    fn should_generate_the_same_nodes_alone_and_with_the_whole_document() {
+        let generator = a_generator(42);
+
+        let document = generator.generate_tree_document().unwrap();
+
+        assert!(document.nodes().len() > 1);
+        for node in document.nodes() {
+            let alone = generator.node(node.node_url()).unwrap().unwrap();
+            assert_eq!(alone.relation(), node.relation());
+            assert_eq!(alone.members(), node.members());
+        }
+        let other_document = a_generator(42).generate_tree_document().unwrap();
+        assert_eq!(other_document.nodes().len(), document.nodes().len());
+    }
+
+    #[test]
+    fn should_generate_the_same_nodes_as_the_batch_generation() {
+        let generator = a_generator(42);
+
+        let document = generate_tree_document(&some_args(42)).unwrap();
+
+        assert!(document.nodes().len() > 1);
+        for node in document.nodes() {
+            let alone = generator.node(node.node_url()).unwrap().unwrap();
+            assert_eq!(alone.relation(), node.relation());
+            assert_eq!(alone.members(), node.members());
+        }
+        assert_eq!(
+            document.collection().members(),
+            generator
+                .generate_tree_document()
+                .unwrap()
+                .collection()
+                .members()
+        );
+    }
+
+    #[test]
+    fn should_reject_the_arguments_without_lazy_relations() {
+        let mut args = some_args(42);
+        args.relation = RelationGeneratorArg::Direct(Vec::new());
+
+        assert!(LazyTreeGenerator::new(args).is_err());
+    }
+
+    #[test]
+    fn should_respect_the_maximum_depth() {
+        let generator = a_generator(1);
+
+        let document = generator.generate_tree_document().unwrap();
+
+        for node in document.nodes() {
+            let depth = node.node_url().matches('/').count()
+                - "https://example.com/root".matches('/').count();
+            assert!(depth <= 3);
+            if depth == 3 {
+                assert!(node.relation().is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn should_not_generate_a_node_outside_of_the_tree() {
+        let generator = a_generator(7);
+
+        assert!(generator
+            .node("https://example.com/root")
+            .unwrap()
+            .is_some());
+        assert!(generator
+            .node("https://example.com/root/9")
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .node("https://example.com/root/00")
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .node("https://example.com/root/a")
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .node("https://example.com/rootx")
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .node("https://example.com/other")
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .node("https://example.com/root/0/0/0/0")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn should_return_the_page_of_a_node_with_the_collection() {
+        let generator = a_generator(3);
+
+        let page = generator.page("https://example.com/root").unwrap().unwrap();
+
+        assert!(page
+            .iter()
+            .any(|triple| triple.predicate == crate::rdf::vocabulary::TREE_VIEW));
+        assert!(generator
+            .page("https://example.com/root/9")
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,20 +1,141 @@
+mod generate_member;
+mod generate_node;
 mod generate_relation;
+mod generate_search_form;
+mod generate_string_index;
+mod generate_topology;
 pub mod generator_argument;
+pub mod lazy_tree;
+pub mod mutation;
+pub mod profile;
+pub mod query;
+pub mod rdf;
+mod seed;
+pub mod server;
 pub mod sparql_converter;
 mod tests;
 pub mod tree;
+pub mod validator;
 
+use self::generate_member::{
+    constrain_member, filters_of_the_paths, generate_members, generate_members_of_pages,
+    member_shape,
+};
+use self::generate_node::generate_nodes;
 use self::generate_relation::generate_relations;
+use self::generate_search_form::apply_search_form;
+use self::generate_string_index::generate_string_index;
+use self::generate_topology::apply_topology;
+use generator_argument::relation_argument::RelationGeneratorArg;
 use generator_argument::Args;
-use num;
+use lazy_tree::LazyTree;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::vec::Vec;
+use tree::collection::Collection;
 use tree::node::Node;
+use tree::search_form::SearchForm;
+use tree::shape::{NodeShape, PropertyShape};
+use tree::tree_document::TreeDocument;
+use tree::value::ValueType;
 
-pub fn generate_tree_document<T: num::ToPrimitive + Debug>(args: &Args<T>) -> Vec<Node> {
-    let mut tree_document: Vec<Node> = Vec::new();
+/// Generate a [`TreeDocument`], a collection identified by `{base_url}/collection`
+/// whose view is the root node `{base_url}/root`.
+/// When members are generated the collection has the shape `{base_url}/shape`,
+/// a string index generating its members with its nodes instead of following the member argument,
+/// and when a search form is requested its template is `{base_url}/node{?variable}`.
+/// The values of the members satisfy the relations on the breadth-first path from the root to their node.
+/// The nodes form a tree unless a topology adding shared children, cycles or links between siblings is requested.
+/// A [lazy](`RelationGeneratorArg::Lazy`) tree is the one of the [lazy generator](`lazy_tree::LazyTreeGenerator`).
+pub fn generate_tree_document<T: num::ToPrimitive + Debug>(
+    args: &Args<T>,
+) -> Result<TreeDocument, &'static str> {
+    if let RelationGeneratorArg::Lazy(_) = &args.relation {
+        return LazyTree::of(args)?.generate_tree_document();
+    }
+    let collection_url = format!("{}/collection", args.base_url);
+    let root_url = format!("{}/root", args.base_url);
 
-    let relations = generate_relations(&args.relation, &args.base_url);
+    let mut shape: Option<NodeShape> = None;
+    let mut nodes = match &args.relation {
+        RelationGeneratorArg::StringIndex(index_args) => {
+            shape = Some(NodeShape::new(
+                format!("{}/shape", args.base_url),
+                vec![PropertyShape::new(index_args.path.clone(), ValueType::String)],
+            ));
+            generate_string_index(index_args, &args.base_url)?
+        }
+        relation_args => {
+            let relations = generate_relations(relation_args, &args.base_url)?;
+            generate_nodes(relations, &root_url, &args.base_url)
+        }
+    };
+    if let Some(topology) = &args.topology {
+        nodes = apply_topology(nodes, &root_url, topology)?;
+    }
 
-    tree_document
+    let search_form = args.search_form.as_ref().map(|search_form_args| {
+        SearchForm::new(
+            format!("{}/node", args.base_url),
+            search_form_args.variable.clone(),
+            search_form_args.path.clone(),
+        )
+    });
+    if let Some(search_form) = &search_form {
+        nodes = apply_search_form(nodes, search_form);
+    }
+
+    if let Some(member_args) = args.members.as_ref().filter(|_| shape.is_none()) {
+        let members = match &args.relation {
+            RelationGeneratorArg::Pagination(pagination) => generate_members_of_pages(
+                member_args,
+                &args.base_url,
+                nodes.len(),
+                pagination.page_size,
+            )?,
+            _ => generate_members(member_args, &args.base_url, nodes.len())?,
+        };
+        let filters = filters_of_the_paths(&nodes, &root_url);
+        let mut rng = rand::thread_rng();
+        nodes = nodes
+            .into_iter()
+            .zip(members)
+            .map(|(node, mut members)| {
+                let filters_of_the_node = filters
+                    .get(node.node_url())
+                    .map_or(&[][..], |filters| filters.as_slice());
+                for member in members.iter_mut() {
+                    constrain_member(
+                        member,
+                        &member_args.properties,
+                        filters_of_the_node,
+                        &mut rng,
+                    )?;
+                }
+                Ok(Node::new(
+                    node.relation().clone(),
+                    node.node_url().clone(),
+                    members,
+                ))
+            })
+            .collect::<Result<Vec<Node>, &'static str>>()?;
+        shape = Some(member_shape(member_args, format!("{}/shape", args.base_url)));
+    }
+
+    // a member can be held by several nodes
+    let mut known_members: HashSet<&String> = HashSet::new();
+    let members: Vec<String> = nodes
+        .iter()
+        .flat_map(|node| node.members().iter().map(|member| &member.url))
+        .filter(|url| known_members.insert(url))
+        .cloned()
+        .collect();
+    let collection = Collection::new(
+        collection_url,
+        root_url,
+        members,
+        shape.as_ref().map(|shape| shape.url().clone()),
+    );
+
+    Ok(TreeDocument::new(collection, nodes, shape, search_form))
 }
@@ -0,0 +1,535 @@
+//! Evolve a TREE document over time by applying a stream of [mutations](`Mutation`).
+pub mod rebalance;
+
+use self::rebalance::{rebalance, Capacity};
+use crate::profile::count_reachable_members;
+use crate::tree::collection::Collection;
+use crate::tree::member::Member;
+use crate::tree::node::Node;
+use crate::tree::relation::Relation;
+use crate::tree::retention_policy::RetentionPolicy;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::tree_document::TreeDocument;
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::vec::Vec;
+
+/// A change of a TREE document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mutation {
+    /// Add new members to a node, like the latest fragment of an LDES.
+    AppendMembers { node: String, members: Vec<Member> },
+    /// Add a new version of a member next to it in the nodes holding it and in the collection,
+    /// the member being kept since the members of an LDES are immutable.
+    UpdateMember { member: String, version: Member },
+    /// Split the nodes holding more than `capacity` members: a node keeps its first members
+    /// and links to a new node `{node_url}/{i}` with the others.
+    SplitNodes { capacity: usize },
+    /// Split the full nodes following the values of their members over the path and
+    /// rewrite the relations of their parents, see [`rebalance`](`self::rebalance`).
+    Rebalance { path: ShaclPath, capacity: Capacity },
+    /// Remove members from the nodes and from the collection, the unknown members being ignored.
+    DeleteMembers { members: Vec<String> },
+    /// Remove the members expired at `now` following the
+    /// [retention policies](`TreeDocument::with_retention_policies`) of the document,
+    /// a member being kept when one of the policies keeps it.
+    ApplyRetention { now: DateTime<Utc> },
+}
+
+/// Apply [mutations](`Mutation`) to a TREE document. After every mutation the `tree:remainingItems`
+/// announced by the relations are updated to the number of members reachable through them.
+pub struct MutationEngine {
+    document: TreeDocument,
+}
+
+impl MutationEngine {
+    pub fn new(document: TreeDocument) -> Self {
+        MutationEngine { document }
+    }
+
+    /// The current state of the document.
+    pub fn document(&self) -> &TreeDocument {
+        &self.document
+    }
+
+    /// Apply a mutation and return the new state of the document,
+    /// the document being unchanged when the mutation is invalid.
+    pub fn apply(&mut self, mutation: &Mutation) -> Result<&TreeDocument, &'static str> {
+        let mut nodes = self.document.nodes().clone();
+        let mut members = self.document.collection().members().clone();
+        match mutation {
+            Mutation::AppendMembers {
+                node,
+                members: new_members,
+            } => append_members(&mut nodes, &mut members, node, new_members)?,
+            Mutation::UpdateMember { member, version } => {
+                update_member(&mut nodes, &mut members, member, version)?
+            }
+            Mutation::SplitNodes { capacity } => split_nodes(&mut nodes, *capacity)?,
+            Mutation::Rebalance { path, capacity } => rebalance(
+                &mut nodes,
+                self.document.collection().view(),
+                path,
+                capacity,
+            )?,
+            Mutation::DeleteMembers {
+                members: deleted_members,
+            } => delete_members(&mut nodes, &mut members, &deleted_members.iter().collect()),
+            Mutation::ApplyRetention { now } => {
+                let expired = expired_members(&nodes, self.document.retention_policies(), *now)?;
+                delete_members(&mut nodes, &mut members, &expired.iter().collect())
+            }
+        }
+
+        let collection = self.document.collection();
+        self.document = TreeDocument::new(
+            Collection::new(
+                collection.url().clone(),
+                collection.view().clone(),
+                members,
+                collection.shape().clone(),
+            ),
+            refresh_remaining_items(nodes),
+            self.document.shape().clone(),
+            self.document.search_form().clone(),
+        )
+        .with_retention_policies(self.document.retention_policies().clone())?;
+        Ok(&self.document)
+    }
+}
+
+/// Apply the mutations in order and return the state of the document after each of them.
+pub fn evolve(
+    document: TreeDocument,
+    mutations: &[Mutation],
+) -> Result<Vec<TreeDocument>, &'static str> {
+    let mut engine = MutationEngine::new(document);
+    mutations
+        .iter()
+        .map(|mutation| engine.apply(mutation).cloned())
+        .collect()
+}
+
+fn with_members(node: &Node, members: Vec<Member>) -> Node {
+    Node::new(node.relation().clone(), node.node_url().clone(), members)
+}
+
+fn delete_members(nodes: &mut [Node], members: &mut Vec<String>, deleted: &HashSet<&String>) {
+    members.retain(|member| !deleted.contains(member));
+    for node in nodes.iter_mut() {
+        *node = with_members(
+            node,
+            node.members()
+                .iter()
+                .filter(|member| !deleted.contains(&member.url))
+                .cloned()
+                .collect(),
+        );
+    }
+}
+
+/// The members expired following every retention policy, none without policy.
+fn expired_members(
+    nodes: &[Node],
+    retention_policies: &[RetentionPolicy],
+    now: DateTime<Utc>,
+) -> Result<HashSet<String>, &'static str> {
+    let mut known_members: HashSet<&String> = HashSet::new();
+    let members: Vec<&Member> = nodes
+        .iter()
+        .flat_map(|node| node.members().iter())
+        .filter(|member| known_members.insert(&member.url))
+        .collect();
+    let mut expired: Option<HashSet<String>> = None;
+    for retention_policy in retention_policies {
+        let expired_by_the_policy = retention_policy.expired_members(&members, now)?;
+        expired = Some(match expired {
+            Some(v) => v.intersection(&expired_by_the_policy).cloned().collect(),
+            None => expired_by_the_policy,
+        });
+    }
+    Ok(expired.unwrap_or_default())
+}
+
+fn append_members(
+    nodes: &mut [Node],
+    members: &mut Vec<String>,
+    node_url: &str,
+    new_members: &[Member],
+) -> Result<(), &'static str> {
+    let node = nodes
+        .iter_mut()
+        .find(|node| node.node_url() == node_url)
+        .ok_or("the node of the new members does not exist")?;
+    let mut known_members: HashSet<&String> = members.iter().collect();
+    if !new_members
+        .iter()
+        .all(|member| known_members.insert(&member.url))
+    {
+        return Err("a new member is already in the collection");
+    }
+    let mut members_of_the_node = node.members().clone();
+    members_of_the_node.extend(new_members.iter().cloned());
+    *node = with_members(node, members_of_the_node);
+    members.extend(new_members.iter().map(|member| member.url.clone()));
+    Ok(())
+}
+
+fn update_member(
+    nodes: &mut [Node],
+    members: &mut Vec<String>,
+    member_url: &str,
+    version: &Member,
+) -> Result<(), &'static str> {
+    if members.contains(&version.url) {
+        return Err("the new version is already in the collection");
+    }
+    let position = members
+        .iter()
+        .position(|member| member == member_url)
+        .ok_or("the updated member is not in the collection")?;
+    members.insert(position + 1, version.url.clone());
+    for node in nodes.iter_mut() {
+        if node.members().iter().any(|member| member.url == member_url) {
+            let members_of_the_node = node
+                .members()
+                .iter()
+                .flat_map(|member| {
+                    if member.url == member_url {
+                        vec![member.clone(), version.clone()]
+                    } else {
+                        vec![member.clone()]
+                    }
+                })
+                .collect();
+            *node = with_members(node, members_of_the_node);
+        }
+    }
+    Ok(())
+}
+
+fn split_nodes(nodes: &mut Vec<Node>, capacity: usize) -> Result<(), &'static str> {
+    if capacity == 0 {
+        return Err("the capacity of a node should be at least 1");
+    }
+    let mut node_urls: HashSet<String> = nodes.iter().map(|node| node.node_url().clone()).collect();
+    // the new nodes are visited too, so that a node with many members becomes a chain of full nodes
+    let mut i = 0;
+    while i < nodes.len() {
+        let node = &nodes[i];
+        if node.members().len() > capacity {
+            let new_url = unused_url(&mut node_urls, node.node_url());
+            let mut relations = node.relation().clone();
+            relations.push(Relation::new(None, None, None, new_url.clone(), None));
+            let new_node = Node::new(Vec::new(), new_url, node.members()[capacity..].to_vec());
+            nodes[i] = Node::new(
+                relations,
+                node.node_url().clone(),
+                node.members()[..capacity].to_vec(),
+            );
+            nodes.push(new_node);
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// The first url `{prefix}/{i}` not used by a node, which is reserved for a new node.
+fn unused_url(node_urls: &mut HashSet<String>, prefix: &str) -> String {
+    let url = (1..)
+        .map(|i| format!("{}/{}", prefix, i))
+        .find(|url| !node_urls.contains(url))
+        .expect("there should be an unused url");
+    node_urls.insert(url.clone());
+    url
+}
+
+/// Set the `tree:remainingItems` of the relations announcing it to the number of members reachable through them.
+fn refresh_remaining_items(nodes: Vec<Node>) -> Vec<Node> {
+    let nodes_by_url: HashMap<&str, &Node> = nodes
+        .iter()
+        .map(|node| (node.node_url().as_str(), node))
+        .collect();
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    nodes
+        .iter()
+        .map(|node| {
+            let relations = node
+                .relation()
+                .iter()
+                .map(|relation| match relation.remaning_items() {
+                    Some(_) => {
+                        let count = *counts.entry(relation.node().as_str()).or_insert_with(|| {
+                            count_reachable_members(&nodes_by_url, relation.node())
+                        });
+                        Relation::new(
+                            Some(count as i32),
+                            relation.path().clone(),
+                            relation.value().clone(),
+                            relation.node().clone(),
+                            relation.relation_type().clone(),
+                        )
+                    }
+                    None => relation.clone(),
+                })
+                .collect();
+            Node::new(relations, node.node_url().clone(), node.members().clone())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{evolve, Mutation, MutationEngine};
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::retention_policy::RetentionPolicy;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use crate::validator::validate;
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+
+    fn a_member(i: usize) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([(
+                String::from("https://example.com/p"),
+                Value {
+                    value: i.to_string(),
+                    value_type: ValueType::Int,
+                },
+            )]),
+        }
+    }
+
+    /// root -> a with a remaining items, the root holding the member 0 and a the member 1.
+    fn a_document() -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                vec![
+                    String::from("https://example.com/member/0"),
+                    String::from("https://example.com/member/1"),
+                ],
+                None,
+            ),
+            vec![
+                Node::new(
+                    vec![Relation::new(
+                        Some(1),
+                        None,
+                        None,
+                        String::from("https://example.com/a"),
+                        None,
+                    )],
+                    String::from("https://example.com/root"),
+                    vec![a_member(0)],
+                ),
+                Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/a"),
+                    vec![a_member(1)],
+                ),
+            ],
+            None,
+            None,
+        )
+    }
+
+    fn member_urls(node: &Node) -> Vec<&str> {
+        node.members()
+            .iter()
+            .map(|member| member.url.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn should_append_members_to_a_node() {
+        let mut engine = MutationEngine::new(a_document());
+
+        let document = engine
+            .apply(&Mutation::AppendMembers {
+                node: String::from("https://example.com/a"),
+                members: vec![a_member(2), a_member(3)],
+            })
+            .unwrap();
+
+        assert_eq!(
+            member_urls(&document.nodes()[1]),
+            vec![
+                "https://example.com/member/1",
+                "https://example.com/member/2",
+                "https://example.com/member/3"
+            ]
+        );
+        assert_eq!(document.collection().members().len(), 4);
+        assert_eq!(document.nodes()[0].relation()[0].remaning_items(), &Some(3));
+        assert!(validate(document.nodes(), "https://example.com/root", true).is_valid());
+    }
+
+    #[test]
+    fn should_add_a_new_version_of_a_member() {
+        let mut version = a_member(5);
+        version.url = String::from("https://example.com/member/1v2");
+        let mut engine = MutationEngine::new(a_document());
+
+        let document = engine
+            .apply(&Mutation::UpdateMember {
+                member: String::from("https://example.com/member/1"),
+                version: version.clone(),
+            })
+            .unwrap();
+
+        assert_eq!(document.nodes()[1].members(), &vec![a_member(1), version]);
+        assert_eq!(
+            document.collection().members(),
+            &vec![
+                String::from("https://example.com/member/0"),
+                String::from("https://example.com/member/1"),
+                String::from("https://example.com/member/1v2")
+            ]
+        );
+    }
+
+    #[test]
+    fn should_split_the_full_nodes_into_a_chain() {
+        let states = evolve(
+            a_document(),
+            &[
+                Mutation::AppendMembers {
+                    node: String::from("https://example.com/a"),
+                    members: (2..7).map(a_member).collect(),
+                },
+                Mutation::SplitNodes { capacity: 2 },
+                Mutation::DeleteMembers {
+                    members: vec![
+                        String::from("https://example.com/member/6"),
+                        String::from("https://example.com/unknown"),
+                    ],
+                },
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(states.len(), 3);
+        assert_eq!(states[0].nodes().len(), 2);
+        let split = &states[1];
+        let node_urls: Vec<&String> = split.nodes().iter().map(|node| node.node_url()).collect();
+        assert_eq!(
+            node_urls,
+            vec![
+                "https://example.com/root",
+                "https://example.com/a",
+                "https://example.com/a/1",
+                "https://example.com/a/1/1"
+            ]
+        );
+        assert!(split.nodes().iter().all(|node| node.members().len() <= 2));
+        assert!(validate(split.nodes(), "https://example.com/root", true).is_valid());
+        assert_eq!(
+            member_urls(&states[2].nodes()[3]),
+            vec!["https://example.com/member/5"]
+        );
+        assert_eq!(states[2].collection().members().len(), 6);
+        assert_eq!(
+            states[2].nodes()[0].relation()[0].remaning_items(),
+            &Some(5)
+        );
+    }
+
+    #[test]
+    fn should_reject_an_invalid_mutation() {
+        let mut engine = MutationEngine::new(a_document());
+
+        assert!(engine
+            .apply(&Mutation::AppendMembers {
+                node: String::from("https://example.com/a"),
+                members: vec![a_member(0)],
+            })
+            .is_err());
+        assert!(engine
+            .apply(&Mutation::AppendMembers {
+                node: String::from("https://example.com/unknown"),
+                members: vec![a_member(2)],
+            })
+            .is_err());
+        assert!(engine
+            .apply(&Mutation::UpdateMember {
+                member: String::from("https://example.com/unknown"),
+                version: a_member(2),
+            })
+            .is_err());
+        assert!(engine
+            .apply(&Mutation::UpdateMember {
+                member: String::from("https://example.com/member/1"),
+                version: a_member(1),
+            })
+            .is_err());
+        assert!(engine.apply(&Mutation::SplitNodes { capacity: 0 }).is_err());
+        assert_eq!(engine.document().collection().members().len(), 2);
+    }
+
+    #[test]
+    fn should_remove_the_members_expired_by_every_policy() {
+        let timestamp_path = String::from("https://example.com/created");
+        let a_timestamped_member = |i: usize, timestamp: &str| {
+            let mut member = a_member(i);
+            member.properties.insert(
+                timestamp_path.clone(),
+                Value {
+                    value: String::from(timestamp),
+                    value_type: ValueType::DateTime,
+                },
+            );
+            member
+        };
+        let document = a_document()
+            .with_retention_policies(vec![
+                RetentionPolicy::DurationAgo {
+                    timestamp_path: timestamp_path.clone(),
+                    duration: String::from("PT1H"),
+                },
+                RetentionPolicy::LatestVersionSubset {
+                    timestamp_path: timestamp_path.clone(),
+                    version_of_path: String::from("https://example.com/p"),
+                    amount: 1,
+                },
+            ])
+            .unwrap();
+        let mut version = a_timestamped_member(0, "2022-01-01T08:00:00Z");
+        version.url = String::from("https://example.com/member/0v2");
+        let mut engine = MutationEngine::new(document);
+        engine
+            .apply(&Mutation::AppendMembers {
+                node: String::from("https://example.com/a"),
+                members: vec![
+                    a_timestamped_member(2, "2022-01-01T09:30:00Z"),
+                    a_timestamped_member(3, "2022-01-01T08:00:00Z"),
+                    version,
+                ],
+            })
+            .unwrap();
+
+        let document = engine
+            .apply(&Mutation::ApplyRetention {
+                now: Utc.with_ymd_and_hms(2022, 1, 1, 10, 0, 0).unwrap(),
+            })
+            .unwrap();
+
+        // the member 3 is old but the only version of its entity
+        assert_eq!(
+            member_urls(&document.nodes()[1]),
+            vec![
+                "https://example.com/member/1",
+                "https://example.com/member/2",
+                "https://example.com/member/3"
+            ]
+        );
+        assert_eq!(document.collection().members().len(), 4);
+        assert_eq!(document.retention_policies().len(), 2);
+    }
+}
@@ -0,0 +1,517 @@
+use super::unused_url;
+use crate::query::comparison::compare_values;
+use crate::query::filter::Filter;
+use crate::tree::member::Member;
+use crate::tree::node::Node;
+use crate::tree::relation::Relation;
+use crate::tree::relation_operator::RelationOperator;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::value::Value;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::vec::Vec;
+
+/// The maximum number of members of the nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capacity {
+    /// Capacity of the nodes without a capacity of their own.
+    pub default: usize,
+    /// Capacity of some nodes identified by their url, inherited by the nodes split from them.
+    pub by_node: HashMap<String, usize>,
+}
+
+impl Capacity {
+    /// A capacity shared by every node.
+    pub fn uniform(capacity: usize) -> Self {
+        Capacity {
+            default: capacity,
+            by_node: HashMap::new(),
+        }
+    }
+
+    /// The capacity of a node.
+    pub fn of(&self, node_url: &str) -> usize {
+        self.by_node.get(node_url).copied().unwrap_or(self.default)
+    }
+}
+
+/// Move the members of the nodes with children down to the children accepting them and split the leaves
+/// holding more members than their capacity at the median of their values over the path, until every leaf fits
+/// in its capacity or cannot be split because its values are equal or incomparable.
+/// A leaf keeps the members lower than the median and a new sibling `{parent_url}/{i}` receives the others,
+/// the relations of the parents being rewritten to bound the values of each of them.
+/// When the root is a full leaf, its members move to two new children, so the depth of the leaves grows evenly.
+pub(super) fn rebalance(
+    nodes: &mut Vec<Node>,
+    root_url: &str,
+    path: &ShaclPath,
+    capacity: &Capacity,
+) -> Result<(), &'static str> {
+    let mut capacities: HashMap<String, usize> = HashMap::new();
+    for node in nodes.iter() {
+        let capacity_of_the_node = capacity.of(node.node_url());
+        if capacity_of_the_node == 0 {
+            return Err("the capacity of a node should be at least 1");
+        }
+        capacities.insert(node.node_url().clone(), capacity_of_the_node);
+    }
+    let mut node_urls: HashSet<String> = nodes.iter().map(|node| node.node_url().clone()).collect();
+    loop {
+        let has_moved = push_down_members(nodes, path);
+        let has_split = split_a_leaf(nodes, root_url, path, &mut capacities, &mut node_urls);
+        if !has_moved && !has_split {
+            return Ok(());
+        }
+    }
+}
+
+/// Move the members of the nodes with children to the first child whose relations over the path they satisfy.
+/// Return whether a member has moved.
+fn push_down_members(nodes: &mut [Node], path: &ShaclPath) -> bool {
+    let index_of: HashMap<String, usize> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.node_url().clone(), i))
+        .collect();
+    let mut has_moved = false;
+    for i in 0..nodes.len() {
+        let node = &nodes[i];
+        if node.relation().is_empty() || node.members().is_empty() {
+            continue;
+        }
+        // the filters of the relations to each child, in order of appearance
+        let mut children: Vec<(&str, Vec<Filter>)> = Vec::new();
+        for relation in node.relation() {
+            let filter =
+                Filter::of_relation(relation).filter(|_| relation.path().as_ref() == Some(path));
+            match children
+                .iter_mut()
+                .find(|(child, _)| *child == relation.node().as_str())
+            {
+                Some((_, filters)) => filters.extend(filter),
+                None => children.push((relation.node().as_str(), filter.into_iter().collect())),
+            }
+        }
+        let mut kept: Vec<Member> = Vec::new();
+        let mut moved: Vec<(usize, Member)> = Vec::new();
+        for member in node.members() {
+            let child = children.iter().find(|(child, filters)| {
+                child != node.node_url()
+                    && index_of.contains_key(*child)
+                    && !filters.is_empty()
+                    && filters.iter().all(|filter| filter.matches(member))
+            });
+            match child {
+                Some((child, _)) => moved.push((index_of[*child], member.clone())),
+                None => kept.push(member.clone()),
+            }
+        }
+        if moved.is_empty() {
+            continue;
+        }
+        has_moved = true;
+        nodes[i] = Node::new(node.relation().clone(), node.node_url().clone(), kept);
+        for (j, member) in moved {
+            let child = &nodes[j];
+            let mut members = child.members().clone();
+            members.push(member);
+            nodes[j] = Node::new(child.relation().clone(), child.node_url().clone(), members);
+        }
+    }
+    has_moved
+}
+
+/// Split the first leaf holding more members than its capacity that can be split. Return whether a leaf was split.
+fn split_a_leaf(
+    nodes: &mut Vec<Node>,
+    root_url: &str,
+    path: &ShaclPath,
+    capacities: &mut HashMap<String, usize>,
+    node_urls: &mut HashSet<String>,
+) -> bool {
+    let split = nodes.iter().enumerate().find_map(|(i, node)| {
+        if node.relation().is_empty() && node.members().len() > capacities[node.node_url()] {
+            split_members(node.members(), path).map(|split| (i, split))
+        } else {
+            None
+        }
+    });
+    let (i, (median, lower, upper)) = match split {
+        Some(v) => v,
+        None => return false,
+    };
+    let node_url = nodes[i].node_url().clone();
+    let capacity_of_the_node = capacities[&node_url];
+
+    if node_url == root_url {
+        let lower_url = unused_url(node_urls, &node_url);
+        let upper_url = unused_url(node_urls, &node_url);
+        nodes[i] = Node::new(
+            vec![
+                a_bound(
+                    path,
+                    RelationOperator::LessThanRelation,
+                    &median,
+                    &lower_url,
+                ),
+                a_bound(
+                    path,
+                    RelationOperator::GreaterThanOrEqualToRelation,
+                    &median,
+                    &upper_url,
+                ),
+            ],
+            node_url,
+            Vec::new(),
+        );
+        for (url, members) in [(lower_url, lower), (upper_url, upper)] {
+            capacities.insert(url.clone(), capacity_of_the_node);
+            nodes.push(Node::new(Vec::new(), url, members));
+        }
+        return true;
+    }
+
+    let parent_indexes: Vec<usize> = nodes
+        .iter()
+        .enumerate()
+        .filter(|(_, parent)| {
+            parent
+                .relation()
+                .iter()
+                .any(|relation| relation.node() == &node_url)
+        })
+        .map(|(j, _)| j)
+        .collect();
+    let prefix = parent_indexes
+        .first()
+        .map(|j| nodes[*j].node_url().clone())
+        .unwrap_or_else(|| node_url.clone());
+    let upper_url = unused_url(node_urls, &prefix);
+    for j in parent_indexes {
+        let parent = &nodes[j];
+        let relations = split_relations(parent.relation(), &node_url, &upper_url, path, &median);
+        nodes[j] = Node::new(
+            relations,
+            parent.node_url().clone(),
+            parent.members().clone(),
+        );
+    }
+    nodes[i] = Node::new(Vec::new(), node_url, lower);
+    capacities.insert(upper_url.clone(), capacity_of_the_node);
+    nodes.push(Node::new(Vec::new(), upper_url, upper));
+    true
+}
+
+/// The median value of the members over the path, the members lower than it and the other members.
+/// The median is taken among the values comparable with the first value comparable with itself,
+/// so that a path mixing datatypes is still sorted consistently.
+/// The members without a value comparable with the median are lower. Return `None` when no value
+/// separates the members.
+fn split_members(
+    members: &[Member],
+    path: &ShaclPath,
+) -> Option<(Value, Vec<Member>, Vec<Member>)> {
+    let values: Vec<&Value> = members
+        .iter()
+        .filter_map(|member| member.properties.get(path))
+        .collect();
+    let reference = *values
+        .iter()
+        .find(|value| compare_values(value, value).is_some())?;
+    let mut values: Vec<&Value> = values
+        .into_iter()
+        .filter(|value| compare_values(value, reference).is_some())
+        .collect();
+    values.sort_by(|a, b| compare_values(a, b).unwrap_or(Ordering::Equal));
+    let median = (*values.get(values.len() / 2)?).clone();
+    let (upper, lower): (Vec<Member>, Vec<Member>) = members.iter().cloned().partition(|member| {
+        member
+            .properties
+            .get(path)
+            .and_then(|value| compare_values(value, &median))
+            .is_some_and(|ordering| ordering != Ordering::Less)
+    });
+    if lower.is_empty() || upper.is_empty() {
+        None
+    } else {
+        Some((median, lower, upper))
+    }
+}
+
+/// The relations of a parent after the split of the node into itself and the upper node.
+/// The relation bounding the values of the node from above are replaced by `< median` and the upper node
+/// receives the other relations of the node, its relations bounding the values from below being replaced by `>= median`.
+fn split_relations(
+    relations: &[Relation],
+    node_url: &str,
+    upper_url: &str,
+    path: &ShaclPath,
+    median: &Value,
+) -> Vec<Relation> {
+    let is_bound = |relation: &Relation, operators: &[RelationOperator]| {
+        relation.path().as_ref() == Some(path)
+            && relation
+                .relation_type()
+                .as_ref()
+                .is_some_and(|operator| operators.contains(operator))
+    };
+    let upper_bounds = [
+        RelationOperator::LessThanRelation,
+        RelationOperator::LessThanOrEqualToRelation,
+    ];
+    let lower_bounds = [
+        RelationOperator::GreaterThanRelation,
+        RelationOperator::GreaterThanOrEqualToRelation,
+    ];
+    let last_relation_to_the_node = relations
+        .iter()
+        .rposition(|relation| relation.node() == node_url)
+        .unwrap_or(relations.len());
+
+    let mut relations_to_the_upper_node: Vec<Relation> = Vec::new();
+    let mut new_relations: Vec<Relation> = Vec::new();
+    for (k, relation) in relations.iter().enumerate() {
+        if relation.node() == node_url {
+            if !is_bound(relation, &upper_bounds) {
+                new_relations.push(relation.clone());
+            }
+            if !is_bound(relation, &lower_bounds) {
+                relations_to_the_upper_node.push(Relation::new(
+                    *relation.remaning_items(),
+                    relation.path().clone(),
+                    relation.value().clone(),
+                    String::from(upper_url),
+                    relation.relation_type().clone(),
+                ));
+            }
+        } else {
+            new_relations.push(relation.clone());
+        }
+        if k == last_relation_to_the_node {
+            new_relations.push(a_bound(
+                path,
+                RelationOperator::LessThanRelation,
+                median,
+                node_url,
+            ));
+            new_relations.push(a_bound(
+                path,
+                RelationOperator::GreaterThanOrEqualToRelation,
+                median,
+                upper_url,
+            ));
+            new_relations.append(&mut relations_to_the_upper_node);
+        }
+    }
+    new_relations
+}
+
+fn a_bound(
+    path: &ShaclPath,
+    operator: RelationOperator,
+    value: &Value,
+    node_url: &str,
+) -> Relation {
+    Relation::new(
+        Some(0),
+        Some(path.clone()),
+        Some(value.clone()),
+        String::from(node_url),
+        Some(operator),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capacity;
+    use crate::mutation::{Mutation, MutationEngine};
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use crate::validator::validate;
+    use std::collections::HashMap;
+
+    const A_PATH: &str = "https://example.com/p";
+    const ROOT_URL: &str = "https://example.com/root";
+
+    fn a_member(i: usize) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([(
+                String::from(A_PATH),
+                Value {
+                    value: i.to_string(),
+                    value_type: ValueType::Int,
+                },
+            )]),
+        }
+    }
+
+    fn a_document(members: Vec<Member>) -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from(ROOT_URL),
+                members.iter().map(|member| member.url.clone()).collect(),
+                None,
+            ),
+            vec![Node::new(Vec::new(), String::from(ROOT_URL), members)],
+            None,
+            None,
+        )
+    }
+
+    fn a_rebalance(capacity: Capacity) -> Mutation {
+        Mutation::Rebalance {
+            path: String::from(A_PATH),
+            capacity,
+        }
+    }
+
+    fn bounds(relations: &[Relation]) -> Vec<(RelationOperator, &str, &str, i32)> {
+        relations
+            .iter()
+            .map(|relation| {
+                (
+                    relation.relation_type().clone().unwrap(),
+                    relation.value().as_ref().unwrap().value.as_str(),
+                    relation.node().as_str(),
+                    relation.remaning_items().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_split_the_full_leaves_at_their_median() {
+        let mut engine = MutationEngine::new(a_document((0..8).map(a_member).collect()));
+
+        let document = engine.apply(&a_rebalance(Capacity::uniform(3))).unwrap();
+
+        assert_eq!(
+            bounds(document.nodes()[0].relation()),
+            vec![
+                (
+                    RelationOperator::LessThanRelation,
+                    "2",
+                    "https://example.com/root/1",
+                    2
+                ),
+                (
+                    RelationOperator::GreaterThanOrEqualToRelation,
+                    "2",
+                    "https://example.com/root/3",
+                    2
+                ),
+                (
+                    RelationOperator::LessThanRelation,
+                    "4",
+                    "https://example.com/root/3",
+                    2
+                ),
+                (
+                    RelationOperator::GreaterThanOrEqualToRelation,
+                    "4",
+                    "https://example.com/root/2",
+                    2
+                ),
+                (
+                    RelationOperator::LessThanRelation,
+                    "6",
+                    "https://example.com/root/2",
+                    2
+                ),
+                (
+                    RelationOperator::GreaterThanOrEqualToRelation,
+                    "6",
+                    "https://example.com/root/4",
+                    2
+                ),
+            ]
+        );
+        assert!(document.nodes()[0].members().is_empty());
+        assert!(document.nodes()[1..]
+            .iter()
+            .all(|node| node.relation().is_empty() && node.members().len() == 2));
+        assert!(validate(document.nodes(), ROOT_URL, true).is_valid());
+    }
+
+    #[test]
+    fn should_grow_with_the_appended_members() {
+        let mut engine = MutationEngine::new(a_document(Vec::new()));
+        let mut capacity = Capacity::uniform(4);
+        capacity.by_node.insert(String::from(ROOT_URL), 2);
+
+        for i in 0..20 {
+            engine
+                .apply(&Mutation::AppendMembers {
+                    node: String::from(ROOT_URL),
+                    members: vec![a_member((i * 7) % 20)],
+                })
+                .unwrap();
+            let document = engine.apply(&a_rebalance(capacity.clone())).unwrap();
+
+            assert!(validate(document.nodes(), ROOT_URL, true).is_valid());
+        }
+        let document = engine.document();
+        assert_eq!(document.collection().members().len(), 20);
+        assert!(document.nodes()[0].members().is_empty());
+        assert!(document.nodes()[1..]
+            .iter()
+            .all(|node| node.relation().is_empty() && node.members().len() <= 4));
+    }
+
+    #[test]
+    fn should_not_split_members_with_the_same_value() {
+        let members: Vec<Member> = (0..4)
+            .map(|i| {
+                let mut member = a_member(1);
+                member.url = format!("https://example.com/member/{}", i);
+                member
+            })
+            .collect();
+        let mut engine = MutationEngine::new(a_document(members));
+
+        let document = engine.apply(&a_rebalance(Capacity::uniform(2))).unwrap();
+
+        assert_eq!(document.nodes().len(), 1);
+        assert!(engine.apply(&a_rebalance(Capacity::uniform(0))).is_err());
+    }
+
+    #[test]
+    fn should_split_members_with_values_of_mixed_datatypes() {
+        // shuffled integers, one member in eight having a string instead
+        let members: Vec<Member> = (0..200)
+            .map(|i| {
+                let mut member = a_member((i * 7919) % 200);
+                if i % 8 == 0 {
+                    member.properties.insert(
+                        String::from(A_PATH),
+                        Value {
+                            value: format!("v{}", i),
+                            value_type: ValueType::String,
+                        },
+                    );
+                }
+                member
+            })
+            .collect();
+        let mut engine = MutationEngine::new(a_document(members));
+
+        let document = engine.apply(&a_rebalance(Capacity::uniform(50))).unwrap();
+
+        assert!(document.nodes().len() > 1);
+        assert_eq!(
+            document
+                .nodes()
+                .iter()
+                .map(|node| node.members().len())
+                .sum::<usize>(),
+            200
+        );
+    }
+}
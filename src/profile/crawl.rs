@@ -0,0 +1,229 @@
+use crate::rdf::format::RdfFormat;
+use crate::rdf::triple::Triple;
+use crate::tree::collection::Collection;
+use crate::tree::node::Node;
+use crate::tree::page::Page;
+use crate::tree::tree_document::TreeDocument;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::vec::Vec;
+
+/// Crawl a TREE dataset from its root node, following every relation in breadth-first order.
+/// `fetch` returns the triples of the page at a url, `None` when the page cannot be fetched,
+/// in which case the node is left out of the document, as it is when the page does not describe it.
+/// The collection is the one of the root page or, when the root page describes none,
+/// a collection identified by the root url.
+pub fn crawl(
+    root_url: &str,
+    mut fetch: impl FnMut(&str) -> Option<Vec<Triple>>,
+) -> Result<TreeDocument, &'static str> {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut collection: Option<Collection> = None;
+    let mut known_url: HashSet<String> = HashSet::from([String::from(root_url)]);
+    let mut pending_url: VecDeque<String> = VecDeque::from([String::from(root_url)]);
+
+    while let Some(node_url) = pending_url.pop_front() {
+        let triples = match fetch(&node_url) {
+            Some(v) => v,
+            None => continue,
+        };
+        let page = match Page::from_triples(&triples, &node_url) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        for relation in page.node.relation() {
+            if known_url.insert(relation.node().clone()) {
+                pending_url.push_back(relation.node().clone());
+            }
+        }
+        if node_url == root_url {
+            collection = page.collection;
+        }
+        nodes.push(page.node);
+    }
+
+    let mut members: Vec<String> = Vec::new();
+    let mut known_member: HashSet<&str> = HashSet::new();
+    for member in nodes.iter().flat_map(|node| node.members().iter()) {
+        if known_member.insert(&member.url) {
+            members.push(member.url.clone());
+        }
+    }
+    let collection = match collection {
+        Some(v) => Collection::new(
+            v.url().clone(),
+            v.view().clone(),
+            members,
+            v.shape().clone(),
+        ),
+        None => Collection::new(
+            String::from(root_url),
+            String::from(root_url),
+            members,
+            None,
+        ),
+    };
+    Ok(TreeDocument::new(collection, nodes, None, None))
+}
+
+/// Fetch the pages of a dataset stored in a directory, the page at `{base_url}/{path}` being
+/// the file `{directory}/{path}` in the format. A page that cannot be read or parsed is not fetched,
+/// nor a page outside of the base url or whose path is absolute or has a `..` component,
+/// which could lead outside of the directory.
+pub fn fetch_from_directory(
+    directory: PathBuf,
+    base_url: String,
+    format: RdfFormat,
+) -> impl FnMut(&str) -> Option<Vec<Triple>> {
+    move |url: &str| {
+        let rest = url.strip_prefix(base_url.as_str())?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+        let path = Path::new(rest.trim_start_matches('/'));
+        if !path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+        let document = fs::read_to_string(directory.join(path)).ok()?;
+        format.parse(&document, Some(url)).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crawl, fetch_from_directory};
+    use crate::rdf::format::RdfFormat;
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::tree_document::TreeDocument;
+    use std::collections::HashMap;
+    use std::fs;
+
+    fn a_node(url: &str, targets: &[&str], member: &str) -> Node {
+        Node::new(
+            targets
+                .iter()
+                .map(|target| {
+                    Relation::new(
+                        None,
+                        None,
+                        None,
+                        format!("https://example.com/{}", target),
+                        None,
+                    )
+                })
+                .collect(),
+            format!("https://example.com/{}", url),
+            vec![Member {
+                url: format!("https://example.com/member/{}", member),
+                properties: HashMap::new(),
+            }],
+        )
+    }
+
+    #[test]
+    fn should_crawl_the_pages_of_a_directory() {
+        let document = TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                a_node("root", &["a", "b"], "0"),
+                a_node("a", &["b", "missing", "other"], "1"),
+                a_node("b", &[], "1"),
+                a_node("elsewhere", &[], "2"),
+            ],
+            None,
+            None,
+        );
+        let directory = std::env::temp_dir().join(format!("crawl-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        for url in ["root", "a", "b"] {
+            let triples = document
+                .page(&format!("https://example.com/{}", url))
+                .unwrap();
+            fs::write(directory.join(url), RdfFormat::Turtle.serialize(&triples)).unwrap();
+        }
+        // a page that does not describe its node
+        let triples = document.page("https://example.com/elsewhere").unwrap();
+        fs::write(
+            directory.join("other"),
+            RdfFormat::Turtle.serialize(&triples),
+        )
+        .unwrap();
+
+        let crawled = crawl(
+            "https://example.com/root",
+            fetch_from_directory(
+                directory.clone(),
+                String::from("https://example.com"),
+                RdfFormat::Turtle,
+            ),
+        )
+        .unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let urls: Vec<&String> = crawled.nodes().iter().map(|node| node.node_url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://example.com/root",
+                "https://example.com/a",
+                "https://example.com/b"
+            ]
+        );
+        assert_eq!(crawled.collection().url(), "https://example.com/collection");
+        assert_eq!(
+            crawled.collection().members(),
+            &vec![
+                String::from("https://example.com/member/0"),
+                String::from("https://example.com/member/1")
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_fetch_a_page_outside_of_the_directory() {
+        let directory = std::env::temp_dir().join(format!("crawl-{}", uuid::Uuid::new_v4()));
+        let page_directory = directory.join("pages");
+        fs::create_dir_all(page_directory.join("evil")).unwrap();
+        fs::write(
+            page_directory.join("evil").join("x"),
+            "<https://example.com/a> <https://example.com/b> <https://example.com/c> .",
+        )
+        .unwrap();
+        fs::write(
+            directory.join("secret"),
+            "<https://example.com/a> <https://example.com/b> <https://example.com/c> .",
+        )
+        .unwrap();
+        let mut fetch = fetch_from_directory(
+            page_directory,
+            String::from("https://example.com"),
+            RdfFormat::Turtle,
+        );
+
+        let outside = fetch("https://example.com/../secret");
+        let absolute = fetch(&format!(
+            "https://example.com/{}",
+            directory.join("secret").display()
+        ));
+        let other_host = fetch("https://example.comevil/x");
+        let inside = fetch("https://example.com/evil/x");
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(outside.is_none());
+        assert!(absolute.is_none());
+        assert!(other_host.is_none());
+        assert!(inside.is_some());
+    }
+}
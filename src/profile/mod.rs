@@ -0,0 +1,594 @@
+//! Statistics of a TREE dataset, used to generate a synthetic dataset resembling it.
+pub mod crawl;
+
+use crate::generator_argument::member_argument::{
+    DistributionOfMember, MemberGeneratorArg, PropertyTemplate,
+};
+use crate::generator_argument::range::{QuantileRange, WeightedChoice};
+use crate::generator_argument::relation_argument::{
+    DistributionOfRelation, LevelDistribution, RelationGeneratorArg, RelationTemplate,
+    TemplateRangeVariationRelation, TreeShape,
+};
+use crate::generator_argument::{Args, RangeParameter};
+use crate::query::comparison::is_numeric;
+use crate::rdf::vocabulary;
+use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+use crate::tree::node::Node;
+use crate::tree::relation_operator::RelationOperator;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::tree_document::TreeDocument;
+use crate::tree::value::{Value, ValueType};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+
+/// Number of quantiles describing the distribution of the numbers, the deciles.
+const N_QUANTILE: usize = 11;
+
+/// The statistics of the nodes of a TREE dataset reachable from its root.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct Profile {
+    /// Number of nodes by number of relations.
+    pub fan_out: BTreeMap<usize, usize>,
+    /// Number of nodes at each depth, the root being at the depth 0.
+    pub nodes_by_depth: Vec<usize>,
+    /// Number of relations by operator, `tree:Relation` standing for the relations without operator.
+    pub operators: BTreeMap<String, usize>,
+    /// Number of relations by operator for every path.
+    pub operators_by_path: BTreeMap<ShaclPath, BTreeMap<String, usize>>,
+    /// The values of the relations and of the members by path.
+    pub paths: BTreeMap<ShaclPath, PathProfile>,
+    /// Number of nodes by number of members.
+    pub members_by_node: BTreeMap<usize, usize>,
+    /// How accurate the `tree:remainingItems` of the relations are.
+    pub remaining_items: RemainingItemsProfile,
+}
+
+/// The values of a path.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct PathProfile {
+    /// Number of values by datatype.
+    pub datatypes: BTreeMap<String, usize>,
+    /// The numbers of the relations, if any.
+    pub relation_values: Option<NumberProfile>,
+    /// The numbers of the members, if any.
+    pub member_values: Option<NumberProfile>,
+}
+
+/// The distribution of numbers.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct NumberProfile {
+    pub count: usize,
+    pub mean: f64,
+    /// The deciles, from the minimum to the maximum.
+    pub quantiles: Vec<f64>,
+    /// Whether every number is an integer.
+    pub is_integral: bool,
+}
+
+/// Comparison of the `tree:remainingItems` of the relations with the number of members
+/// reachable from the nodes they target.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct RemainingItemsProfile {
+    /// Number of relations with a `tree:remainingItems`.
+    pub n_relation: usize,
+    /// Number of relations whose `tree:remainingItems` is exact.
+    pub n_exact: usize,
+    /// Mean of the absolute difference between the announced and the actual number of members.
+    pub mean_absolute_error: f64,
+}
+
+impl Profile {
+    /// Compute the profile of the nodes of the document reachable from the view of its collection.
+    pub fn of(document: &TreeDocument) -> Profile {
+        let nodes_by_url: HashMap<&str, &Node> = document
+            .nodes()
+            .iter()
+            .map(|node| (node.node_url().as_str(), node))
+            .collect();
+        let depths = depths(&nodes_by_url, document.collection().view());
+
+        let mut fan_out: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut nodes_by_depth: Vec<usize> = Vec::new();
+        let mut operators: BTreeMap<String, usize> = BTreeMap::new();
+        let mut operators_by_path: BTreeMap<ShaclPath, BTreeMap<String, usize>> = BTreeMap::new();
+        let mut members_by_node: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut relation_values: BTreeMap<&ShaclPath, Vec<&Value>> = BTreeMap::new();
+        let mut member_values: BTreeMap<&ShaclPath, Vec<&Value>> = BTreeMap::new();
+        let mut seen_members: HashSet<&str> = HashSet::new();
+
+        for (node_url, depth) in depths.iter() {
+            let node = nodes_by_url[node_url];
+            *fan_out.entry(node.relation().len()).or_default() += 1;
+            if nodes_by_depth.len() <= *depth {
+                nodes_by_depth.resize(depth + 1, 0);
+            }
+            nodes_by_depth[*depth] += 1;
+            *members_by_node.entry(node.members().len()).or_default() += 1;
+
+            for relation in node.relation() {
+                let operator = match relation.relation_type() {
+                    Some(v) => v.to_string(),
+                    None => String::from(vocabulary::TREE_RELATION_CLASS),
+                };
+                if let Some(path) = relation.path() {
+                    *operators_by_path
+                        .entry(path.clone())
+                        .or_default()
+                        .entry(operator.clone())
+                        .or_default() += 1;
+                    if let Some(value) = relation.value() {
+                        relation_values.entry(path).or_default().push(value);
+                    }
+                }
+                *operators.entry(operator).or_default() += 1;
+            }
+            for member in node.members() {
+                if !seen_members.insert(&member.url) {
+                    continue;
+                }
+                for (path, value) in member.properties.iter() {
+                    member_values.entry(path).or_default().push(value);
+                }
+            }
+        }
+
+        let mut paths: BTreeMap<ShaclPath, PathProfile> = BTreeMap::new();
+        for path in relation_values.keys().chain(member_values.keys()) {
+            if paths.contains_key(*path) {
+                continue;
+            }
+            let relation_values = relation_values
+                .get(path)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let member_values = member_values
+                .get(path)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut datatypes: BTreeMap<String, usize> = BTreeMap::new();
+            for value in relation_values.iter().chain(member_values.iter()) {
+                *datatypes.entry(value.value_type.to_string()).or_default() += 1;
+            }
+            paths.insert(
+                (*path).clone(),
+                PathProfile {
+                    datatypes,
+                    relation_values: NumberProfile::of(relation_values),
+                    member_values: NumberProfile::of(member_values),
+                },
+            );
+        }
+
+        Profile {
+            fan_out,
+            nodes_by_depth,
+            operators,
+            operators_by_path,
+            paths,
+            members_by_node,
+            remaining_items: RemainingItemsProfile::of(&nodes_by_url, &depths),
+        }
+    }
+
+    /// Number of nodes reachable from the root.
+    pub fn n_node(&self) -> usize {
+        self.nodes_by_depth.iter().sum()
+    }
+
+    /// The arguments generating a dataset resembling the profiled one with about `scale` times
+    /// as many nodes. The relations follow the most frequent operator among the paths with numbers,
+    /// their values being drawn from the observed distribution. The tree has the depth of the
+    /// profiled one, the nodes of a level having on average as many children as the profiled
+    /// nodes of the same depth, the root having `scale` times more. The members have the numeric
+    /// properties of the profiled members.
+    pub fn to_args(&self, scale: f64, base_url: String) -> Result<Args<f64>, &'static str> {
+        let (path, relation_values, operator) = self
+            .operators_by_path
+            .iter()
+            .filter(|(path, _)| is_numeric(&self.value_type_of(path)))
+            .filter_map(|(path, operators)| {
+                self.paths
+                    .get(path)
+                    .and_then(|profile| profile.relation_values.as_ref())
+                    .map(|relation_values| (path, relation_values, operators))
+            })
+            .flat_map(|(path, relation_values, operators)| {
+                operators.iter().filter_map(move |(operator, count)| {
+                    RelationOperator::from_iri(operator)
+                        .map(|operator| (path, relation_values, operator, *count))
+                })
+            })
+            .max_by_key(|(_, _, _, count)| *count)
+            .map(|(path, relation_values, operator, _)| (path, relation_values, operator))
+            .ok_or("the dataset has no relation with an operator over numbers")?;
+
+        let fan_out_by_level = self
+            .nodes_by_depth
+            .windows(2)
+            .enumerate()
+            .map(|(depth, n_nodes)| {
+                let mean_fan_out = n_nodes[1] as f64 / n_nodes[0] as f64;
+                let mean_fan_out = if depth == 0 {
+                    mean_fan_out * scale
+                } else {
+                    mean_fan_out
+                };
+                mean_range(mean_fan_out)
+            })
+            .collect::<Option<Vec<Box<dyn RangeParameter<usize>>>>>()
+            .ok_or("the fan-out of the nodes should be finite")?;
+        if fan_out_by_level.is_empty() {
+            return Err("the dataset has no relation");
+        }
+
+        Ok(Args {
+            relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                template: RelationTemplate {
+                    path: path.clone(),
+                    relation_type: operator,
+                },
+                range: Box::new(relation_values.range()),
+                distribution_of_relation: DistributionOfRelation::ByLevel(LevelDistribution {
+                    max_depth: fan_out_by_level.len(),
+                    fan_out_by_level,
+                    shape: TreeShape::Unbalanced,
+                }),
+                value_type: self.value_type_of(path),
+                out_of_range_strategy: OutOfRangeStrategy::Clamp,
+                temporal_format: TemporalFormat::default(),
+            }),
+            members: self.member_args(),
+            search_form: None,
+            topology: None,
+            base_url,
+        })
+    }
+
+    /// The members with the numeric properties of the profiled members, `None` if there is no such property.
+    fn member_args(&self) -> Option<MemberGeneratorArg<f64>> {
+        let properties: Vec<PropertyTemplate<f64>> = self
+            .paths
+            .iter()
+            .filter(|(path, _)| is_numeric(&self.value_type_of(path)))
+            .filter_map(|(path, profile)| {
+                profile
+                    .member_values
+                    .as_ref()
+                    .map(|member_values| PropertyTemplate {
+                        path: path.clone(),
+                        range: Box::new(member_values.range()),
+                        value_type: self.value_type_of(path),
+                        out_of_range_strategy: OutOfRangeStrategy::Clamp,
+                        temporal_format: TemporalFormat::default(),
+                    })
+            })
+            .collect();
+        let distribution = WeightedChoice::new(
+            self.members_by_node
+                .iter()
+                .map(|(n_member, count)| (*n_member, *count))
+                .collect(),
+        )?;
+        if properties.is_empty() {
+            return None;
+        }
+        Some(MemberGeneratorArg {
+            properties,
+            distribution_of_member: DistributionOfMember::Random(Box::new(distribution)),
+            versioning: None,
+        })
+    }
+
+    /// The most frequent value type of a path.
+    fn value_type_of(&self, path: &ShaclPath) -> ValueType {
+        self.paths
+            .get(path)
+            .and_then(|profile| profile.datatypes.iter().max_by_key(|(_, count)| **count))
+            .map(|(datatype, _)| ValueType::from_datatype(datatype))
+            .unwrap_or(ValueType::String)
+    }
+}
+
+/// A range of the two integers around a mean, drawn so that the mean of the draws is the mean.
+fn mean_range(mean: f64) -> Option<Box<dyn RangeParameter<usize>>> {
+    if !mean.is_finite() || mean < 0.0 {
+        return None;
+    }
+    let lower = mean.floor();
+    let weight_of_upper = ((mean - lower) * 1000.0).round() as usize;
+    let range = WeightedChoice::new(vec![
+        (lower as usize, 1000 - weight_of_upper),
+        (lower as usize + 1, weight_of_upper),
+    ])?;
+    Some(Box::new(range))
+}
+
+impl NumberProfile {
+    /// The profile of the numbers among the values, `None` if there is none.
+    fn of(values: &[&Value]) -> Option<NumberProfile> {
+        let mut numbers: Vec<f64> = values
+            .iter()
+            .filter(|value| is_numeric(&value.value_type))
+            .filter_map(|value| value.value.parse::<f64>().ok())
+            .filter(|number| number.is_finite())
+            .collect();
+        if numbers.is_empty() {
+            return None;
+        }
+        numbers.sort_by(|a, b| a.total_cmp(b));
+        let quantiles = (0..N_QUANTILE)
+            .map(|i| numbers[i * (numbers.len() - 1) / (N_QUANTILE - 1)])
+            .collect();
+        Some(NumberProfile {
+            count: numbers.len(),
+            mean: numbers.iter().sum::<f64>() / numbers.len() as f64,
+            quantiles,
+            is_integral: numbers.iter().all(|number| number.fract() == 0.0),
+        })
+    }
+
+    /// A range drawing numbers following the distribution.
+    pub fn range(&self) -> QuantileRange {
+        QuantileRange::new(self.quantiles.clone(), self.is_integral)
+    }
+}
+
+impl RemainingItemsProfile {
+    fn of(nodes_by_url: &HashMap<&str, &Node>, depths: &[(&str, usize)]) -> RemainingItemsProfile {
+        let mut reachable_members: HashMap<&str, usize> = HashMap::new();
+        let mut n_relation = 0;
+        let mut n_exact = 0;
+        let mut total_error = 0.0;
+        for (node_url, _) in depths {
+            for relation in nodes_by_url[node_url].relation() {
+                let remaining_items = match relation.remaning_items() {
+                    Some(v) => *v as f64,
+                    None => continue,
+                };
+                let target = relation.node().as_str();
+                let actual = *reachable_members
+                    .entry(target)
+                    .or_insert_with(|| count_reachable_members(nodes_by_url, target))
+                    as f64;
+                n_relation += 1;
+                if remaining_items == actual {
+                    n_exact += 1;
+                }
+                total_error += (remaining_items - actual).abs();
+            }
+        }
+        RemainingItemsProfile {
+            n_relation,
+            n_exact,
+            mean_absolute_error: if n_relation == 0 {
+                0.0
+            } else {
+                total_error / n_relation as f64
+            },
+        }
+    }
+}
+
+/// The nodes reachable from the root with their depth, in breadth-first order.
+fn depths<'a>(
+    nodes_by_url: &HashMap<&'a str, &'a Node>,
+    root_url: &'a str,
+) -> Vec<(&'a str, usize)> {
+    let mut depths: Vec<(&str, usize)> = Vec::new();
+    if !nodes_by_url.contains_key(root_url) {
+        return depths;
+    }
+    let mut visited: HashSet<&str> = HashSet::from([root_url]);
+    let mut pending: VecDeque<(&str, usize)> = VecDeque::from([(root_url, 0)]);
+    while let Some((url, depth)) = pending.pop_front() {
+        depths.push((url, depth));
+        for relation in nodes_by_url[url].relation() {
+            let target = relation.node().as_str();
+            if nodes_by_url.contains_key(target) && visited.insert(target) {
+                pending.push_back((target, depth + 1));
+            }
+        }
+    }
+    depths
+}
+
+/// Number of distinct members of the node and of the nodes reachable from it.
+pub(crate) fn count_reachable_members(
+    nodes_by_url: &HashMap<&str, &Node>,
+    node_url: &str,
+) -> usize {
+    let mut members: HashSet<&str> = HashSet::new();
+    let mut visited: HashSet<&str> = HashSet::from([node_url]);
+    let mut pending: VecDeque<&str> = VecDeque::from([node_url]);
+    while let Some(url) = pending.pop_front() {
+        let node = match nodes_by_url.get(url) {
+            Some(v) => v,
+            None => continue,
+        };
+        members.extend(node.members().iter().map(|member| member.url.as_str()));
+        for relation in node.relation() {
+            if visited.insert(relation.node().as_str()) {
+                pending.push_back(relation.node().as_str());
+            }
+        }
+    }
+    members.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Profile;
+    use crate::generate_tree_document;
+    use crate::rdf::vocabulary;
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::{BTreeMap, HashMap};
+
+    const A_PATH: &str = "https://example.com/p";
+
+    fn a_relation(target: &str, value: i32, remaining_items: Option<i32>) -> Relation {
+        Relation::new(
+            remaining_items,
+            Some(String::from(A_PATH)),
+            Some(Value {
+                value: value.to_string(),
+                value_type: ValueType::Int,
+            }),
+            format!("https://example.com/{}", target),
+            Some(RelationOperator::GreaterThanOrEqualToRelation),
+        )
+    }
+
+    fn a_member(i: i32) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([(
+                String::from(A_PATH),
+                Value {
+                    value: i.to_string(),
+                    value_type: ValueType::Int,
+                },
+            )]),
+        }
+    }
+
+    fn a_document() -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                Node::new(
+                    vec![
+                        a_relation("a", 0, Some(2)),
+                        a_relation("b", 10, Some(5)),
+                        Relation::new(
+                            None,
+                            None,
+                            None,
+                            String::from("https://example.com/c"),
+                            None,
+                        ),
+                    ],
+                    String::from("https://example.com/root"),
+                    vec![a_member(1)],
+                ),
+                Node::new(
+                    vec![a_relation("b", 10, None)],
+                    String::from("https://example.com/a"),
+                    vec![a_member(2)],
+                ),
+                Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/b"),
+                    vec![a_member(12)],
+                ),
+                Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/c"),
+                    Vec::new(),
+                ),
+                Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/unreachable"),
+                    Vec::new(),
+                ),
+            ],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn should_profile_the_reachable_nodes() {
+        let profile = Profile::of(&a_document());
+
+        assert_eq!(profile.n_node(), 4);
+        assert_eq!(profile.fan_out, BTreeMap::from([(0, 2), (1, 1), (3, 1)]));
+        assert_eq!(profile.nodes_by_depth, vec![1, 3]);
+        assert_eq!(
+            profile.operators,
+            BTreeMap::from([
+                (String::from(vocabulary::TREE_RELATION_CLASS), 1),
+                (
+                    RelationOperator::GreaterThanOrEqualToRelation.to_string(),
+                    3
+                ),
+            ])
+        );
+        assert_eq!(profile.members_by_node, BTreeMap::from([(0, 1), (1, 3)]));
+        let path = &profile.paths[A_PATH];
+        assert_eq!(path.relation_values.as_ref().unwrap().count, 3);
+        let member_values = path.member_values.as_ref().unwrap();
+        assert_eq!(member_values.mean, 5.0);
+        assert_eq!(member_values.quantiles.first(), Some(&1.0));
+        assert_eq!(member_values.quantiles.last(), Some(&12.0));
+        assert!(member_values.is_integral);
+        assert_eq!(profile.remaining_items.n_relation, 2);
+        assert_eq!(profile.remaining_items.n_exact, 1);
+        assert_eq!(profile.remaining_items.mean_absolute_error, 2.0);
+        assert!(serde_json::to_string(&profile).is_ok());
+    }
+
+    #[test]
+    fn should_generate_a_similar_dataset() {
+        let profile = Profile::of(&a_document());
+
+        let args = profile
+            .to_args(10.0, String::from("https://example.com"))
+            .unwrap();
+        let document = generate_tree_document(&args).unwrap();
+
+        let generated = Profile::of(&document);
+        assert_eq!(generated.nodes_by_depth, vec![1, 30]);
+        assert_eq!(
+            generated.operators.keys().collect::<Vec<&String>>(),
+            vec![&RelationOperator::GreaterThanOrEqualToRelation.to_string()]
+        );
+        let member_values = generated.paths[A_PATH].member_values.as_ref().unwrap();
+        assert!(member_values.is_integral);
+        assert!(member_values.quantiles[0] >= 1.0);
+        assert!(member_values.quantiles[10] <= 12.0);
+    }
+
+    #[test]
+    fn should_ignore_the_paths_of_relations_without_value() {
+        let mut document = a_document();
+        let root = &document.nodes()[0];
+        let mut relations = root.relation().clone();
+        relations.push(Relation::new(
+            None,
+            Some(String::from("https://example.com/q")),
+            None,
+            String::from("https://example.com/c"),
+            Some(RelationOperator::LessThanRelation),
+        ));
+        let root = Node::new(relations, root.node_url().clone(), root.members().clone());
+        document = TreeDocument::new(
+            document.collection().clone(),
+            std::iter::once(root)
+                .chain(document.nodes()[1..].iter().cloned())
+                .collect(),
+            None,
+            None,
+        );
+
+        let args = Profile::of(&document)
+            .to_args(1.0, String::from("https://example.com"))
+            .unwrap();
+
+        let generated = Profile::of(&generate_tree_document(&args).unwrap());
+        assert_eq!(
+            generated.operators_by_path.keys().collect::<Vec<&String>>(),
+            vec![A_PATH]
+        );
+    }
+}
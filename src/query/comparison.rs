@@ -0,0 +1,262 @@
+use crate::tree::value::{Value, ValueType};
+use crate::validator::lexical_form::{is_year, without_timezone};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use std::cmp::Ordering;
+
+/// Compare two values following the
+/// [operator mapping](https://www.w3.org/TR/sparql11-query/#OperatorMapping) of SPARQL,
+/// `None` if the values cannot be compared, like a number and a string.
+/// The dates, times and years can only be compared when they share a timezone, or both have none.
+/// The values of the other types can only be equal.
+pub fn compare_values(a: &Value, b: &Value) -> Option<Ordering> {
+    if is_numeric(&a.value_type) && is_numeric(&b.value_type) {
+        return compare_numbers(a, b);
+    }
+    match (&a.value_type, &b.value_type) {
+        (ValueType::Boolean, ValueType::Boolean) => {
+            Some(parse_boolean(&a.value)?.cmp(&parse_boolean(&b.value)?))
+        }
+        (ValueType::DateTime, ValueType::DateTime) => compare_date_times(&a.value, &b.value),
+        (ValueType::Date, ValueType::Date) => compare_with_timezones(&a.value, &b.value, |v| {
+            NaiveDate::parse_from_str(v, "%Y-%m-%d").ok()
+        }),
+        (ValueType::Time, ValueType::Time) => compare_with_timezones(&a.value, &b.value, |v| {
+            NaiveTime::parse_from_str(v, "%H:%M:%S%.f").ok()
+        }),
+        (ValueType::GYear, ValueType::GYear) => compare_with_timezones(&a.value, &b.value, |v| {
+            is_year(v).then(|| v.parse::<i64>().ok()).flatten()
+        }),
+        (ValueType::String, ValueType::String) => Some(a.value.cmp(&b.value)),
+        (ValueType::LangString(a_language), ValueType::LangString(b_language))
+            if a_language.eq_ignore_ascii_case(b_language) =>
+        {
+            Some(a.value.cmp(&b.value))
+        }
+        (a_type, b_type) if a_type == b_type && a.value == b.value => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+/// Whether the value type is a number.
+pub(crate) fn is_numeric(value_type: &ValueType) -> bool {
+    matches!(
+        value_type,
+        ValueType::Integer
+            | ValueType::Decimal
+            | ValueType::Float
+            | ValueType::Double
+            | ValueType::NonPositiveInteger
+            | ValueType::NegativeInteger
+            | ValueType::Long
+            | ValueType::Int
+            | ValueType::Short
+            | ValueType::Byte
+            | ValueType::NonNegativeInteger
+            | ValueType::UnsignedLong
+            | ValueType::UnsignedInt
+            | ValueType::UnsignedShort
+            | ValueType::UnsignedByte
+            | ValueType::PositiveInteger
+    )
+}
+
+/// Compare the integers exactly and the other numbers as doubles.
+fn compare_numbers(a: &Value, b: &Value) -> Option<Ordering> {
+    if let (Ok(a), Ok(b)) = (a.value.parse::<i128>(), b.value.parse::<i128>()) {
+        return Some(a.cmp(&b));
+    }
+    let a: f64 = a.value.parse().ok()?;
+    let b: f64 = b.value.parse().ok()?;
+    a.partial_cmp(&b)
+}
+
+fn parse_boolean(value: &str) -> Option<bool> {
+    match value {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Compare the instants of two date times having a timezone, or the local date times of two
+/// date times without one.
+fn compare_date_times(a: &str, b: &str) -> Option<Ordering> {
+    match (
+        DateTime::parse_from_rfc3339(a),
+        DateTime::parse_from_rfc3339(b),
+    ) {
+        (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+        (Err(_), Err(_)) => {
+            let a = NaiveDateTime::parse_from_str(a, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+            let b = NaiveDateTime::parse_from_str(b, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+            Some(a.cmp(&b))
+        }
+        _ => None,
+    }
+}
+
+/// Compare two temporal values having the same timezone, or both having none,
+/// by their values without the timezone.
+fn compare_with_timezones<T: Ord>(
+    a: &str,
+    b: &str,
+    parse: impl Fn(&str) -> Option<T>,
+) -> Option<Ordering> {
+    let (a, a_timezone) = split_timezone(a)?;
+    let (b, b_timezone) = split_timezone(b)?;
+    if a_timezone != b_timezone {
+        return None;
+    }
+    Some(parse(a)?.cmp(&parse(b)?))
+}
+
+/// Split a lexical form from its optional timezone, given as an offset in minutes.
+fn split_timezone(lexical_form: &str) -> Option<(&str, Option<i32>)> {
+    let rest = without_timezone(lexical_form);
+    let timezone = match &lexical_form[rest.len()..] {
+        "" => None,
+        "Z" => Some(0),
+        offset => {
+            let hours: i32 = offset[1..3].parse().ok()?;
+            let minutes: i32 = offset[4..6].parse().ok()?;
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            Some(sign * (hours * 60 + minutes))
+        }
+    };
+    Some((rest, timezone))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare_values;
+    use crate::tree::value::{Value, ValueType};
+    use std::cmp::Ordering;
+
+    fn a_value(value: &str, value_type: ValueType) -> Value {
+        Value {
+            value: String::from(value),
+            value_type,
+        }
+    }
+
+    #[test]
+    fn should_compare_numbers_of_different_types() {
+        assert_eq!(
+            compare_values(
+                &a_value("9", ValueType::Int),
+                &a_value("10", ValueType::Long)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("1.5", ValueType::Decimal),
+                &a_value("1", ValueType::Integer)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("INF", ValueType::Double),
+                &a_value("1.0E300", ValueType::Double)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("NaN", ValueType::Double),
+                &a_value("1.0E0", ValueType::Double)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_compare_date_times_across_timezones() {
+        assert_eq!(
+            compare_values(
+                &a_value("2022-01-01T10:00:00+02:00", ValueType::DateTime),
+                &a_value("2022-01-01T09:00:00Z", ValueType::DateTime)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("2022-01-01T10:00:00", ValueType::DateTime),
+                &a_value("2022-01-01T09:00:00Z", ValueType::DateTime)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_compare_dates_times_and_years_sharing_a_timezone() {
+        assert_eq!(
+            compare_values(
+                &a_value("-0100", ValueType::GYear),
+                &a_value("-0050", ValueType::GYear)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("-0100-01-01", ValueType::Date),
+                &a_value("-0050-01-01", ValueType::Date)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("2022-01-02Z", ValueType::Date),
+                &a_value("2022-01-01+00:00", ValueType::Date)
+            ),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("09:00:00.5", ValueType::Time),
+                &a_value("10:00:00", ValueType::Time)
+            ),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("10:00:00+02:00", ValueType::Time),
+                &a_value("09:00:00Z", ValueType::Time)
+            ),
+            None
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("2022", ValueType::GYear),
+                &a_value("2021-05:00", ValueType::GYear)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn should_not_compare_values_of_incompatible_types() {
+        assert_eq!(
+            compare_values(
+                &a_value("1", ValueType::Int),
+                &a_value("1", ValueType::String)
+            ),
+            None
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("a", ValueType::LangString(String::from("en"))),
+                &a_value("a", ValueType::LangString(String::from("fr")))
+            ),
+            None
+        );
+        assert_eq!(
+            compare_values(
+                &a_value("https://example.com/a", ValueType::Iri),
+                &a_value("https://example.com/a", ValueType::Iri)
+            ),
+            Some(Ordering::Equal)
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use super::comparison::compare_values;
+use super::geo::{point_of, BoundingBox};
+use crate::rdf::vocabulary;
+use crate::tree::member::Member;
+use crate::tree::relation::Relation;
+use crate::tree::relation_operator::RelationOperator;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::value::{escape_literal, Value};
+use std::cmp::Ordering;
+use std::fmt;
+use std::vec::Vec;
+
+/// A SPARQL FILTER expression over the values of the properties of the members.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// Compare the value of a property with a value.
+    Comparison {
+        path: ShaclPath,
+        operator: ComparisonOperator,
+        value: Value,
+    },
+    /// The value of a property starts with a string (`STRSTARTS`).
+    Prefix { path: ShaclPath, value: String },
+    /// The value of a property contains a string (`CONTAINS`).
+    Substring { path: ShaclPath, value: String },
+    /// The value of a property ends with a string (`STRENDS`).
+    Suffix { path: ShaclPath, value: String },
+    /// The WKT point of a property is within an area (`geof:sfWithin`).
+    GeoWithin { path: ShaclPath, area: BoundingBox },
+    /// Every filter is satisfied.
+    And(Vec<Filter>),
+    /// At least one filter is satisfied.
+    Or(Vec<Filter>),
+}
+
+/// A comparison operator of SPARQL.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    Equal,
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl Filter {
+    /// Whether the member satisfies the filter, a property missing from the member or a value
+    /// that cannot be compared making the expression false like a SPARQL error would.
+    pub fn matches(&self, member: &Member) -> bool {
+        match self {
+            Filter::Comparison {
+                path,
+                operator,
+                value,
+            } => member
+                .properties
+                .get(path)
+                .and_then(|member_value| compare_values(member_value, value))
+                .is_some_and(|ordering| operator.accepts(ordering)),
+            Filter::Prefix { path, value } => member
+                .properties
+                .get(path)
+                .is_some_and(|member_value| member_value.value.starts_with(value.as_str())),
+            Filter::Substring { path, value } => member
+                .properties
+                .get(path)
+                .is_some_and(|member_value| member_value.value.contains(value.as_str())),
+            Filter::Suffix { path, value } => member
+                .properties
+                .get(path)
+                .is_some_and(|member_value| member_value.value.ends_with(value.as_str())),
+            Filter::GeoWithin { path, area } => member
+                .properties
+                .get(path)
+                .and_then(point_of)
+                .is_some_and(|point| area.contains(&point)),
+            Filter::And(filters) => filters.iter().all(|filter| filter.matches(member)),
+            Filter::Or(filters) => filters.iter().any(|filter| filter.matches(member)),
+        }
+    }
+
+    /// The filter satisfied by the members reachable through a relation, `None` for a relation
+    /// without operator, path or value and for a geospatial relation, whose geometry is not a rectangle.
+    pub fn of_relation(relation: &Relation) -> Option<Filter> {
+        let (operator, path, value) =
+            match (relation.relation_type(), relation.path(), relation.value()) {
+                (Some(operator), Some(path), Some(value)) => {
+                    (operator, path.clone(), value.clone())
+                }
+                _ => return None,
+            };
+        let comparison = |operator| {
+            Some(Filter::Comparison {
+                path: path.clone(),
+                operator,
+                value: value.clone(),
+            })
+        };
+        match operator {
+            RelationOperator::GreaterThanRelation => comparison(ComparisonOperator::GreaterThan),
+            RelationOperator::GreaterThanOrEqualToRelation => {
+                comparison(ComparisonOperator::GreaterThanOrEqual)
+            }
+            RelationOperator::LessThanRelation => comparison(ComparisonOperator::LessThan),
+            RelationOperator::LessThanOrEqualToRelation => {
+                comparison(ComparisonOperator::LessThanOrEqual)
+            }
+            RelationOperator::EqualThanRelation => comparison(ComparisonOperator::Equal),
+            RelationOperator::PrefixRelation => Some(Filter::Prefix {
+                path,
+                value: value.value,
+            }),
+            RelationOperator::SubstringRelation => Some(Filter::Substring {
+                path,
+                value: value.value,
+            }),
+            RelationOperator::SuffixRelation => Some(Filter::Suffix {
+                path,
+                value: value.value,
+            }),
+            RelationOperator::GeospatiallyContainsRelation => None,
+        }
+    }
+
+    /// The paths of the properties used by the filter, in order of appearance and without duplicate.
+    pub fn paths(&self) -> Vec<&ShaclPath> {
+        let mut paths: Vec<&ShaclPath> = Vec::new();
+        self.collect_paths(&mut paths);
+        paths
+    }
+
+    fn collect_paths<'a>(&'a self, paths: &mut Vec<&'a ShaclPath>) {
+        match self {
+            Filter::Comparison { path, .. }
+            | Filter::Prefix { path, .. }
+            | Filter::Substring { path, .. }
+            | Filter::Suffix { path, .. }
+            | Filter::GeoWithin { path, .. } => {
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+            Filter::And(filters) | Filter::Or(filters) => {
+                for filter in filters {
+                    filter.collect_paths(paths);
+                }
+            }
+        }
+    }
+
+    /// The filter as a SPARQL expression, the value of each path being bound to the variable
+    /// `?value{i}` with `i` the index of the path in [`Filter::paths`].
+    pub fn to_sparql(&self) -> String {
+        let paths = self.paths();
+        self.to_sparql_with(&paths)
+    }
+
+    fn to_sparql_with(&self, paths: &[&ShaclPath]) -> String {
+        let variable = |path: &ShaclPath| {
+            let index = paths
+                .iter()
+                .position(|v| *v == path)
+                .expect("the paths should contain every path of the filter");
+            format!("?value{}", index)
+        };
+        match self {
+            Filter::Comparison {
+                path,
+                operator,
+                value,
+            } => format!("{} {} {}", variable(path), operator, value),
+            Filter::Prefix { path, value } => {
+                format!(
+                    "STRSTARTS(STR({}), \"{}\")",
+                    variable(path),
+                    escape_literal(value)
+                )
+            }
+            Filter::Substring { path, value } => {
+                format!(
+                    "CONTAINS(STR({}), \"{}\")",
+                    variable(path),
+                    escape_literal(value)
+                )
+            }
+            Filter::Suffix { path, value } => {
+                format!(
+                    "STRENDS(STR({}), \"{}\")",
+                    variable(path),
+                    escape_literal(value)
+                )
+            }
+            Filter::GeoWithin { path, area } => format!(
+                "<{}>({}, {})",
+                vocabulary::GEOF_SF_WITHIN,
+                variable(path),
+                area.to_value()
+            ),
+            Filter::And(filters) => join(filters, paths, "&&", "true"),
+            Filter::Or(filters) => join(filters, paths, "||", "false"),
+        }
+    }
+}
+
+fn join(filters: &[Filter], paths: &[&ShaclPath], operator: &str, empty: &str) -> String {
+    if filters.is_empty() {
+        return String::from(empty);
+    }
+    let expressions: Vec<String> = filters
+        .iter()
+        .map(|filter| filter.to_sparql_with(paths))
+        .collect();
+    format!("({})", expressions.join(&format!(" {} ", operator)))
+}
+
+impl ComparisonOperator {
+    /// Whether the ordering of a value relative to the operand satisfies the operator.
+    pub fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            ComparisonOperator::Equal => ordering == Ordering::Equal,
+            ComparisonOperator::LessThan => ordering == Ordering::Less,
+            ComparisonOperator::LessThanOrEqual => ordering != Ordering::Greater,
+            ComparisonOperator::GreaterThan => ordering == Ordering::Greater,
+            ComparisonOperator::GreaterThanOrEqual => ordering != Ordering::Less,
+        }
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let string_representation: &str = match self {
+            ComparisonOperator::Equal => "=",
+            ComparisonOperator::LessThan => "<",
+            ComparisonOperator::LessThanOrEqual => "<=",
+            ComparisonOperator::GreaterThan => ">",
+            ComparisonOperator::GreaterThanOrEqual => ">=",
+        };
+        write!(f, "{}", string_representation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComparisonOperator, Filter};
+    use crate::query::geo::{BoundingBox, Point};
+    use crate::rdf::vocabulary;
+    use crate::tree::member::Member;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::HashMap;
+
+    fn a_member() -> Member {
+        Member {
+            url: String::from("https://example.com/member/0"),
+            properties: HashMap::from([
+                (
+                    String::from("https://example.com/age"),
+                    Value {
+                        value: String::from("30"),
+                        value_type: ValueType::Int,
+                    },
+                ),
+                (
+                    String::from("https://example.com/name"),
+                    Value {
+                        value: String::from("Alice"),
+                        value_type: ValueType::String,
+                    },
+                ),
+                (
+                    String::from("https://example.com/location"),
+                    Value {
+                        value: String::from("POINT(4 51)"),
+                        value_type: ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)),
+                    },
+                ),
+            ]),
+        }
+    }
+
+    fn an_age_filter(operator: ComparisonOperator, age: &str) -> Filter {
+        Filter::Comparison {
+            path: String::from("https://example.com/age"),
+            operator,
+            value: Value {
+                value: String::from(age),
+                value_type: ValueType::Integer,
+            },
+        }
+    }
+
+    #[test]
+    fn should_match_a_member() {
+        let member = a_member();
+
+        assert!(an_age_filter(ComparisonOperator::Equal, "30").matches(&member));
+        assert!(an_age_filter(ComparisonOperator::LessThanOrEqual, "30").matches(&member));
+        assert!(!an_age_filter(ComparisonOperator::GreaterThan, "30").matches(&member));
+        assert!(Filter::Prefix {
+            path: String::from("https://example.com/name"),
+            value: String::from("Al"),
+        }
+        .matches(&member));
+        assert!(Filter::Suffix {
+            path: String::from("https://example.com/name"),
+            value: String::from("ice"),
+        }
+        .matches(&member));
+        assert!(Filter::GeoWithin {
+            path: String::from("https://example.com/location"),
+            area: BoundingBox {
+                min: Point { x: 3.0, y: 50.0 },
+                max: Point { x: 5.0, y: 52.0 },
+            },
+        }
+        .matches(&member));
+        assert!(Filter::Or(vec![
+            an_age_filter(ComparisonOperator::LessThan, "18"),
+            an_age_filter(ComparisonOperator::GreaterThan, "20"),
+        ])
+        .matches(&member));
+        assert!(!Filter::And(vec![
+            an_age_filter(ComparisonOperator::LessThan, "18"),
+            an_age_filter(ComparisonOperator::GreaterThan, "20"),
+        ])
+        .matches(&member));
+    }
+
+    #[test]
+    fn should_not_match_a_missing_or_incomparable_property() {
+        let member = a_member();
+
+        assert!(!Filter::Substring {
+            path: String::from("https://example.com/unknown"),
+            value: String::new(),
+        }
+        .matches(&member));
+        assert!(!Filter::Comparison {
+            path: String::from("https://example.com/name"),
+            operator: ComparisonOperator::GreaterThan,
+            value: Value {
+                value: String::from("1"),
+                value_type: ValueType::Int,
+            },
+        }
+        .matches(&member));
+    }
+
+    #[test]
+    fn should_serialize_the_filter_in_sparql() {
+        let filter = Filter::And(vec![
+            an_age_filter(ComparisonOperator::GreaterThanOrEqual, "18"),
+            Filter::Prefix {
+                path: String::from("https://example.com/name"),
+                value: String::from("A\""),
+            },
+            an_age_filter(ComparisonOperator::LessThan, "65"),
+        ]);
+
+        assert_eq!(
+            filter.to_sparql(),
+            "(?value0 >= \"18\"^^<http://www.w3.org/2001/XMLSchema#integer> && STRSTARTS(STR(?value1), \"A\\\"\") && ?value0 < \"65\"^^<http://www.w3.org/2001/XMLSchema#integer>)"
+        );
+        assert_eq!(filter.paths().len(), 2);
+    }
+
+    #[test]
+    fn should_build_the_filter_of_a_relation() {
+        let a_relation = |operator| {
+            Relation::new(
+                None,
+                Some(String::from("https://example.com/age")),
+                Some(Value {
+                    value: String::from("18"),
+                    value_type: ValueType::Integer,
+                }),
+                String::from("https://example.com/node"),
+                Some(operator),
+            )
+        };
+
+        assert_eq!(
+            Filter::of_relation(&a_relation(RelationOperator::GreaterThanOrEqualToRelation)),
+            Some(an_age_filter(ComparisonOperator::GreaterThanOrEqual, "18"))
+        );
+        assert_eq!(
+            Filter::of_relation(&a_relation(RelationOperator::PrefixRelation)),
+            Some(Filter::Prefix {
+                path: String::from("https://example.com/age"),
+                value: String::from("18"),
+            })
+        );
+        assert_eq!(
+            Filter::of_relation(&a_relation(RelationOperator::GeospatiallyContainsRelation)),
+            None
+        );
+        assert_eq!(
+            Filter::of_relation(&Relation::new(
+                None,
+                None,
+                None,
+                String::from("https://example.com/node"),
+                Some(RelationOperator::LessThanRelation),
+            )),
+            None
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::rdf::vocabulary;
+use crate::tree::value::{Value, ValueType};
+
+/// A point of a [WKT](https://www.ogc.org/standard/sfa/) geometry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A rectangle aligned with the axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    /// Whether the point is in the interior of the rectangle.
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x > self.min.x && point.x < self.max.x && point.y > self.min.y && point.y < self.max.y
+    }
+
+    /// The rectangle as a WKT polygon.
+    pub fn to_wkt(&self) -> String {
+        format!(
+            "POLYGON(({0} {1}, {2} {1}, {2} {3}, {0} {3}, {0} {1}))",
+            self.min.x, self.min.y, self.max.x, self.max.y
+        )
+    }
+
+    /// The rectangle as a `geo:wktLiteral`.
+    pub fn to_value(&self) -> Value {
+        Value {
+            value: self.to_wkt(),
+            value_type: ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)),
+        }
+    }
+}
+
+/// The point of a `geo:wktLiteral` value, `None` if the value is not a WKT point.
+pub fn point_of(value: &Value) -> Option<Point> {
+    if value.value_type != ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)) {
+        return None;
+    }
+    let coordinates = strip_keyword(without_crs(&value.value), "POINT")?;
+    let points = parse_points(coordinates.strip_prefix('(')?.strip_suffix(')')?)?;
+    match points[..] {
+        [point] => Some(point),
+        _ => None,
+    }
+}
+
+/// The points of a WKT literal, whatever the kind of geometry, `None` if it cannot be parsed.
+pub fn points_of_wkt(wkt: &str) -> Option<Vec<Point>> {
+    let geometry = without_crs(wkt);
+    let coordinates = geometry.trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == ' ');
+    let coordinates = coordinates.replace(['(', ')'], ",");
+    let points: Vec<Point> = coordinates
+        .split(',')
+        .filter(|point| !point.trim().is_empty())
+        .map(parse_point)
+        .collect::<Option<Vec<Point>>>()?;
+    if points.is_empty() {
+        None
+    } else {
+        Some(points)
+    }
+}
+
+/// The smallest rectangle containing the points.
+pub fn bounding_box_of(points: &[Point]) -> Option<BoundingBox> {
+    let first = points.first()?;
+    Some(points.iter().fold(
+        BoundingBox {
+            min: *first,
+            max: *first,
+        },
+        |acc, point| BoundingBox {
+            min: Point {
+                x: acc.min.x.min(point.x),
+                y: acc.min.y.min(point.y),
+            },
+            max: Point {
+                x: acc.max.x.max(point.x),
+                y: acc.max.y.max(point.y),
+            },
+        },
+    ))
+}
+
+/// Remove the optional IRI of the coordinate reference system preceding the geometry.
+fn without_crs(wkt: &str) -> &str {
+    let wkt = wkt.trim();
+    match wkt.strip_prefix('<').and_then(|v| v.split_once('>')) {
+        Some((_, geometry)) => geometry.trim(),
+        None => wkt,
+    }
+}
+
+fn strip_keyword<'a>(wkt: &'a str, keyword: &str) -> Option<&'a str> {
+    if wkt.len() < keyword.len() || !wkt[..keyword.len()].eq_ignore_ascii_case(keyword) {
+        return None;
+    }
+    Some(wkt[keyword.len()..].trim())
+}
+
+fn parse_points(coordinates: &str) -> Option<Vec<Point>> {
+    coordinates.split(',').map(parse_point).collect()
+}
+
+fn parse_point(coordinates: &str) -> Option<Point> {
+    let mut numbers = coordinates.split_whitespace().map(|v| v.parse::<f64>());
+    let x = numbers.next()?.ok()?;
+    let y = numbers.next()?.ok()?;
+    Some(Point { x, y })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bounding_box_of, point_of, points_of_wkt, BoundingBox, Point};
+    use crate::rdf::vocabulary;
+    use crate::tree::value::{Value, ValueType};
+
+    fn a_wkt_value(wkt: &str) -> Value {
+        Value {
+            value: String::from(wkt),
+            value_type: ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)),
+        }
+    }
+
+    #[test]
+    fn should_parse_a_point() {
+        assert_eq!(
+            point_of(&a_wkt_value("POINT(4.4 51.2)")),
+            Some(Point { x: 4.4, y: 51.2 })
+        );
+        assert_eq!(
+            point_of(&a_wkt_value(
+                "<http://www.opengis.net/def/crs/OGC/1.3/CRS84> Point (1 -2)"
+            )),
+            Some(Point { x: 1.0, y: -2.0 })
+        );
+        assert_eq!(point_of(&a_wkt_value("POLYGON((0 0, 1 1, 0 0))")), None);
+        assert_eq!(
+            point_of(&Value {
+                value: String::from("POINT(1 2)"),
+                value_type: ValueType::String,
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn should_compute_the_bounding_box_of_a_polygon() {
+        let points = points_of_wkt("POLYGON((0 1, 4 1, 2 5, 0 1))").unwrap();
+
+        assert_eq!(
+            bounding_box_of(&points),
+            Some(BoundingBox {
+                min: Point { x: 0.0, y: 1.0 },
+                max: Point { x: 4.0, y: 5.0 },
+            })
+        );
+    }
+
+    #[test]
+    fn should_contain_only_the_points_of_the_interior() {
+        let bounding_box = BoundingBox {
+            min: Point { x: 0.0, y: 0.0 },
+            max: Point { x: 2.0, y: 2.0 },
+        };
+
+        assert!(bounding_box.contains(&Point { x: 1.0, y: 1.0 }));
+        assert!(!bounding_box.contains(&Point { x: 0.0, y: 1.0 }));
+        assert_eq!(bounding_box.to_wkt(), "POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))");
+    }
+}
@@ -0,0 +1,255 @@
+use super::comparison::compare_values;
+use crate::tree::member::Member;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::tree_document::TreeDocument;
+use crate::tree::value::Value;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::vec::Vec;
+
+/// The [version materialization](https://semiceu.github.io/LinkedDataEventStreams/#version-materializations)
+/// of the members of a document, the state a client replicating it should reach: for every entity,
+/// its latest version following the timestamps, identified by the entity and without the version path,
+/// sorted by entity. The members without an entity are kept as is after the entities.
+/// A version without a comparable timestamp is older than the others and, between versions with equal
+/// timestamps, the last one in the order of the nodes is the latest.
+pub fn materialize(
+    document: &TreeDocument,
+    version_of_path: &ShaclPath,
+    timestamp_path: &ShaclPath,
+) -> Vec<Member> {
+    let mut known_members: HashSet<&String> = HashSet::new();
+    let mut latest_versions: BTreeMap<&String, &Member> = BTreeMap::new();
+    let mut unversioned: Vec<Member> = Vec::new();
+    for member in document
+        .nodes()
+        .iter()
+        .flat_map(|node| node.members().iter())
+        .filter(|member| known_members.insert(&member.url))
+    {
+        let entity = match member.properties.get(version_of_path) {
+            Some(v) => &v.value,
+            None => {
+                unversioned.push(member.clone());
+                continue;
+            }
+        };
+        let is_latest = match latest_versions.get(entity) {
+            Some(latest) => {
+                match (
+                    comparable_timestamp(member, timestamp_path),
+                    comparable_timestamp(latest, timestamp_path),
+                ) {
+                    (Some(timestamp), Some(latest_timestamp)) => {
+                        compare_values(timestamp, latest_timestamp)
+                            .is_some_and(|ordering| ordering != Ordering::Less)
+                    }
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                }
+            }
+            None => true,
+        };
+        if is_latest {
+            latest_versions.insert(entity, member);
+        }
+    }
+
+    latest_versions
+        .into_iter()
+        .map(|(entity, version)| {
+            let mut properties = version.properties.clone();
+            properties.remove(version_of_path);
+            Member {
+                url: entity.clone(),
+                properties,
+            }
+        })
+        .chain(unversioned)
+        .collect()
+}
+
+/// The timestamp of the member, `None` if it cannot be compared, even with itself.
+fn comparable_timestamp<'a>(member: &'a Member, timestamp_path: &ShaclPath) -> Option<&'a Value> {
+    member
+        .properties
+        .get(timestamp_path)
+        .filter(|timestamp| compare_values(timestamp, timestamp).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::materialize;
+    use crate::generate_tree_document;
+    use crate::generator_argument::member_argument::{
+        DistributionOfMember, MemberGeneratorArg, PropertyTemplate, VersioningArg,
+    };
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::generator_argument::relation_argument::{PaginationArg, RelationGeneratorArg};
+    use crate::generator_argument::Args;
+    use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::retention_policy::RetentionPolicy;
+    use crate::tree::shape::PropertyShape;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::{HashMap, HashSet};
+
+    const VERSION_OF_PATH: &str = "http://purl.org/dc/terms/isVersionOf";
+    const TIMESTAMP_PATH: &str = "http://purl.org/dc/terms/created";
+
+    #[test]
+    fn should_materialize_the_latest_version_of_the_generated_entities() {
+        let args = Args {
+            relation: RelationGeneratorArg::Pagination(PaginationArg {
+                n_page: 5,
+                page_size: 10,
+                backward_links: false,
+            }),
+            members: Some(MemberGeneratorArg {
+                properties: vec![PropertyTemplate {
+                    path: String::from("ex:p"),
+                    range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }],
+                distribution_of_member: DistributionOfMember::Direct(Vec::new()),
+                versioning: Some(VersioningArg {
+                    version_of_path: String::from(VERSION_OF_PATH),
+                    timestamp_path: String::from(TIMESTAMP_PATH),
+                    n_entity: 8,
+                    start: Utc.with_ymd_and_hms(2022, 1, 1, 0, 0, 0).unwrap(),
+                    interval: Duration::minutes(90),
+                }),
+            }),
+            search_form: None,
+            topology: None,
+            base_url: String::from("https://example.com"),
+        };
+
+        let document = generate_tree_document(&args).unwrap();
+
+        let members: Vec<_> = document
+            .nodes()
+            .iter()
+            .flat_map(|node| node.members().iter())
+            .collect();
+        assert_eq!(members.len(), 50);
+        assert_eq!(
+            members[0].properties[TIMESTAMP_PATH].value,
+            "2022-01-01T00:00:00Z"
+        );
+        assert_eq!(
+            members[1].properties[TIMESTAMP_PATH].value,
+            "2022-01-01T01:30:00Z"
+        );
+        assert!(members
+            .windows(2)
+            .all(|pair| pair[0].properties[TIMESTAMP_PATH].value
+                < pair[1].properties[TIMESTAMP_PATH].value));
+        assert!(members
+            .iter()
+            .all(|member| member.properties[VERSION_OF_PATH]
+                .value
+                .starts_with("https://example.com/entity/")));
+        assert!(document
+            .shape()
+            .as_ref()
+            .unwrap()
+            .properties()
+            .contains(&PropertyShape::new(
+                String::from(TIMESTAMP_PATH),
+                ValueType::DateTime
+            )));
+
+        let state = materialize(
+            &document,
+            &String::from(VERSION_OF_PATH),
+            &String::from(TIMESTAMP_PATH),
+        );
+
+        assert!(state.len() <= 8);
+        assert!(state
+            .iter()
+            .all(|entity| !entity.properties.contains_key(VERSION_OF_PATH)));
+        // the latest versions are the ones kept by a retention policy keeping a single version
+        let expired = RetentionPolicy::LatestVersionSubset {
+            timestamp_path: String::from(TIMESTAMP_PATH),
+            version_of_path: String::from(VERSION_OF_PATH),
+            amount: 1,
+        }
+        .expired_members(&members, Utc::now())
+        .unwrap();
+        let latest_timestamps: HashSet<&String> = members
+            .iter()
+            .filter(|member| !expired.contains(&member.url))
+            .map(|member| &member.properties[TIMESTAMP_PATH].value)
+            .collect();
+        let materialized_timestamps: HashSet<&String> = state
+            .iter()
+            .map(|entity| &entity.properties[TIMESTAMP_PATH].value)
+            .collect();
+        assert_eq!(latest_timestamps, materialized_timestamps);
+    }
+
+    #[test]
+    fn should_not_replace_the_latest_version_by_a_version_with_an_incomparable_timestamp() {
+        let a_version = |url: &str, timestamp: &str| Member {
+            url: String::from(url),
+            properties: HashMap::from([
+                (
+                    String::from(VERSION_OF_PATH),
+                    Value {
+                        value: String::from("https://example.com/entity/0"),
+                        value_type: ValueType::String,
+                    },
+                ),
+                (
+                    String::from(TIMESTAMP_PATH),
+                    Value {
+                        value: String::from(timestamp),
+                        value_type: ValueType::DateTime,
+                    },
+                ),
+            ]),
+        };
+        let valid = a_version("https://example.com/member/0", "2022-01-01T00:00:00Z");
+        let invalid = a_version("https://example.com/member/1", "not a date");
+        for versions in [
+            vec![valid.clone(), invalid.clone()],
+            vec![invalid.clone(), valid.clone()],
+        ] {
+            let document = TreeDocument::new(
+                Collection::new(
+                    String::from("https://example.com/collection"),
+                    String::from("https://example.com/root"),
+                    versions.iter().map(|member| member.url.clone()).collect(),
+                    None,
+                ),
+                vec![Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/root"),
+                    versions,
+                )],
+                None,
+                None,
+            );
+
+            let state = materialize(
+                &document,
+                &String::from(VERSION_OF_PATH),
+                &String::from(TIMESTAMP_PATH),
+            );
+
+            assert_eq!(state.len(), 1);
+            assert_eq!(
+                state[0].properties[TIMESTAMP_PATH].value,
+                "2022-01-01T00:00:00Z"
+            );
+        }
+    }
+}
@@ -0,0 +1,8 @@
+//! SPARQL queries over the members of a generated TREE document.
+pub mod comparison;
+pub mod filter;
+pub mod geo;
+pub mod materialization;
+pub mod oracle;
+pub mod pruning;
+pub mod workload;
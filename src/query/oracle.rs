@@ -0,0 +1,568 @@
+use super::filter::Filter;
+use super::pruning::can_prune;
+use crate::tree::node::Node;
+use crate::tree::relation::Relation;
+use crate::tree::tree_document::TreeDocument;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+
+/// The results a client should find when evaluating a filter over a TREE document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedResults {
+    /// Urls of the members satisfying the filter, sorted.
+    pub members: Vec<String>,
+    /// Urls of a smallest set of nodes a perfectly pruning client has to fetch to find the members,
+    /// in breadth-first order from the root. The client only follows the relations it
+    /// [cannot prune](`can_prune`), so a member only reachable through pruned relations is not covered.
+    pub nodes: Vec<String>,
+    /// Whether `nodes` is a minimum, the document being otherwise too large for an exact search
+    /// and `nodes` being an upper bound.
+    pub is_minimal: bool,
+}
+
+/// How a client evaluating a filter compares with the [`ExpectedResults`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Score {
+    /// Fraction of the returned members that satisfy the filter.
+    pub precision: f64,
+    /// Fraction of the members satisfying the filter that are returned.
+    pub recall: f64,
+    /// Number of nodes of the minimal set divided by the number of nodes fetched,
+    /// 1 when the client fetched no more than needed.
+    pub traversal_efficiency: f64,
+}
+
+/// Maximum number of sets of nodes explored by the exact search of the minimal set of nodes.
+const MAX_EXPLORED_SETS: usize = 1_000_000;
+
+/// Compute the members satisfying the filter and the minimal set of nodes to fetch to find them.
+/// A matching member only held by nodes unreachable from the root cannot be found
+/// by a client, so it is not part of the results.
+pub fn expected_results(document: &TreeDocument, filter: &Filter) -> ExpectedResults {
+    let nodes_by_url: HashMap<&str, &Node> = document
+        .nodes()
+        .iter()
+        .map(|node| (node.node_url().as_str(), node))
+        .collect();
+    let root_url = document.collection().view().as_str();
+
+    let mut members: Vec<String> = Vec::new();
+    let mut found: HashSet<&str> = HashSet::new();
+    for (node_url, _) in shortest_path_parents(&nodes_by_url, root_url, |_| true) {
+        for member in nodes_by_url[node_url].members() {
+            if filter.matches(member) && found.insert(member.url.as_str()) {
+                members.push(member.url.clone());
+            }
+        }
+    }
+    members.sort();
+
+    let parents = shortest_path_parents(&nodes_by_url, root_url, |relation| {
+        !can_prune(filter, relation)
+    });
+    let graph = PruningGraph::new(&nodes_by_url, &parents, filter);
+    let greedy = graph.greedy_cover(&parents);
+    let mut search = MinimalCoverSearch {
+        graph: &graph,
+        best: greedy,
+        n_explored: 0,
+    };
+    let is_minimal = search.run();
+
+    ExpectedResults {
+        members,
+        nodes: search
+            .best
+            .iter()
+            .map(|i| String::from(parents[*i].0))
+            .collect(),
+        is_minimal,
+    }
+}
+
+/// The nodes a perfectly pruning client can reach, identified by their rank in breadth-first order.
+struct PruningGraph {
+    /// Nodes targeted by the relations of every node that cannot be pruned.
+    children: Vec<Vec<usize>>,
+    /// Indexes of the matching members held by every node.
+    members: Vec<Vec<usize>>,
+    /// Number of matching members found in the reachable nodes.
+    n_member: usize,
+    /// Whether a node holds a matching member or leads to a node holding one.
+    is_useful: Vec<bool>,
+}
+
+impl PruningGraph {
+    fn new(
+        nodes_by_url: &HashMap<&str, &Node>,
+        parents: &[(&str, Option<&str>)],
+        filter: &Filter,
+    ) -> Self {
+        let rank: HashMap<&str, usize> = parents
+            .iter()
+            .enumerate()
+            .map(|(i, (url, _))| (*url, i))
+            .collect();
+        let mut member_indexes: HashMap<&str, usize> = HashMap::new();
+        let mut children: Vec<Vec<usize>> = Vec::new();
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        for (url, _) in parents.iter() {
+            let node = nodes_by_url[url];
+            let mut node_children: Vec<usize> = node
+                .relation()
+                .iter()
+                .filter(|relation| !can_prune(filter, relation))
+                .filter_map(|relation| rank.get(relation.node().as_str()).copied())
+                .collect();
+            node_children.sort();
+            node_children.dedup();
+            children.push(node_children);
+            let mut node_members: Vec<usize> = Vec::new();
+            for member in node
+                .members()
+                .iter()
+                .filter(|member| filter.matches(member))
+            {
+                let n_member = member_indexes.len();
+                node_members.push(
+                    *member_indexes
+                        .entry(member.url.as_str())
+                        .or_insert(n_member),
+                );
+            }
+            members.push(node_members);
+        }
+
+        let mut is_useful: Vec<bool> = members.iter().map(|v| !v.is_empty()).collect();
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for i in 0..children.len() {
+                if !is_useful[i] && children[i].iter().any(|child| is_useful[*child]) {
+                    is_useful[i] = true;
+                    is_changed = true;
+                }
+            }
+        }
+        PruningGraph {
+            children,
+            members,
+            n_member: member_indexes.len(),
+            is_useful,
+        }
+    }
+
+    /// The root and the shortest path from the root to the first node, in breadth-first order,
+    /// holding each matching member, which is an upper bound of the minimal set of nodes.
+    fn greedy_cover(&self, parents: &[(&str, Option<&str>)]) -> Vec<usize> {
+        let rank: HashMap<&str, usize> = parents
+            .iter()
+            .enumerate()
+            .map(|(i, (url, _))| (*url, i))
+            .collect();
+        let mut found: Vec<bool> = vec![false; self.n_member];
+        let mut needed: BTreeSet<usize> = BTreeSet::new();
+        if !parents.is_empty() {
+            needed.insert(0);
+        }
+        for (i, node_members) in self.members.iter().enumerate() {
+            let mut has_new_match = false;
+            for member in node_members {
+                has_new_match |= !found[*member];
+                found[*member] = true;
+            }
+            if !has_new_match {
+                continue;
+            }
+            // the path from the root to the node, up to a node already needed with its own path
+            let mut current = Some(i);
+            while let Some(j) = current {
+                if !needed.insert(j) {
+                    break;
+                }
+                current = parents[j].1.map(|parent| rank[parent]);
+            }
+        }
+        needed.into_iter().collect()
+    }
+}
+
+/// Branch and bound search of a smallest set of nodes reachable from the root covering every matching member.
+struct MinimalCoverSearch<'a> {
+    graph: &'a PruningGraph,
+    /// Smallest set of nodes found so far, sorted.
+    best: Vec<usize>,
+    n_explored: usize,
+}
+
+impl MinimalCoverSearch<'_> {
+    /// Search the minimal set of nodes, `false` if the search was stopped before being exhaustive.
+    fn run(&mut self) -> bool {
+        if self.graph.children.is_empty() {
+            return true;
+        }
+        let mut coverage: Vec<usize> = vec![0; self.graph.n_member];
+        let mut selected: Vec<usize> = Vec::new();
+        let mut excluded: Vec<bool> = vec![false; self.graph.children.len()];
+        let n_covered = self.select(0, &mut selected, &mut coverage);
+        self.explore(
+            &mut selected,
+            Vec::new(),
+            &mut excluded,
+            &mut coverage,
+            n_covered,
+        )
+    }
+
+    /// Select a node and return the number of members it is the first to cover.
+    fn select(&self, node: usize, selected: &mut Vec<usize>, coverage: &mut [usize]) -> usize {
+        selected.push(node);
+        let mut n_new = 0;
+        for member in self.graph.members[node].iter() {
+            if coverage[*member] == 0 {
+                n_new += 1;
+            }
+            coverage[*member] += 1;
+        }
+        n_new
+    }
+
+    /// Unselect the last selected node.
+    fn unselect(&self, selected: &mut Vec<usize>, coverage: &mut [usize]) {
+        let node = selected.pop().expect("a node should be selected");
+        for member in self.graph.members[node].iter() {
+            coverage[*member] -= 1;
+        }
+    }
+
+    /// Extend the selected nodes, which are connected to the root, with the candidates one at a time,
+    /// a candidate being either selected or excluded from the following extensions so that
+    /// every set is explored once. `n_covered` is the number of members covered by the selected nodes.
+    fn explore(
+        &mut self,
+        selected: &mut Vec<usize>,
+        mut candidates: Vec<usize>,
+        excluded: &mut [bool],
+        coverage: &mut [usize],
+        n_covered: usize,
+    ) -> bool {
+        if n_covered == self.graph.n_member {
+            if selected.len() < self.best.len() {
+                self.best = selected.clone();
+                self.best.sort();
+            }
+            return true;
+        }
+        if selected.len() + 1 >= self.best.len() {
+            return true;
+        }
+        self.n_explored += 1;
+        if self.n_explored > MAX_EXPLORED_SETS {
+            return false;
+        }
+        let last = *selected.last().expect("the root should be selected");
+        for child in self.graph.children[last].iter() {
+            if self.graph.is_useful[*child]
+                && !excluded[*child]
+                && !selected.contains(child)
+                && !candidates.contains(child)
+            {
+                candidates.push(*child);
+            }
+        }
+        candidates.sort();
+
+        let mut is_exhaustive = true;
+        let mut newly_excluded: Vec<usize> = Vec::new();
+        for (i, candidate) in candidates.iter().enumerate() {
+            let remaining: Vec<usize> = candidates[i + 1..].to_vec();
+            let n_new = self.select(*candidate, selected, coverage);
+            is_exhaustive &=
+                self.explore(selected, remaining, excluded, coverage, n_covered + n_new);
+            self.unselect(selected, coverage);
+            excluded[*candidate] = true;
+            newly_excluded.push(*candidate);
+            if !is_exhaustive {
+                break;
+            }
+        }
+        for candidate in newly_excluded {
+            excluded[candidate] = false;
+        }
+        is_exhaustive
+    }
+}
+
+impl ExpectedResults {
+    /// Score the members returned by a client and the nodes it fetched, the duplicates being ignored.
+    pub fn score(&self, returned_members: &[String], fetched_nodes: &[String]) -> Score {
+        let expected: HashSet<&String> = self.members.iter().collect();
+        let returned: HashSet<&String> = returned_members.iter().collect();
+        let n_correct = returned.intersection(&expected).count();
+        let fetched: HashSet<&String> = fetched_nodes.iter().collect();
+        Score {
+            precision: ratio(n_correct, returned.len()),
+            recall: ratio(n_correct, expected.len()),
+            traversal_efficiency: ratio(self.nodes.len(), fetched.len()).min(1.0),
+        }
+    }
+}
+
+/// The ratio of two counts, 1 when the denominator is null since nothing could be missed.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        1.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+/// The nodes reachable from the root in breadth-first order with their parent on a shortest path,
+/// only following the relations accepted by `follows`.
+fn shortest_path_parents<'a>(
+    nodes_by_url: &HashMap<&'a str, &'a Node>,
+    root_url: &'a str,
+    follows: impl Fn(&Relation) -> bool,
+) -> Vec<(&'a str, Option<&'a str>)> {
+    let mut parents: Vec<(&str, Option<&str>)> = Vec::new();
+    if !nodes_by_url.contains_key(root_url) {
+        return parents;
+    }
+    let mut visited: HashSet<&str> = HashSet::from([root_url]);
+    let mut pending: VecDeque<(&str, Option<&str>)> = VecDeque::from([(root_url, None)]);
+    while let Some((url, parent)) = pending.pop_front() {
+        parents.push((url, parent));
+        if let Some(node) = nodes_by_url.get(url) {
+            for relation in node.relation().iter().filter(|relation| follows(relation)) {
+                let target = relation.node().as_str();
+                if nodes_by_url.contains_key(target) && visited.insert(target) {
+                    pending.push_back((target, Some(url)));
+                }
+            }
+        }
+    }
+    parents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::expected_results;
+    use crate::query::filter::{ComparisonOperator, Filter};
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::HashMap;
+
+    fn a_member(i: usize) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([(
+                String::from("https://example.com/p"),
+                Value {
+                    value: i.to_string(),
+                    value_type: ValueType::Int,
+                },
+            )]),
+        }
+    }
+
+    fn a_node(url: &str, targets: &[&str], members: Vec<Member>) -> Node {
+        Node::new(
+            targets
+                .iter()
+                .map(|target| {
+                    Relation::new(
+                        None,
+                        None,
+                        None,
+                        format!("https://example.com/{}", target),
+                        None,
+                    )
+                })
+                .collect(),
+            format!("https://example.com/{}", url),
+            members,
+        )
+    }
+
+    /// root -> a -> c, root -> b -> c, c -> root and an unreachable node d.
+    fn a_document() -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                a_node("root", &["a", "b"], vec![a_member(0)]),
+                a_node("a", &["c"], vec![a_member(1)]),
+                a_node("b", &["c"], vec![a_member(2), a_member(1)]),
+                a_node("c", &["root"], vec![a_member(3)]),
+                a_node("d", &[], vec![a_member(4)]),
+            ],
+            None,
+            None,
+        )
+    }
+
+    fn a_filter(operator: ComparisonOperator, value: &str) -> Filter {
+        Filter::Comparison {
+            path: String::from("https://example.com/p"),
+            operator,
+            value: Value {
+                value: String::from(value),
+                value_type: ValueType::Int,
+            },
+        }
+    }
+
+    #[test]
+    fn should_compute_the_members_and_the_minimal_nodes() {
+        let results = expected_results(
+            &a_document(),
+            &a_filter(ComparisonOperator::GreaterThanOrEqual, "3"),
+        );
+
+        assert_eq!(results.members, vec!["https://example.com/member/3"]);
+        assert_eq!(
+            results.nodes,
+            vec![
+                "https://example.com/root",
+                "https://example.com/a",
+                "https://example.com/c"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_only_fetch_the_root_without_match() {
+        let results = expected_results(&a_document(), &a_filter(ComparisonOperator::LessThan, "0"));
+
+        assert!(results.members.is_empty());
+        assert_eq!(results.nodes, vec!["https://example.com/root"]);
+    }
+
+    #[test]
+    fn should_count_a_member_of_several_nodes_once() {
+        let results = expected_results(&a_document(), &a_filter(ComparisonOperator::Equal, "1"));
+
+        assert_eq!(results.members, vec!["https://example.com/member/1"]);
+        assert_eq!(
+            results.nodes,
+            vec!["https://example.com/root", "https://example.com/a"]
+        );
+    }
+
+    #[test]
+    fn should_find_a_smaller_set_than_the_first_holders_of_the_members() {
+        // root -> a holding m1 and root -> b -> c holding m1 and m2
+        let document = TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                a_node("root", &["a", "b"], Vec::new()),
+                a_node("a", &[], vec![a_member(1)]),
+                a_node("b", &["c"], Vec::new()),
+                a_node("c", &[], vec![a_member(1), a_member(2)]),
+            ],
+            None,
+            None,
+        );
+
+        let results = expected_results(&document, &a_filter(ComparisonOperator::GreaterThan, "0"));
+
+        assert_eq!(
+            results.members,
+            vec![
+                "https://example.com/member/1",
+                "https://example.com/member/2"
+            ]
+        );
+        assert_eq!(
+            results.nodes,
+            vec![
+                "https://example.com/root",
+                "https://example.com/b",
+                "https://example.com/c"
+            ]
+        );
+        assert!(results.is_minimal);
+    }
+
+    #[test]
+    fn should_not_follow_the_relations_a_client_can_prune() {
+        // the relation to a tells that its members have a value lower than 0
+        let to_a = Relation::new(
+            None,
+            Some(String::from("https://example.com/p")),
+            Some(Value {
+                value: String::from("0"),
+                value_type: ValueType::Int,
+            }),
+            String::from("https://example.com/a"),
+            Some(RelationOperator::LessThanRelation),
+        );
+        let to_b = a_node("root", &["b"], Vec::new()).relation()[0].clone();
+        let root = Node::new(
+            vec![to_a, to_b],
+            String::from("https://example.com/root"),
+            Vec::new(),
+        );
+        let document = TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                root,
+                a_node("a", &[], vec![a_member(1)]),
+                a_node("b", &[], vec![a_member(1)]),
+            ],
+            None,
+            None,
+        );
+
+        let results = expected_results(&document, &a_filter(ComparisonOperator::Equal, "1"));
+
+        assert_eq!(
+            results.nodes,
+            vec!["https://example.com/root", "https://example.com/b"]
+        );
+    }
+
+    #[test]
+    fn should_score_a_client() {
+        let results = expected_results(
+            &a_document(),
+            &a_filter(ComparisonOperator::LessThanOrEqual, "1"),
+        );
+
+        let score = results.score(
+            &[
+                String::from("https://example.com/member/0"),
+                String::from("https://example.com/member/2"),
+            ],
+            &[
+                String::from("https://example.com/root"),
+                String::from("https://example.com/a"),
+                String::from("https://example.com/b"),
+                String::from("https://example.com/c"),
+            ],
+        );
+
+        assert_eq!(score.precision, 0.5);
+        assert_eq!(score.recall, 0.5);
+        assert_eq!(score.traversal_efficiency, 0.5);
+    }
+}
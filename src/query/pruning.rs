@@ -0,0 +1,438 @@
+use super::comparison::compare_values;
+use super::filter::{ComparisonOperator, Filter};
+use super::geo::{bounding_box_of, points_of_wkt, BoundingBox};
+use crate::tree::relation::Relation;
+use crate::tree::relation_operator::RelationOperator;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::value::{Value, ValueType};
+use std::cmp::Ordering;
+
+/// Decide whether the node targeted by the relation can be skipped by a client evaluating the filter,
+/// because no member reachable through the relation can satisfy it.
+/// The decision is conservative: a relation without operator, path or value, or with a value
+/// that cannot be compared with the filter, is followed. A relation with no remaining item is always pruned.
+pub fn can_prune(filter: &Filter, relation: &Relation) -> bool {
+    if *relation.remaning_items() == Some(0) {
+        return true;
+    }
+    match (relation.relation_type(), relation.path(), relation.value()) {
+        (Some(operator), Some(path), Some(value)) => !is_satisfiable(
+            filter,
+            &Constraint {
+                operator,
+                path,
+                value,
+            },
+        ),
+        _ => false,
+    }
+}
+
+/// What a relation tells about the members of the node it targets.
+struct Constraint<'a> {
+    operator: &'a RelationOperator,
+    path: &'a ShaclPath,
+    value: &'a Value,
+}
+
+/// A bound of an interval of values.
+struct Bound<'a> {
+    value: &'a Value,
+    inclusive: bool,
+}
+
+/// The values between two bounds, `None` being unbounded.
+struct Interval<'a> {
+    lower: Option<Bound<'a>>,
+    upper: Option<Bound<'a>>,
+}
+
+/// Whether a member respecting the constraint might satisfy the filter.
+fn is_satisfiable(filter: &Filter, constraint: &Constraint) -> bool {
+    match filter {
+        Filter::And(filters) => filters
+            .iter()
+            .all(|filter| is_satisfiable(filter, constraint)),
+        Filter::Or(filters) => filters
+            .iter()
+            .any(|filter| is_satisfiable(filter, constraint)),
+        Filter::Comparison { path, .. }
+        | Filter::Prefix { path, .. }
+        | Filter::Substring { path, .. }
+        | Filter::Suffix { path, .. }
+        | Filter::GeoWithin { path, .. }
+            if path != constraint.path =>
+        {
+            true
+        }
+        Filter::Comparison {
+            operator, value, ..
+        } => is_comparison_satisfiable(*operator, value, constraint),
+        Filter::Prefix { value, .. } => is_prefix_satisfiable(value, constraint),
+        Filter::Substring { value, .. } => match constraint.operator {
+            RelationOperator::EqualThanRelation => constraint.value.value.contains(value.as_str()),
+            _ => true,
+        },
+        Filter::Suffix { value, .. } => match constraint.operator {
+            RelationOperator::SuffixRelation => {
+                value.ends_with(constraint.value.value.as_str())
+                    || constraint.value.value.ends_with(value.as_str())
+            }
+            RelationOperator::EqualThanRelation => constraint.value.value.ends_with(value.as_str()),
+            _ => true,
+        },
+        Filter::GeoWithin { area, .. } => match constraint.operator {
+            RelationOperator::GeospatiallyContainsRelation => {
+                match points_of_wkt(&constraint.value.value)
+                    .and_then(|points| bounding_box_of(&points))
+                {
+                    Some(geometry) => overlaps(&geometry, area),
+                    None => true,
+                }
+            }
+            _ => true,
+        },
+    }
+}
+
+fn is_comparison_satisfiable(
+    operator: ComparisonOperator,
+    value: &Value,
+    constraint: &Constraint,
+) -> bool {
+    let filter_interval = interval_of_comparison(operator, value);
+    match constraint.operator {
+        RelationOperator::PrefixRelation => {
+            if operator == ComparisonOperator::Equal {
+                return !is_textual(&value.value_type)
+                    || value.value.starts_with(constraint.value.value.as_str());
+            }
+            !is_prefix_outside(&constraint.value.value, &filter_interval)
+        }
+        RelationOperator::SuffixRelation if operator == ComparisonOperator::Equal => {
+            !is_textual(&value.value_type) || value.value.ends_with(constraint.value.value.as_str())
+        }
+        RelationOperator::SubstringRelation if operator == ComparisonOperator::Equal => {
+            !is_textual(&value.value_type) || value.value.contains(constraint.value.value.as_str())
+        }
+        relation_operator => match interval_of_relation(relation_operator, constraint.value) {
+            Some(relation_interval) => !are_disjoint(&relation_interval, &filter_interval),
+            None => true,
+        },
+    }
+}
+
+fn is_prefix_satisfiable(prefix: &str, constraint: &Constraint) -> bool {
+    let relation_value = constraint.value.value.as_str();
+    match constraint.operator {
+        RelationOperator::PrefixRelation => {
+            relation_value.starts_with(prefix) || prefix.starts_with(relation_value)
+        }
+        RelationOperator::EqualThanRelation => relation_value.starts_with(prefix),
+        RelationOperator::SubstringRelation
+        | RelationOperator::SuffixRelation
+        | RelationOperator::GeospatiallyContainsRelation => true,
+        relation_operator => match interval_of_relation(relation_operator, constraint.value) {
+            Some(relation_interval) => !is_prefix_outside(prefix, &relation_interval),
+            None => true,
+        },
+    }
+}
+
+/// The values respecting a comparison relation.
+fn interval_of_relation<'a>(operator: &RelationOperator, value: &'a Value) -> Option<Interval<'a>> {
+    let comparison_operator = match operator {
+        RelationOperator::GreaterThanRelation => ComparisonOperator::GreaterThan,
+        RelationOperator::GreaterThanOrEqualToRelation => ComparisonOperator::GreaterThanOrEqual,
+        RelationOperator::LessThanRelation => ComparisonOperator::LessThan,
+        RelationOperator::LessThanOrEqualToRelation => ComparisonOperator::LessThanOrEqual,
+        RelationOperator::EqualThanRelation => ComparisonOperator::Equal,
+        _ => return None,
+    };
+    Some(interval_of_comparison(comparison_operator, value))
+}
+
+/// The values satisfying a comparison with a value.
+fn interval_of_comparison(operator: ComparisonOperator, value: &Value) -> Interval<'_> {
+    let bound = |inclusive| Some(Bound { value, inclusive });
+    match operator {
+        ComparisonOperator::Equal => Interval {
+            lower: bound(true),
+            upper: bound(true),
+        },
+        ComparisonOperator::LessThan => Interval {
+            lower: None,
+            upper: bound(false),
+        },
+        ComparisonOperator::LessThanOrEqual => Interval {
+            lower: None,
+            upper: bound(true),
+        },
+        ComparisonOperator::GreaterThan => Interval {
+            lower: bound(false),
+            upper: None,
+        },
+        ComparisonOperator::GreaterThanOrEqual => Interval {
+            lower: bound(true),
+            upper: None,
+        },
+    }
+}
+
+/// Whether no value can be in both intervals, the bounds that cannot be compared being ignored.
+fn are_disjoint(a: &Interval, b: &Interval) -> bool {
+    let is_above = |lower: &Option<Bound>, upper: &Option<Bound>| match (lower, upper) {
+        (Some(lower), Some(upper)) => match compare_values(lower.value, upper.value) {
+            Some(Ordering::Greater) => true,
+            Some(Ordering::Equal) => !(lower.inclusive && upper.inclusive),
+            _ => false,
+        },
+        _ => false,
+    };
+    is_above(&a.lower, &b.upper) || is_above(&b.lower, &a.upper)
+}
+
+/// Whether no string starting with the prefix is in the interval of strings.
+/// The strings starting with a prefix are greater than or equal to it and lower than any
+/// greater string that does not start with it.
+fn is_prefix_outside(prefix: &str, interval: &Interval) -> bool {
+    let is_below = interval.upper.as_ref().is_some_and(|upper| {
+        is_textual(&upper.value.value_type)
+            && match upper.value.value.as_str().cmp(prefix) {
+                Ordering::Less => true,
+                Ordering::Equal => !upper.inclusive,
+                Ordering::Greater => false,
+            }
+    });
+    let is_above = interval.lower.as_ref().is_some_and(|lower| {
+        is_textual(&lower.value.value_type)
+            && lower.value.value.as_str() > prefix
+            && !lower.value.value.starts_with(prefix)
+    });
+    is_below || is_above
+}
+
+fn is_textual(value_type: &ValueType) -> bool {
+    matches!(value_type, ValueType::String | ValueType::LangString(_))
+}
+
+/// Whether a point could be both in the geometry, described by its bounding box, and in the interior of the area.
+fn overlaps(geometry: &BoundingBox, area: &BoundingBox) -> bool {
+    geometry.max.x > area.min.x
+        && geometry.min.x < area.max.x
+        && geometry.max.y > area.min.y
+        && geometry.min.y < area.max.y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::can_prune;
+    use crate::query::filter::{ComparisonOperator, Filter};
+    use crate::query::geo::{BoundingBox, Point};
+    use crate::rdf::vocabulary;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::value::{Value, ValueType};
+
+    const A_PATH: &str = "https://example.com/p";
+
+    fn a_value(value: &str, value_type: ValueType) -> Value {
+        Value {
+            value: String::from(value),
+            value_type,
+        }
+    }
+
+    fn a_relation(operator: RelationOperator, value: Value) -> Relation {
+        Relation::new(
+            None,
+            Some(String::from(A_PATH)),
+            Some(value),
+            String::from("https://example.com/node"),
+            Some(operator),
+        )
+    }
+
+    fn a_comparison(operator: ComparisonOperator, value: Value) -> Filter {
+        Filter::Comparison {
+            path: String::from(A_PATH),
+            operator,
+            value,
+        }
+    }
+
+    fn an_int(value: &str) -> Value {
+        a_value(value, ValueType::Int)
+    }
+
+    fn a_string(value: &str) -> Value {
+        a_value(value, ValueType::String)
+    }
+
+    #[test]
+    fn should_prune_a_comparison_relation_disjoint_with_the_filter() {
+        let relation = a_relation(RelationOperator::GreaterThanRelation, an_int("10"));
+
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::LessThanOrEqual, an_int("10")),
+            &relation
+        ));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::Equal, an_int("3")),
+            &relation
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::LessThan, an_int("11")),
+            &relation
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::GreaterThan, an_int("0")),
+            &relation
+        ));
+
+        let relation = a_relation(RelationOperator::LessThanOrEqualToRelation, an_int("5"));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::GreaterThanOrEqual, an_int("5")),
+            &relation
+        ));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::GreaterThan, an_int("5")),
+            &relation
+        ));
+    }
+
+    #[test]
+    fn should_combine_the_filters() {
+        let relation = a_relation(RelationOperator::EqualThanRelation, an_int("7"));
+        let outside = a_comparison(ComparisonOperator::GreaterThan, an_int("7"));
+        let inside = a_comparison(ComparisonOperator::LessThan, an_int("8"));
+
+        assert!(can_prune(
+            &Filter::And(vec![inside.clone(), outside.clone()]),
+            &relation
+        ));
+        assert!(!can_prune(&Filter::Or(vec![inside, outside]), &relation));
+    }
+
+    #[test]
+    fn should_not_prune_a_relation_over_another_path_or_an_incomparable_value() {
+        let relation = a_relation(RelationOperator::GreaterThanRelation, an_int("10"));
+
+        assert!(!can_prune(
+            &Filter::Comparison {
+                path: String::from("https://example.com/other"),
+                operator: ComparisonOperator::LessThan,
+                value: an_int("0"),
+            },
+            &relation
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::LessThan, a_string("0")),
+            &relation
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::LessThan, an_int("0")),
+            &Relation::new(
+                None,
+                None,
+                None,
+                String::from("https://example.com/node"),
+                None
+            )
+        ));
+    }
+
+    #[test]
+    fn should_prune_a_relation_without_remaining_item() {
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::LessThan, an_int("0")),
+            &Relation::new(
+                Some(0),
+                None,
+                None,
+                String::from("https://example.com/node"),
+                None
+            )
+        ));
+    }
+
+    #[test]
+    fn should_prune_the_string_relations() {
+        let prefix = a_relation(RelationOperator::PrefixRelation, a_string("ab"));
+        let a_prefix_filter = |value: &str| Filter::Prefix {
+            path: String::from(A_PATH),
+            value: String::from(value),
+        };
+
+        assert!(!can_prune(&a_prefix_filter("a"), &prefix));
+        assert!(!can_prune(&a_prefix_filter("abc"), &prefix));
+        assert!(can_prune(&a_prefix_filter("ac"), &prefix));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::Equal, a_string("ba")),
+            &prefix
+        ));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::GreaterThan, a_string("ac")),
+            &prefix
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::GreaterThan, a_string("abz")),
+            &prefix
+        ));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::LessThan, a_string("ab")),
+            &prefix
+        ));
+
+        let suffix = a_relation(RelationOperator::SuffixRelation, a_string("ing"));
+        assert!(can_prune(
+            &Filter::Suffix {
+                path: String::from(A_PATH),
+                value: String::from("ed"),
+            },
+            &suffix
+        ));
+        assert!(!can_prune(
+            &Filter::Suffix {
+                path: String::from(A_PATH),
+                value: String::from("ring"),
+            },
+            &suffix
+        ));
+
+        let substring = a_relation(RelationOperator::SubstringRelation, a_string("or"));
+        assert!(can_prune(
+            &a_comparison(ComparisonOperator::Equal, a_string("tree")),
+            &substring
+        ));
+        assert!(!can_prune(
+            &a_comparison(ComparisonOperator::Equal, a_string("forest")),
+            &substring
+        ));
+
+        let greater = a_relation(RelationOperator::GreaterThanRelation, a_string("m"));
+        assert!(can_prune(&a_prefix_filter("a"), &greater));
+        assert!(!can_prune(&a_prefix_filter("mo"), &greater));
+    }
+
+    #[test]
+    fn should_prune_a_geospatial_relation_outside_of_the_area() {
+        let relation = a_relation(
+            RelationOperator::GeospatiallyContainsRelation,
+            a_value(
+                "POLYGON((0 0, 2 0, 2 2, 0 2, 0 0))",
+                ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)),
+            ),
+        );
+        let an_area_filter = |min_x: f64, max_x: f64| Filter::GeoWithin {
+            path: String::from(A_PATH),
+            area: BoundingBox {
+                min: Point { x: min_x, y: 0.0 },
+                max: Point { x: max_x, y: 1.0 },
+            },
+        };
+
+        assert!(can_prune(&an_area_filter(2.0, 3.0), &relation));
+        assert!(!can_prune(&an_area_filter(1.0, 3.0), &relation));
+    }
+}
@@ -0,0 +1,355 @@
+use super::comparison::compare_values;
+use super::filter::{ComparisonOperator, Filter};
+use super::geo::{bounding_box_of, point_of, BoundingBox, Point};
+use crate::generator_argument::query_workload_argument::QueryWorkloadArg;
+use crate::rdf::vocabulary;
+use crate::seed::keyed_rng;
+use crate::tree::member::Member;
+use crate::tree::shacl_path::ShaclPath;
+use crate::tree::tree_document::TreeDocument;
+use crate::tree::value::{Value, ValueType};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+use std::vec::Vec;
+
+/// The kind of a generated query.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryKind {
+    /// The members having a value.
+    Point,
+    /// The members having a value between two bounds.
+    Range,
+    /// The members having a value starting with a string.
+    Prefix,
+    /// The members located in a rectangle.
+    Geo,
+}
+
+/// A query of the members of a collection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Query {
+    pub kind: QueryKind,
+    pub filter: Filter,
+    /// Number of members of the document satisfying the filter.
+    pub expected_result_count: usize,
+}
+
+impl Query {
+    /// The SPARQL SELECT query of the members of the collection satisfying the filter.
+    pub fn to_sparql(&self, collection_url: &str) -> String {
+        let mut query = format!(
+            "SELECT DISTINCT ?member WHERE {{\n  <{}> <{}> ?member .\n",
+            collection_url,
+            vocabulary::TREE_MEMBER
+        );
+        for (i, path) in self.filter.paths().iter().enumerate() {
+            query.push_str(&format!(
+                "  OPTIONAL {{ ?member <{}> ?value{} . }}\n",
+                path, i
+            ));
+        }
+        query.push_str(&format!("  FILTER({})\n}}\n", self.filter.to_sparql()));
+        query
+    }
+}
+
+/// Generate queries over the paths of the relations of the document, the filters using the
+/// values of the relations and of the members, or over the paths of the members when no relation
+/// has a path. A kind of query without usable value, like a geo query without WKT point, is skipped.
+pub fn generate_queries(document: &TreeDocument, arg: &QueryWorkloadArg) -> Vec<Query> {
+    let members = distinct_members(document);
+    let values_by_path = values_by_path(document, &members);
+    let mut rng = keyed_rng(arg.seed, "queries");
+
+    let mut queries: Vec<Query> = Vec::new();
+    for kind in arg.kinds.iter() {
+        let candidates: Vec<(&ShaclPath, Vec<&Value>)> = values_by_path
+            .iter()
+            .map(|(path, values)| {
+                (
+                    *path,
+                    values
+                        .iter()
+                        .copied()
+                        .filter(|value| is_candidate(kind, value))
+                        .collect::<Vec<&Value>>(),
+                )
+            })
+            .filter(|(_, values)| !values.is_empty())
+            .collect();
+        if candidates.is_empty() {
+            continue;
+        }
+        for _ in 0..arg.n_query_by_kind {
+            let (path, values) = candidates
+                .choose(&mut rng)
+                .expect("the candidates should not be empty");
+            let filter = generate_a_filter(kind, path, values, &mut rng);
+            let expected_result_count = members
+                .iter()
+                .filter(|member| filter.matches(member))
+                .count();
+            queries.push(Query {
+                kind: *kind,
+                filter,
+                expected_result_count,
+            });
+        }
+    }
+    queries
+}
+
+/// The members of the document, a member present in several nodes being counted once.
+pub(crate) fn distinct_members(document: &TreeDocument) -> Vec<&Member> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    document
+        .nodes()
+        .iter()
+        .flat_map(|node| node.members().iter())
+        .filter(|member| seen.insert(member.url.as_str()))
+        .collect()
+}
+
+/// The values of the relations and of the members by path, sorted by path so that the
+/// generation is reproducible.
+fn values_by_path<'a>(
+    document: &'a TreeDocument,
+    members: &[&'a Member],
+) -> BTreeMap<&'a ShaclPath, Vec<&'a Value>> {
+    let mut values_by_path: BTreeMap<&ShaclPath, Vec<&Value>> = BTreeMap::new();
+    for relation in document
+        .nodes()
+        .iter()
+        .flat_map(|node| node.relation().iter())
+    {
+        if let (Some(path), Some(value)) = (relation.path(), relation.value()) {
+            values_by_path.entry(path).or_default().push(value);
+        }
+    }
+    let has_relation_path = !values_by_path.is_empty();
+    for member in members {
+        let mut properties: Vec<(&ShaclPath, &Value)> = member.properties.iter().collect();
+        properties.sort_by(|a, b| a.0.cmp(b.0));
+        for (path, value) in properties {
+            match values_by_path.get_mut(path) {
+                Some(values) => values.push(value),
+                None if !has_relation_path => values_by_path.entry(path).or_default().push(value),
+                None => (),
+            }
+        }
+    }
+    values_by_path
+}
+
+/// Whether a value can be used to generate a query of a kind.
+fn is_candidate(kind: &QueryKind, value: &Value) -> bool {
+    match kind {
+        QueryKind::Point => true,
+        QueryKind::Range => compare_values(value, value) == Some(Ordering::Equal),
+        QueryKind::Prefix => {
+            matches!(
+                value.value_type,
+                ValueType::String | ValueType::LangString(_) | ValueType::Iri
+            ) && !value.value.is_empty()
+        }
+        QueryKind::Geo => point_of(value).is_some(),
+    }
+}
+
+fn generate_a_filter(
+    kind: &QueryKind,
+    path: &ShaclPath,
+    values: &[&Value],
+    rng: &mut StdRng,
+) -> Filter {
+    let value = *values.choose(rng).expect("the values should not be empty");
+    match kind {
+        QueryKind::Point => Filter::Comparison {
+            path: path.clone(),
+            operator: ComparisonOperator::Equal,
+            value: value.clone(),
+        },
+        QueryKind::Range => {
+            // a bound comparable with the first one, the first one itself at worst
+            let comparable: Vec<&Value> = values
+                .iter()
+                .copied()
+                .filter(|other| compare_values(value, other).is_some())
+                .collect();
+            let other = *comparable.choose(rng).unwrap_or(&value);
+            let (lower, upper) = match compare_values(value, other) {
+                Some(Ordering::Greater) => (other, value),
+                _ => (value, other),
+            };
+            Filter::And(vec![
+                Filter::Comparison {
+                    path: path.clone(),
+                    operator: ComparisonOperator::GreaterThanOrEqual,
+                    value: lower.clone(),
+                },
+                Filter::Comparison {
+                    path: path.clone(),
+                    operator: ComparisonOperator::LessThanOrEqual,
+                    value: upper.clone(),
+                },
+            ])
+        }
+        QueryKind::Prefix => {
+            let n_char = value.value.chars().count();
+            let length = rng.gen_range(1..=n_char);
+            Filter::Prefix {
+                path: path.clone(),
+                value: value.value.chars().take(length).collect(),
+            }
+        }
+        QueryKind::Geo => {
+            let points: Vec<Point> = values.iter().filter_map(|value| point_of(value)).collect();
+            let extent = bounding_box_of(&points).expect("the points should not be empty");
+            let center = point_of(value).expect("the value should be a WKT point");
+            // a rectangle between a twentieth and the whole of the extent of the points
+            let half_width =
+                (extent.max.x - extent.min.x).max(f64::EPSILON) * rng.gen_range(0.05..1.0) / 2.0;
+            let half_height =
+                (extent.max.y - extent.min.y).max(f64::EPSILON) * rng.gen_range(0.05..1.0) / 2.0;
+            Filter::GeoWithin {
+                path: path.clone(),
+                area: BoundingBox {
+                    min: Point {
+                        x: center.x - half_width,
+                        y: center.y - half_height,
+                    },
+                    max: Point {
+                        x: center.x + half_width,
+                        y: center.y + half_height,
+                    },
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_queries, Query, QueryKind};
+    use crate::generator_argument::query_workload_argument::QueryWorkloadArg;
+    use crate::query::filter::{ComparisonOperator, Filter};
+    use crate::rdf::vocabulary;
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::HashMap;
+
+    fn a_member(i: usize) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([
+                (
+                    String::from("https://example.com/p"),
+                    Value {
+                        value: i.to_string(),
+                        value_type: ValueType::Int,
+                    },
+                ),
+                (
+                    String::from("https://example.com/location"),
+                    Value {
+                        value: format!("POINT({} {})", i, i),
+                        value_type: ValueType::Custom(String::from(vocabulary::GEO_WKT_LITERAL)),
+                    },
+                ),
+            ]),
+        }
+    }
+
+    fn a_document() -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                Vec::new(),
+                None,
+            ),
+            vec![
+                Node::new(
+                    vec![Relation::new(
+                        None,
+                        Some(String::from("https://example.com/p")),
+                        Some(Value {
+                            value: String::from("5"),
+                            value_type: ValueType::Int,
+                        }),
+                        String::from("https://example.com/root/0"),
+                        Some(RelationOperator::GreaterThanOrEqualToRelation),
+                    )],
+                    String::from("https://example.com/root"),
+                    (0..5).map(a_member).collect(),
+                ),
+                Node::new(
+                    Vec::new(),
+                    String::from("https://example.com/root/0"),
+                    (5..10).map(a_member).collect(),
+                ),
+            ],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn should_generate_queries_over_the_paths_of_the_relations() {
+        let document = a_document();
+        let arg = QueryWorkloadArg {
+            kinds: vec![
+                QueryKind::Point,
+                QueryKind::Range,
+                QueryKind::Prefix,
+                QueryKind::Geo,
+            ],
+            n_query_by_kind: 3,
+            seed: 1,
+        };
+
+        let queries = generate_queries(&document, &arg);
+
+        // no string value for the prefix queries and no WKT point on the path of the relations
+        assert_eq!(queries.len(), 6);
+        for query in queries.iter() {
+            assert_eq!(query.filter.paths(), vec!["https://example.com/p"]);
+            let expected_result_count = (0..10)
+                .map(a_member)
+                .filter(|member| query.filter.matches(member))
+                .count();
+            assert_eq!(query.expected_result_count, expected_result_count);
+            assert!(query.expected_result_count >= 1);
+        }
+        assert_eq!(queries, generate_queries(&document, &arg));
+    }
+
+    #[test]
+    fn should_serialize_a_query_in_sparql() {
+        let query = Query {
+            kind: QueryKind::Point,
+            filter: Filter::Comparison {
+                path: String::from("https://example.com/p"),
+                operator: ComparisonOperator::Equal,
+                value: Value {
+                    value: String::from("1"),
+                    value_type: ValueType::Int,
+                },
+            },
+            expected_result_count: 1,
+        };
+
+        assert_eq!(
+            query.to_sparql("https://example.com/collection"),
+            "SELECT DISTINCT ?member WHERE {\n  <https://example.com/collection> <https://w3id.org/tree#member> ?member .\n  OPTIONAL { ?member <https://example.com/p> ?value0 . }\n  FILTER(?value0 = \"1\"^^<http://www.w3.org/2001/XMLSchema#int>)\n}\n"
+        );
+    }
+}
@@ -0,0 +1,46 @@
+use super::json_ld::to_json_ld;
+use super::json_ld_parser::parse_json_ld;
+use super::parse_error::ParseError;
+use super::triple::{to_n_triples, Triple};
+use super::turtle::to_turtle;
+use super::turtle_parser::parse_turtle;
+
+/// A serialization format of RDF.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RdfFormat {
+    Turtle,
+    JsonLd,
+    NTriples,
+}
+
+impl RdfFormat {
+    /// All the formats, by order of preference.
+    pub const ALL: [RdfFormat; 3] = [RdfFormat::Turtle, RdfFormat::JsonLd, RdfFormat::NTriples];
+
+    /// The media type of the format.
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            RdfFormat::Turtle => "text/turtle",
+            RdfFormat::JsonLd => "application/ld+json",
+            RdfFormat::NTriples => "application/n-triples",
+        }
+    }
+
+    /// Serialize the triples in the format.
+    pub fn serialize(&self, triples: &[Triple]) -> String {
+        match self {
+            RdfFormat::Turtle => to_turtle(triples),
+            RdfFormat::JsonLd => to_json_ld(triples),
+            RdfFormat::NTriples => to_n_triples(triples),
+        }
+    }
+
+    /// Parse a document in the format, the relative IRIs of a Turtle or N-Triples document
+    /// being resolved against the base IRI.
+    pub fn parse(&self, document: &str, base_iri: Option<&str>) -> Result<Vec<Triple>, ParseError> {
+        match self {
+            RdfFormat::Turtle | RdfFormat::NTriples => parse_turtle(document, base_iri),
+            RdfFormat::JsonLd => parse_json_ld(document, base_iri),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use super::term::Term;
+use super::triple::Triple;
+use super::vocabulary;
+use crate::tree::value::ValueType;
+use serde_json::{json, Map, Value as JsonValue};
+use std::vec::Vec;
+
+/// Serialize triples as a [flattened](https://www.w3.org/TR/json-ld11/#flattened-document-form)
+/// JSON-LD document without context, a node object by subject in the order of their first appearance.
+pub fn to_json_ld(triples: &[Triple]) -> String {
+    let mut node_objects: Vec<(&Term, Map<String, JsonValue>)> = Vec::new();
+    for triple in triples {
+        let position = match node_objects
+            .iter()
+            .position(|(subject, _)| *subject == &triple.subject)
+        {
+            Some(v) => v,
+            None => {
+                let mut node_object = Map::new();
+                node_object.insert(String::from("@id"), json!(id(&triple.subject)));
+                node_objects.push((&triple.subject, node_object));
+                node_objects.len() - 1
+            }
+        };
+        let (key, object) = if triple.predicate == vocabulary::RDF_TYPE {
+            (String::from("@type"), json!(id(&triple.object)))
+        } else {
+            (triple.predicate.clone(), object(&triple.object))
+        };
+        let values = node_objects[position].1.entry(key).or_insert(json!([]));
+        values
+            .as_array_mut()
+            .expect("the values of a property should be an array")
+            .push(object);
+    }
+
+    JsonValue::Array(
+        node_objects
+            .into_iter()
+            .map(|(_, node_object)| JsonValue::Object(node_object))
+            .collect(),
+    )
+    .to_string()
+}
+
+fn id(term: &Term) -> String {
+    match term {
+        Term::NamedNode(v) => v.clone(),
+        Term::BlankNode(v) => format!("_:{}", v),
+        Term::Literal(v) => v.value.clone(),
+    }
+}
+
+fn object(term: &Term) -> JsonValue {
+    match term {
+        Term::Literal(v) => match &v.value_type {
+            ValueType::LangString(language) => json!({"@value": v.value, "@language": language}),
+            ValueType::Iri => json!({"@id": v.value}),
+            value_type => json!({"@value": v.value, "@type": value_type.to_string()}),
+        },
+        _ => json!({ "@id": id(term) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_json_ld;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+    use crate::tree::value::{Value, ValueType};
+    use serde_json::json;
+
+    #[test]
+    fn should_return_a_node_object_by_subject() {
+        let triples = vec![
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::TREE_NODE_CLASS)),
+            ),
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::TREE_RELATION),
+                Term::BlankNode(String::from("relation0")),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from(vocabulary::TREE_VALUE),
+                Term::Literal(Value {
+                    value: String::from("chat"),
+                    value_type: ValueType::LangString(String::from("fr")),
+                }),
+            ),
+        ];
+
+        let json_ld: serde_json::Value = serde_json::from_str(&to_json_ld(&triples)).unwrap();
+
+        assert_eq!(
+            json_ld,
+            json!([
+                {
+                    "@id": "https://example.com/root",
+                    "@type": [vocabulary::TREE_NODE_CLASS],
+                    vocabulary::TREE_RELATION: [{"@id": "_:relation0"}]
+                },
+                {
+                    "@id": "_:relation0",
+                    vocabulary::TREE_VALUE: [{"@value": "chat", "@language": "fr"}]
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,495 @@
+use super::parse_error::ParseError;
+use super::term::Term;
+use super::triple::Triple;
+use super::turtle_parser::resolve_iri;
+use super::vocabulary;
+use crate::tree::value::{Value, ValueType};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// Parse a [JSON-LD](https://www.w3.org/TR/json-ld11/) document into triples.
+/// Only the embedded contexts are supported, with prefixes, terms, type coercion and `@vocab`,
+/// and the named graphs are merged into the default graph.
+/// The relative IRIs of the nodes are resolved against the base IRI like in [Turtle](`super::turtle_parser::parse_turtle`).
+pub fn parse_json_ld(document: &str, base_iri: Option<&str>) -> Result<Vec<Triple>, ParseError> {
+    let json: JsonValue = serde_json::from_str(document).map_err(|error| ParseError {
+        message: "invalid JSON",
+        line: Some(error.line()),
+    })?;
+    let mut parser = JsonLdParser {
+        triples: Vec::new(),
+        n_blank_node: 0,
+    };
+    let context = Context {
+        base: base_iri.map(String::from),
+        ..Default::default()
+    };
+    parser.parse_top_level(&json, &context)?;
+    Ok(parser.triples)
+}
+
+/// The mapping of the terms and prefixes to IRIs of a JSON-LD context.
+#[derive(Clone, Default)]
+struct Context {
+    terms: HashMap<String, TermDefinition>,
+    vocab: Option<String>,
+    /// IRI against which the relative IRIs of the nodes are resolved.
+    base: Option<String>,
+}
+
+#[derive(Clone)]
+struct TermDefinition {
+    iri: String,
+    /// The `@type` of the values of the term, `@id` when they are IRIs.
+    coercion: Option<String>,
+}
+
+struct JsonLdParser {
+    triples: Vec<Triple>,
+    /// Number of node objects without `@id`, used to name them.
+    n_blank_node: usize,
+}
+
+impl JsonLdParser {
+    fn parse_top_level(&mut self, json: &JsonValue, context: &Context) -> Result<(), ParseError> {
+        match json {
+            JsonValue::Array(items) => {
+                for item in items {
+                    self.parse_top_level(item, context)?;
+                }
+                Ok(())
+            }
+            JsonValue::Object(object) => {
+                let context = with_context(context, object)?;
+                let is_graph_container = object.contains_key("@graph")
+                    && object
+                        .keys()
+                        .all(|key| key == "@graph" || key == "@context" || key == "@id");
+                match object.get("@graph") {
+                    Some(graph) if is_graph_container => self.parse_top_level(graph, &context),
+                    _ => self.parse_node_object(object, &context).map(|_| ()),
+                }
+            }
+            _ => Err(invalid("expected a node object")),
+        }
+    }
+
+    /// Parse a node object and return its subject.
+    fn parse_node_object(
+        &mut self,
+        object: &Map<String, JsonValue>,
+        context: &Context,
+    ) -> Result<Term, ParseError> {
+        let context = with_context(context, object)?;
+        let subject = match object.get("@id") {
+            Some(JsonValue::String(id)) => self.node(&expand_iri(id, &context, false)),
+            Some(_) => return Err(invalid("@id should be a string")),
+            None => self.new_blank_node(),
+        };
+        for (key, values) in object {
+            match key.as_str() {
+                "@type" => {
+                    for value in as_array(values) {
+                        let class = value
+                            .as_str()
+                            .ok_or_else(|| invalid("@type should be a string"))?;
+                        let class = self.node(&expand_iri(class, &context, true));
+                        self.triples.push(Triple::new(
+                            subject.clone(),
+                            String::from(vocabulary::RDF_TYPE),
+                            class,
+                        ));
+                    }
+                }
+                "@graph" => self.parse_top_level(values, &context)?,
+                key if key.starts_with('@') => (),
+                key => {
+                    let predicate = expand_iri(key, &context, true);
+                    // a key that is not mapped to an absolute IRI is ignored
+                    if !predicate.contains(':') || predicate.starts_with("_:") {
+                        continue;
+                    }
+                    let coercion = context
+                        .terms
+                        .get(key)
+                        .and_then(|definition| definition.coercion.clone());
+                    for value in as_array(values) {
+                        let object = self.parse_value(value, coercion.as_deref(), &context)?;
+                        self.triples
+                            .push(Triple::new(subject.clone(), predicate.clone(), object));
+                    }
+                }
+            }
+        }
+        Ok(subject)
+    }
+
+    fn parse_value(
+        &mut self,
+        value: &JsonValue,
+        coercion: Option<&str>,
+        context: &Context,
+    ) -> Result<Term, ParseError> {
+        match value {
+            JsonValue::String(v) => Ok(match coercion {
+                Some("@id") => self.node(&expand_iri(v, context, false)),
+                Some("@vocab") => self.node(&expand_iri(v, context, true)),
+                Some(datatype) => a_literal(v, ValueType::from_datatype(datatype)),
+                None => a_literal(v, ValueType::String),
+            }),
+            JsonValue::Bool(v) => Ok(a_literal(&v.to_string(), ValueType::Boolean)),
+            JsonValue::Number(v) => Ok(if v.is_f64() {
+                a_literal(
+                    &format!("{:E}", v.as_f64().unwrap_or_default()),
+                    ValueType::Double,
+                )
+            } else {
+                a_literal(&v.to_string(), ValueType::Integer)
+            }),
+            JsonValue::Object(object) => {
+                if let Some(literal) = object.get("@value") {
+                    return parse_value_object(literal, object, context);
+                }
+                if let Some(items) = object.get("@list") {
+                    return self.parse_list(items, coercion, context);
+                }
+                self.parse_node_object(object, context)
+            }
+            JsonValue::Array(_) => Err(invalid("nested arrays are not supported")),
+            JsonValue::Null => Err(invalid("unexpected null value")),
+        }
+    }
+
+    fn parse_list(
+        &mut self,
+        items: &JsonValue,
+        coercion: Option<&str>,
+        context: &Context,
+    ) -> Result<Term, ParseError> {
+        let mut list = Term::NamedNode(String::from(RDF_NIL));
+        let items: Vec<Term> = as_array(items)
+            .iter()
+            .map(|item| self.parse_value(item, coercion, context))
+            .collect::<Result<Vec<Term>, ParseError>>()?;
+        for item in items.into_iter().rev() {
+            let node = self.new_blank_node();
+            self.triples
+                .push(Triple::new(node.clone(), String::from(RDF_FIRST), item));
+            self.triples
+                .push(Triple::new(node.clone(), String::from(RDF_REST), list));
+            list = node;
+        }
+        Ok(list)
+    }
+
+    /// A named node or, for an identifier starting with `_:`, a blank node.
+    fn node(&self, id: &str) -> Term {
+        match id.strip_prefix("_:") {
+            Some(label) => Term::labelled_blank_node(label),
+            None => Term::NamedNode(String::from(id)),
+        }
+    }
+
+    fn new_blank_node(&mut self) -> Term {
+        self.n_blank_node += 1;
+        Term::anonymous_blank_node(self.n_blank_node)
+    }
+}
+
+fn parse_value_object(
+    literal: &JsonValue,
+    object: &Map<String, JsonValue>,
+    context: &Context,
+) -> Result<Term, ParseError> {
+    let lexical_form = match literal {
+        JsonValue::String(v) => v.clone(),
+        JsonValue::Bool(v) => v.to_string(),
+        JsonValue::Number(v) => v.to_string(),
+        _ => return Err(invalid("@value should be a string, a number or a boolean")),
+    };
+    let value_type = match (object.get("@language"), object.get("@type"), literal) {
+        (Some(JsonValue::String(language)), _, _) => ValueType::LangString(language.clone()),
+        (_, Some(JsonValue::String(datatype)), _) => {
+            ValueType::from_datatype(&expand_iri(datatype, context, true))
+        }
+        (Some(_), _, _) | (_, Some(_), _) => {
+            return Err(invalid("@language and @type should be strings"))
+        }
+        (None, None, JsonValue::Bool(_)) => ValueType::Boolean,
+        (None, None, JsonValue::Number(v)) if v.is_f64() => ValueType::Double,
+        (None, None, JsonValue::Number(_)) => ValueType::Integer,
+        (None, None, _) => ValueType::String,
+    };
+    Ok(a_literal(&lexical_form, value_type))
+}
+
+/// The context of a node object, the context of its parent updated by its `@context`.
+fn with_context(context: &Context, object: &Map<String, JsonValue>) -> Result<Context, ParseError> {
+    let mut context = context.clone();
+    let local_contexts = match object.get("@context") {
+        Some(v) => as_array(v),
+        None => return Ok(context),
+    };
+    for local_context in local_contexts {
+        let definitions = match local_context {
+            JsonValue::Object(v) => v,
+            JsonValue::Null => {
+                // the base IRI is not part of the context that is reset
+                context = Context {
+                    base: context.base,
+                    ..Default::default()
+                };
+                continue;
+            }
+            _ => return Err(invalid("remote contexts are not supported")),
+        };
+        if let Some(vocab) = definitions.get("@vocab").and_then(|v| v.as_str()) {
+            context.vocab = Some(expand_iri(vocab, &context, false));
+        }
+        // the definitions can refer to the prefixes defined next to them, so a definition
+        // is expanded once the prefixes it uses are
+        let mut pending: Vec<(&String, &JsonValue)> = definitions
+            .iter()
+            .filter(|(term, _)| !term.starts_with('@'))
+            .collect();
+        for _ in 0..=pending.len() {
+            pending.retain(|(term, definition)| {
+                let (iri, coercion) = match definition {
+                    JsonValue::String(iri) => (iri.as_str(), None),
+                    JsonValue::Object(v) => (
+                        v.get("@id")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or(term.as_str()),
+                        v.get("@type").and_then(|v| v.as_str()),
+                    ),
+                    _ => return false,
+                };
+                let is_pending = |value: &str| {
+                    value.split_once(':').is_some_and(|(prefix, _)| {
+                        prefix != term.as_str()
+                            && definitions.contains_key(prefix)
+                            && !context.terms.contains_key(prefix)
+                    })
+                };
+                if is_pending(iri) || coercion.is_some_and(is_pending) {
+                    return true;
+                }
+                let coercion = coercion.map(|v| match v {
+                    "@id" | "@vocab" => String::from(v),
+                    datatype => expand_iri(datatype, &context, true),
+                });
+                let iri = expand_iri(iri, &context, true);
+                context
+                    .terms
+                    .insert((*term).clone(), TermDefinition { iri, coercion });
+                false
+            });
+        }
+    }
+    Ok(context)
+}
+
+/// Expand a term, a compact IRI or, in the position of a vocabulary term, a name relative to `@vocab`.
+fn expand_iri(value: &str, context: &Context, is_vocabulary: bool) -> String {
+    if let Some(definition) = context.terms.get(value) {
+        return definition.iri.clone();
+    }
+    if let Some((prefix, suffix)) = value.split_once(':') {
+        if !suffix.starts_with("//") {
+            if let Some(definition) = context.terms.get(prefix) {
+                return format!("{}{}", definition.iri, suffix);
+            }
+        }
+        return String::from(value);
+    }
+    match (&context.vocab, &context.base) {
+        (Some(vocab), _) if is_vocabulary => format!("{}{}", vocab, value),
+        (_, Some(base)) if !is_vocabulary => resolve_iri(base, String::from(value)),
+        _ => String::from(value),
+    }
+}
+
+fn as_array(value: &JsonValue) -> Vec<&JsonValue> {
+    match value {
+        JsonValue::Array(values) => values.iter().collect(),
+        value => vec![value],
+    }
+}
+
+fn a_literal(value: &str, value_type: ValueType) -> Term {
+    Term::Literal(Value {
+        value: String::from(value),
+        value_type,
+    })
+}
+
+fn invalid(message: &'static str) -> ParseError {
+    ParseError {
+        message,
+        line: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_json_ld;
+    use crate::rdf::json_ld::to_json_ld;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+    use crate::tree::value::{Value, ValueType};
+
+    fn a_literal(value: &str, value_type: ValueType) -> Term {
+        Term::Literal(Value {
+            value: String::from(value),
+            value_type,
+        })
+    }
+
+    #[test]
+    fn should_parse_the_serialized_json_ld() {
+        let triples = vec![
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::TREE_NODE_CLASS)),
+            ),
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::TREE_RELATION),
+                Term::BlankNode(String::from("relation0")),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from(vocabulary::TREE_REMAINING_ITEMS),
+                a_literal("3", ValueType::Integer),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from(vocabulary::TREE_VALUE),
+                a_literal("chat", ValueType::LangString(String::from("fr"))),
+            ),
+        ];
+
+        // the properties of a node object are read in lexicographic order
+        assert_eq!(parse_json_ld(&to_json_ld(&triples), None), Ok(triples));
+    }
+
+    #[test]
+    fn should_expand_a_compacted_document() {
+        let document = r#"{
+            "@context": {
+                "tree": "https://w3id.org/tree#",
+                "xsd": "http://www.w3.org/2001/XMLSchema#",
+                "relation": {"@id": "tree:relation"},
+                "node": {"@id": "tree:node", "@type": "@id"},
+                "value": {"@id": "tree:value", "@type": "xsd:int"}
+            },
+            "@id": "https://example.com/root",
+            "@type": "tree:Node",
+            "relation": [{
+                "@type": "tree:GreaterThanRelation",
+                "node": "https://example.com/1",
+                "value": "5",
+                "tree:remainingItems": 10.5,
+                "ignored": true
+            }]
+        }"#;
+
+        let triples = parse_json_ld(document, None).unwrap();
+
+        let relation = Term::BlankNode(String::from("anonymous1"));
+        assert_eq!(
+            triples,
+            vec![
+                Triple::new(
+                    Term::NamedNode(String::from("https://example.com/root")),
+                    String::from(vocabulary::RDF_TYPE),
+                    Term::NamedNode(String::from(vocabulary::TREE_NODE_CLASS)),
+                ),
+                Triple::new(
+                    relation.clone(),
+                    String::from(vocabulary::RDF_TYPE),
+                    Term::NamedNode(String::from("https://w3id.org/tree#GreaterThanRelation")),
+                ),
+                Triple::new(
+                    relation.clone(),
+                    String::from(vocabulary::TREE_NODE),
+                    Term::NamedNode(String::from("https://example.com/1")),
+                ),
+                Triple::new(
+                    relation.clone(),
+                    String::from(vocabulary::TREE_REMAINING_ITEMS),
+                    a_literal("1.05E1", ValueType::Double),
+                ),
+                Triple::new(
+                    relation.clone(),
+                    String::from(vocabulary::TREE_VALUE),
+                    a_literal("5", ValueType::Int),
+                ),
+                Triple::new(
+                    Term::NamedNode(String::from("https://example.com/root")),
+                    String::from(vocabulary::TREE_RELATION),
+                    relation,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_resolve_the_relative_ids_and_keep_the_blank_node_labels_apart() {
+        let document = r#"{
+            "@id": "../root",
+            "https://w3id.org/tree#relation": [
+                {"@id": "_:anonymous1"},
+                {"https://w3id.org/tree#node": {"@id": "1"}}
+            ]
+        }"#;
+
+        let triples = parse_json_ld(document, Some("https://example.com/a/b")).unwrap();
+
+        let root = Term::NamedNode(String::from("https://example.com/a/../root"));
+        assert_eq!(
+            triples,
+            vec![
+                Triple::new(
+                    root.clone(),
+                    String::from(vocabulary::TREE_RELATION),
+                    Term::BlankNode(String::from("_anonymous1")),
+                ),
+                Triple::new(
+                    Term::BlankNode(String::from("anonymous1")),
+                    String::from(vocabulary::TREE_NODE),
+                    Term::NamedNode(String::from("https://example.com/a/1")),
+                ),
+                Triple::new(
+                    root,
+                    String::from(vocabulary::TREE_RELATION),
+                    Term::BlankNode(String::from("anonymous1")),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_reject_an_invalid_document() {
+        assert_eq!(
+            parse_json_ld("[\n{\"@id\": }", None).unwrap_err().line,
+            Some(2)
+        );
+        assert_eq!(
+            parse_json_ld(
+                r#"{"@context": "https://example.com/context.jsonld"}"#,
+                None
+            )
+            .unwrap_err()
+            .message,
+            "remote contexts are not supported"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+pub mod format;
+pub mod json_ld;
+pub mod json_ld_parser;
+pub mod parse_error;
+pub mod term;
+pub mod triple;
+pub mod turtle;
+pub mod turtle_parser;
+pub mod vocabulary;
@@ -0,0 +1,19 @@
+use std::fmt;
+
+/// Error of the parsing of an RDF document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// What is wrong with the document.
+    pub message: &'static str,
+    /// The line of the error starting at 1, if it is known.
+    pub line: Option<usize>,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{} at line {}", self.message, line),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::tree::value::{Value, ValueType};
+use std::fmt;
+
+/// An [RDF term](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-term).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Term {
+    /// An IRI.
+    NamedNode(String),
+    /// A blank node with its local label.
+    BlankNode(String),
+    /// A literal.
+    Literal(Value),
+}
+
+/// Start of the labels of the blank nodes that a parser names itself.
+const ANONYMOUS_LABEL: &str = "anonymous";
+
+impl Term {
+    /// The blank node labelled `label` in a parsed document. A label that could clash with the
+    /// [anonymous blank nodes](`Term::anonymous_blank_node`) is prefixed with `_`.
+    pub(crate) fn labelled_blank_node(label: &str) -> Term {
+        if label.trim_start_matches('_').starts_with(ANONYMOUS_LABEL) {
+            Term::BlankNode(format!("_{}", label))
+        } else {
+            Term::BlankNode(String::from(label))
+        }
+    }
+
+    /// The `n`-th blank node without label of a parsed document.
+    pub(crate) fn anonymous_blank_node(n: usize) -> Term {
+        Term::BlankNode(format!("{}{}", ANONYMOUS_LABEL, n))
+    }
+}
+
+impl From<Value> for Term {
+    fn from(value: Value) -> Self {
+        match value.value_type {
+            ValueType::Iri => Term::NamedNode(value.value),
+            _ => Term::Literal(value),
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    /// Serialize the term following [N-Triples](https://www.w3.org/TR/n-triples/).
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Term::NamedNode(iri) => write!(f, "<{}>", iri),
+            Term::BlankNode(label) => write!(f, "_:{}", label),
+            Term::Literal(value) => write!(f, "{}", value),
+        }
+    }
+}
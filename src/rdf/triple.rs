@@ -0,0 +1,28 @@
+use super::term::Term;
+use std::fmt;
+
+/// An [RDF triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple).
+#[derive(derive_new::new, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Triple {
+    /// Subject, either a named node or a blank node.
+    pub subject: Term,
+    /// IRI of the predicate.
+    pub predicate: String,
+    /// Object.
+    pub object: Term,
+}
+
+impl fmt::Display for Triple {
+    /// Serialize the triple as an [N-Triples](https://www.w3.org/TR/n-triples/) statement.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} <{}> {} .", self.subject, self.predicate, self.object)
+    }
+}
+
+/// Serialize triples as an [N-Triples](https://www.w3.org/TR/n-triples/) document.
+pub fn to_n_triples(triples: &[Triple]) -> String {
+    triples
+        .iter()
+        .map(|triple| format!("{}\n", triple))
+        .collect()
+}
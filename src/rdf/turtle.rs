@@ -0,0 +1,126 @@
+use super::term::Term;
+use super::triple::Triple;
+use super::vocabulary;
+use crate::tree::value::{escape_literal, ValueType};
+use std::vec::Vec;
+
+/// Prefixes used to compact the IRIs of a [Turtle](https://www.w3.org/TR/turtle/) document.
+pub const PREFIXES: [(&str, &str); 7] = [
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("tree", "https://w3id.org/tree#"),
+    ("sh", "http://www.w3.org/ns/shacl#"),
+    ("hydra", "http://www.w3.org/ns/hydra/core#"),
+    ("ldes", "https://w3id.org/ldes#"),
+    ("geo", "http://www.opengis.net/ont/geosparql#"),
+];
+
+/// Serialize triples as a [Turtle](https://www.w3.org/TR/turtle/) document,
+/// grouping the triples by subject in the order of their first appearance.
+pub fn to_turtle(triples: &[Triple]) -> String {
+    let mut document: String = PREFIXES
+        .iter()
+        .map(|(prefix, namespace)| format!("@prefix {}: <{}> .\n", prefix, namespace))
+        .collect();
+
+    let mut subjects: Vec<&Term> = Vec::new();
+    for triple in triples {
+        if !subjects.contains(&&triple.subject) {
+            subjects.push(&triple.subject);
+        }
+    }
+    for subject in subjects {
+        let predicate_objects: Vec<String> = triples
+            .iter()
+            .filter(|triple| triple.subject == *subject)
+            .map(|triple| format!("{} {}", predicate(&triple.predicate), term(&triple.object)))
+            .collect();
+        document.push_str(&format!(
+            "\n{} {} .\n",
+            term(subject),
+            predicate_objects.join(" ;\n    ")
+        ));
+    }
+    document
+}
+
+fn predicate(iri: &str) -> String {
+    if iri == vocabulary::RDF_TYPE {
+        String::from("a")
+    } else {
+        self::iri(iri)
+    }
+}
+
+fn term(term: &Term) -> String {
+    match term {
+        Term::NamedNode(v) => iri(v),
+        Term::Literal(v) => match &v.value_type {
+            ValueType::Iri | ValueType::LangString(_) => v.to_string(),
+            value_type => format!(
+                "\"{}\"^^{}",
+                escape_literal(&v.value),
+                iri(&value_type.to_string())
+            ),
+        },
+        Term::BlankNode(_) => term.to_string(),
+    }
+}
+
+/// Compact the IRI with a prefix when its local name is simple enough to be a prefixed name.
+fn iri(iri: &str) -> String {
+    for (prefix, namespace) in PREFIXES {
+        if let Some(local_name) = iri.strip_prefix(namespace) {
+            if !local_name.is_empty()
+                && local_name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return format!("{}:{}", prefix, local_name);
+            }
+        }
+    }
+    format!("<{}>", iri)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_turtle;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+    use crate::tree::value::{Value, ValueType};
+
+    #[test]
+    fn should_group_the_triples_by_subject_and_compact_the_iris() {
+        let triples = vec![
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::TREE_NODE_CLASS)),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from(vocabulary::TREE_VALUE),
+                Term::Literal(Value {
+                    value: String::from("5"),
+                    value_type: ValueType::Int,
+                }),
+            ),
+            Triple::new(
+                Term::NamedNode(String::from("https://example.com/root")),
+                String::from(vocabulary::TREE_RELATION),
+                Term::BlankNode(String::from("relation0")),
+            ),
+        ];
+
+        let turtle = to_turtle(&triples);
+
+        assert!(
+            turtle.starts_with("@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .\n")
+        );
+        assert!(turtle.ends_with(
+            "\n<https://example.com/root> a tree:Node ;\n    tree:relation _:relation0 .\n\n_:relation0 tree:value \"5\"^^xsd:int .\n"
+        ));
+    }
+}
@@ -0,0 +1,725 @@
+use super::parse_error::ParseError;
+use super::term::Term;
+use super::triple::Triple;
+use super::vocabulary;
+use crate::tree::value::{Value, ValueType};
+use std::collections::HashMap;
+use std::vec::Vec;
+
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+/// Parse a [Turtle](https://www.w3.org/TR/turtle/) document, or an N-Triples document which is a subset of it.
+/// The relative IRIs are resolved against the base IRI by concatenation, without removing the dot segments.
+pub fn parse_turtle(document: &str, base_iri: Option<&str>) -> Result<Vec<Triple>, ParseError> {
+    let mut parser = TurtleParser {
+        chars: document.chars().collect(),
+        position: 0,
+        prefixes: HashMap::new(),
+        base_iri: base_iri.map(String::from),
+        triples: Vec::new(),
+        n_blank_node: 0,
+    };
+    parser.parse_document()?;
+    Ok(parser.triples)
+}
+
+struct TurtleParser {
+    chars: Vec<char>,
+    position: usize,
+    prefixes: HashMap<String, String>,
+    base_iri: Option<String>,
+    triples: Vec<Triple>,
+    /// Number of blank nodes without label, used to name them.
+    n_blank_node: usize,
+}
+
+impl TurtleParser {
+    fn parse_document(&mut self) -> Result<(), ParseError> {
+        loop {
+            self.skip_whitespace();
+            if self.peek().is_none() {
+                return Ok(());
+            }
+            self.parse_statement()?;
+        }
+    }
+
+    fn parse_statement(&mut self) -> Result<(), ParseError> {
+        if self.consume_str("@prefix") {
+            self.parse_prefix()?;
+            return self.expect('.');
+        }
+        if self.consume_str("@base") {
+            self.parse_base()?;
+            return self.expect('.');
+        }
+        if self.consume_keyword("PREFIX") {
+            return self.parse_prefix();
+        }
+        if self.consume_keyword("BASE") {
+            return self.parse_base();
+        }
+        self.parse_triples()?;
+        self.expect('.')
+    }
+
+    fn parse_prefix(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        let prefix = self.parse_name_until_colon()?;
+        self.skip_whitespace();
+        let namespace = self.parse_iri_ref()?;
+        self.prefixes.insert(prefix, namespace);
+        Ok(())
+    }
+
+    fn parse_base(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        self.base_iri = Some(self.parse_iri_ref()?);
+        Ok(())
+    }
+
+    fn parse_triples(&mut self) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('[') {
+            let subject = self.parse_blank_node_property_list()?;
+            self.skip_whitespace();
+            if !matches!(self.peek(), Some('.') | None) {
+                self.parse_predicate_object_list(&subject)?;
+            }
+            return Ok(());
+        }
+        let subject = match self.peek() {
+            Some('<') => Term::NamedNode(self.parse_iri_ref()?),
+            Some('_') => self.parse_blank_node_label()?,
+            Some('(') => self.parse_collection()?,
+            _ => Term::NamedNode(self.parse_prefixed_name()?),
+        };
+        self.parse_predicate_object_list(&subject)
+    }
+
+    fn parse_predicate_object_list(&mut self, subject: &Term) -> Result<(), ParseError> {
+        loop {
+            self.skip_whitespace();
+            let predicate = self.parse_verb()?;
+            self.parse_object_list(subject, &predicate)?;
+            self.skip_whitespace();
+            if self.peek() != Some(';') {
+                return Ok(());
+            }
+            while self.peek() == Some(';') {
+                self.position += 1;
+                self.skip_whitespace();
+            }
+            if matches!(self.peek(), Some('.') | Some(']') | None) {
+                return Ok(());
+            }
+        }
+    }
+
+    fn parse_object_list(&mut self, subject: &Term, predicate: &str) -> Result<(), ParseError> {
+        loop {
+            self.skip_whitespace();
+            let object = self.parse_object()?;
+            self.triples.push(Triple::new(
+                subject.clone(),
+                String::from(predicate),
+                object,
+            ));
+            self.skip_whitespace();
+            if self.peek() != Some(',') {
+                return Ok(());
+            }
+            self.position += 1;
+        }
+    }
+
+    fn parse_verb(&mut self) -> Result<String, ParseError> {
+        if self.peek() == Some('a') && self.peek_at(1).is_none_or(is_delimiter) {
+            self.position += 1;
+            return Ok(String::from(vocabulary::RDF_TYPE));
+        }
+        match self.peek() {
+            Some('<') => self.parse_iri_ref(),
+            _ => self.parse_prefixed_name(),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Term, ParseError> {
+        match self.peek() {
+            Some('<') => Ok(Term::NamedNode(self.parse_iri_ref()?)),
+            Some('_') => self.parse_blank_node_label(),
+            Some('[') => self.parse_blank_node_property_list(),
+            Some('(') => self.parse_collection(),
+            Some('"') | Some('\'') => self.parse_literal(),
+            Some(c) if c.is_ascii_digit() || c == '+' || c == '-' || c == '.' => {
+                self.parse_number()
+            }
+            Some(_) if self.consume_boolean("true") => Ok(a_literal("true", ValueType::Boolean)),
+            Some(_) if self.consume_boolean("false") => Ok(a_literal("false", ValueType::Boolean)),
+            Some(_) => Ok(Term::NamedNode(self.parse_prefixed_name()?)),
+            None => Err(self.error("expected an object")),
+        }
+    }
+
+    fn parse_blank_node_property_list(&mut self) -> Result<Term, ParseError> {
+        self.expect('[')?;
+        let subject = self.new_blank_node();
+        self.skip_whitespace();
+        if self.peek() != Some(']') {
+            self.parse_predicate_object_list(&subject)?;
+        }
+        self.expect(']')?;
+        Ok(subject)
+    }
+
+    fn parse_collection(&mut self) -> Result<Term, ParseError> {
+        self.expect('(')?;
+        let mut items: Vec<Term> = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(')') => {
+                    self.position += 1;
+                    break;
+                }
+                None => return Err(self.error("unterminated collection")),
+                _ => items.push(self.parse_object()?),
+            }
+        }
+        let mut list = Term::NamedNode(String::from(RDF_NIL));
+        for item in items.into_iter().rev() {
+            let node = self.new_blank_node();
+            self.triples
+                .push(Triple::new(node.clone(), String::from(RDF_FIRST), item));
+            self.triples
+                .push(Triple::new(node.clone(), String::from(RDF_REST), list));
+            list = node;
+        }
+        Ok(list)
+    }
+
+    fn parse_literal(&mut self) -> Result<Term, ParseError> {
+        let value = self.parse_string()?;
+        match self.peek() {
+            Some('@') => {
+                self.position += 1;
+                let start = self.position;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-')
+                {
+                    self.position += 1;
+                }
+                if start == self.position {
+                    return Err(self.error("expected a language tag"));
+                }
+                let language: String = self.chars[start..self.position].iter().collect();
+                Ok(a_literal(&value, ValueType::LangString(language)))
+            }
+            Some('^') => {
+                self.position += 1;
+                self.expect('^')?;
+                let datatype = match self.peek() {
+                    Some('<') => self.parse_iri_ref()?,
+                    _ => self.parse_prefixed_name()?,
+                };
+                Ok(a_literal(&value, ValueType::from_datatype(&datatype)))
+            }
+            _ => Ok(a_literal(&value, ValueType::String)),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        let quote = self.peek().ok_or_else(|| self.error("expected a string"))?;
+        let is_long = self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote);
+        self.position += if is_long { 3 } else { 1 };
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    if !is_long {
+                        self.position += 1;
+                        return Ok(value);
+                    }
+                    if self.peek_at(1) == Some(quote) && self.peek_at(2) == Some(quote) {
+                        // the quotes before the last three belong to the string
+                        while self.peek_at(3) == Some(quote) {
+                            value.push(quote);
+                            self.position += 1;
+                        }
+                        self.position += 3;
+                        return Ok(value);
+                    }
+                    value.push(c);
+                    self.position += 1;
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    value.push(self.parse_escape()?);
+                }
+                Some('\n') | Some('\r') if !is_long => {
+                    return Err(self.error("unescaped line break in a string"))
+                }
+                Some(c) => {
+                    value.push(c);
+                    self.position += 1;
+                }
+            }
+        }
+    }
+
+    /// Parse an escape sequence, the position being after the backslash.
+    fn parse_escape(&mut self) -> Result<char, ParseError> {
+        let c = self
+            .peek()
+            .ok_or_else(|| self.error("unterminated escape sequence"))?;
+        self.position += 1;
+        match c {
+            't' => Ok('\t'),
+            'b' => Ok('\u{8}'),
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            'f' => Ok('\u{c}'),
+            '"' | '\'' | '\\' => Ok(c),
+            'u' => self.parse_code_point(4),
+            'U' => self.parse_code_point(8),
+            _ => Err(self.error("invalid escape sequence")),
+        }
+    }
+
+    fn parse_code_point(&mut self, n_digit: usize) -> Result<char, ParseError> {
+        if self.position + n_digit > self.chars.len() {
+            return Err(self.error("invalid unicode escape sequence"));
+        }
+        let digits: String = self.chars[self.position..self.position + n_digit]
+            .iter()
+            .collect();
+        self.position += n_digit;
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error("invalid unicode escape sequence"))
+    }
+
+    fn parse_number(&mut self) -> Result<Term, ParseError> {
+        let start = self.position;
+        if matches!(self.peek(), Some('+') | Some('-')) {
+            self.position += 1;
+        }
+        let integer_digits = self.skip_digits();
+        let mut value_type = ValueType::Integer;
+        if self.peek() == Some('.') && self.peek_at(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+            self.skip_digits();
+            value_type = ValueType::Decimal;
+        } else if integer_digits == 0 {
+            return Err(self.error("invalid number"));
+        }
+        if matches!(self.peek(), Some('e') | Some('E')) {
+            self.position += 1;
+            if matches!(self.peek(), Some('+') | Some('-')) {
+                self.position += 1;
+            }
+            if self.skip_digits() == 0 {
+                return Err(self.error("invalid exponent"));
+            }
+            value_type = ValueType::Double;
+        }
+        let value: String = self.chars[start..self.position].iter().collect();
+        Ok(a_literal(&value, value_type))
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.position - start
+    }
+
+    fn parse_iri_ref(&mut self) -> Result<String, ParseError> {
+        self.expect('<')?;
+        let mut iri = String::new();
+        loop {
+            match self.peek() {
+                Some('>') => {
+                    self.position += 1;
+                    break;
+                }
+                Some('\\') => {
+                    self.position += 1;
+                    iri.push(match self.peek() {
+                        Some('u') | Some('U') => self.parse_escape()?,
+                        _ => return Err(self.error("invalid escape sequence in an IRI")),
+                    });
+                }
+                Some(c) if c.is_whitespace() || c == '<' || c == '"' => {
+                    return Err(self.error("invalid character in an IRI"))
+                }
+                Some(c) => {
+                    iri.push(c);
+                    self.position += 1;
+                }
+                None => return Err(self.error("unterminated IRI")),
+            }
+        }
+        Ok(self.resolve(iri))
+    }
+
+    /// Resolve a relative IRI against the base IRI.
+    fn resolve(&self, iri: String) -> String {
+        match &self.base_iri {
+            Some(base_iri) => resolve_iri(base_iri, iri),
+            None => iri,
+        }
+    }
+
+    fn parse_prefixed_name(&mut self) -> Result<String, ParseError> {
+        let prefix = self.parse_name_until_colon()?;
+        let namespace = self
+            .prefixes
+            .get(&prefix)
+            .ok_or_else(|| self.error("undefined prefix"))?
+            .clone();
+        let mut local_name = String::new();
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some(c) if "_~.-!$&'()*+,;=/?#@%".contains(c) => {
+                            local_name.push(c);
+                            self.position += 1;
+                        }
+                        _ => return Err(self.error("invalid escape sequence in a local name")),
+                    }
+                }
+                Some(c) if is_name_char(c) || c == ':' || c == '%' || c == '.' => {
+                    local_name.push(c);
+                    self.position += 1;
+                }
+                _ => break,
+            }
+        }
+        // a local name does not end with a dot, which ends the statement
+        while local_name.ends_with('.') && self.chars[self.position - 1] == '.' {
+            local_name.pop();
+            self.position -= 1;
+        }
+        Ok(format!("{}{}", namespace, local_name))
+    }
+
+    /// Parse the prefix of a prefixed name and the colon following it.
+    fn parse_name_until_colon(&mut self) -> Result<String, ParseError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| is_name_char(c) || c == '.') {
+            self.position += 1;
+        }
+        let prefix: String = self.chars[start..self.position].iter().collect();
+        if self.peek() != Some(':') || prefix.ends_with('.') {
+            self.position = start;
+            return Err(self.error("expected a prefixed name"));
+        }
+        self.position += 1;
+        Ok(prefix)
+    }
+
+    fn parse_blank_node_label(&mut self) -> Result<Term, ParseError> {
+        if !self.consume_str("_:") {
+            return Err(self.error("expected a blank node"));
+        }
+        let start = self.position;
+        while self.peek().is_some_and(|c| is_name_char(c) || c == '.') {
+            self.position += 1;
+        }
+        while self.position > start && self.chars[self.position - 1] == '.' {
+            self.position -= 1;
+        }
+        if start == self.position {
+            return Err(self.error("expected a blank node label"));
+        }
+        let label: String = self.chars[start..self.position].iter().collect();
+        Ok(Term::labelled_blank_node(&label))
+    }
+
+    fn new_blank_node(&mut self) -> Term {
+        self.n_blank_node += 1;
+        Term::anonymous_blank_node(self.n_blank_node)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == '#' {
+                while self.peek().is_some_and(|c| c != '\n') {
+                    self.position += 1;
+                }
+            } else if c.is_whitespace() {
+                self.position += 1;
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ParseError> {
+        self.skip_whitespace();
+        if self.peek() != Some(expected) {
+            return Err(self.error(match expected {
+                '.' => "expected '.'",
+                ']' => "expected ']'",
+                '[' => "expected '['",
+                '(' => "expected '('",
+                '<' => "expected an IRI",
+                '^' => "expected a datatype",
+                _ => "unexpected character",
+            }));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn consume_str(&mut self, expected: &str) -> bool {
+        let n_char = expected.chars().count();
+        let matches = self.position + n_char <= self.chars.len()
+            && self.chars[self.position..self.position + n_char]
+                .iter()
+                .copied()
+                .eq(expected.chars());
+        if matches {
+            self.position += n_char;
+        }
+        matches
+    }
+
+    /// Consume a case-insensitive keyword followed by a whitespace.
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let n_char = keyword.len();
+        let matches = self.position + n_char < self.chars.len()
+            && self.chars[self.position..self.position + n_char]
+                .iter()
+                .collect::<String>()
+                .eq_ignore_ascii_case(keyword)
+            && self.chars[self.position + n_char].is_whitespace();
+        if matches {
+            self.position += n_char;
+        }
+        matches
+    }
+
+    fn consume_boolean(&mut self, keyword: &str) -> bool {
+        let n_char = keyword.len();
+        let is_followed_by_delimiter = self
+            .chars
+            .get(self.position + n_char)
+            .is_none_or(|c| is_delimiter(*c));
+        is_followed_by_delimiter && self.consume_str(keyword)
+    }
+
+    fn error(&self, message: &'static str) -> ParseError {
+        let line = self.chars[..self.position.min(self.chars.len())]
+            .iter()
+            .filter(|c| **c == '\n')
+            .count()
+            + 1;
+        ParseError {
+            message,
+            line: Some(line),
+        }
+    }
+}
+
+fn a_literal(value: &str, value_type: ValueType) -> Term {
+    Term::Literal(Value {
+        value: String::from(value),
+        value_type,
+    })
+}
+
+/// Whether the character can be part of a prefix, a local name or a blank node label.
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-' || c == '\u{b7}'
+}
+
+/// Whether the character ends a keyword.
+fn is_delimiter(c: char) -> bool {
+    !is_name_char(c) && c != ':'
+}
+
+/// Resolve a relative IRI against a base IRI by concatenation, without removing the dot segments.
+pub(super) fn resolve_iri(base_iri: &str, iri: String) -> String {
+    if has_scheme(&iri) {
+        iri
+    } else if iri.is_empty() {
+        String::from(base_iri)
+    } else if iri.starts_with('#') {
+        format!("{}{}", base_iri.split('#').next().unwrap_or_default(), iri)
+    } else if iri.starts_with("//") {
+        let scheme = base_iri.split(':').next().unwrap_or_default();
+        format!("{}:{}", scheme, iri)
+    } else if iri.starts_with('/') {
+        let authority_end = base_iri
+            .find("://")
+            .and_then(|start| base_iri[start + 3..].find('/').map(|end| start + 3 + end))
+            .unwrap_or(base_iri.len());
+        format!("{}{}", &base_iri[..authority_end], iri)
+    } else {
+        let directory_end = base_iri.rfind('/').map_or(base_iri.len(), |i| i + 1);
+        format!("{}{}", &base_iri[..directory_end], iri)
+    }
+}
+
+fn has_scheme(iri: &str) -> bool {
+    match iri.split_once(':') {
+        Some((scheme, _)) => {
+            !scheme.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_turtle;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::turtle::to_turtle;
+    use crate::rdf::vocabulary;
+    use crate::tree::value::{Value, ValueType};
+
+    fn a_named_node(iri: &str) -> Term {
+        Term::NamedNode(String::from(iri))
+    }
+
+    fn a_literal(value: &str, value_type: ValueType) -> Term {
+        Term::Literal(Value {
+            value: String::from(value),
+            value_type,
+        })
+    }
+
+    #[test]
+    fn should_parse_the_serialized_turtle() {
+        let triples = vec![
+            Triple::new(
+                a_named_node("https://example.com/root"),
+                String::from(vocabulary::RDF_TYPE),
+                a_named_node(vocabulary::TREE_NODE_CLASS),
+            ),
+            Triple::new(
+                a_named_node("https://example.com/root"),
+                String::from(vocabulary::TREE_RELATION),
+                Term::BlankNode(String::from("relation0")),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from(vocabulary::TREE_VALUE),
+                a_literal("a \"quoted\"\nvalue", ValueType::String),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from("https://example.com/label"),
+                a_literal("chat", ValueType::LangString(String::from("fr"))),
+            ),
+            Triple::new(
+                Term::BlankNode(String::from("relation0")),
+                String::from("https://example.com/p"),
+                a_literal("5", ValueType::Int),
+            ),
+        ];
+
+        assert_eq!(parse_turtle(&to_turtle(&triples), None), Ok(triples));
+    }
+
+    #[test]
+    fn should_parse_the_abbreviations() {
+        let document = r#"
+            BASE <https://example.com/dataset/>
+            @prefix ex: <https://example.com/> .
+            # a comment
+            <root> a ex:Node, ex:Page ;
+                ex:count 5, -1.5, 1e3, true ;
+                ex:relation [ ex:value 'single' ; ex:next () ] ;
+                ex:list ( ex:a """long
+"string""" ) ;
+                .
+            <#fragment> ex:name ex:a.b .
+        "#;
+
+        let triples = parse_turtle(document, None).unwrap();
+
+        let root = a_named_node("https://example.com/dataset/root");
+        assert_eq!(triples.len(), 15);
+        assert_eq!(
+            triples[0],
+            Triple::new(
+                root.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                a_named_node("https://example.com/Node")
+            )
+        );
+        assert_eq!(triples[3].object, a_literal("-1.5", ValueType::Decimal));
+        assert_eq!(triples[4].object, a_literal("1e3", ValueType::Double));
+        assert_eq!(triples[5].object, a_literal("true", ValueType::Boolean));
+        assert!(triples.contains(&Triple::new(
+            Term::BlankNode(String::from("anonymous1")),
+            String::from("https://example.com/next"),
+            a_named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil"),
+        )));
+        assert!(triples
+            .iter()
+            .any(|triple| triple.object == a_literal("long\n\"string", ValueType::String)));
+        assert_eq!(
+            triples.last(),
+            Some(&Triple::new(
+                a_named_node("https://example.com/dataset/#fragment"),
+                String::from("https://example.com/name"),
+                a_named_node("https://example.com/a.b"),
+            ))
+        );
+    }
+
+    #[test]
+    fn should_keep_the_labelled_and_anonymous_blank_nodes_apart() {
+        let triples = parse_turtle("_:anonymous1 <https://example.com/p> [] .", None).unwrap();
+
+        assert_eq!(
+            triples,
+            vec![Triple::new(
+                Term::BlankNode(String::from("_anonymous1")),
+                String::from("https://example.com/p"),
+                Term::BlankNode(String::from("anonymous1")),
+            )]
+        );
+    }
+
+    #[test]
+    fn should_report_the_line_of_an_error() {
+        let error = parse_turtle(
+            "@prefix ex: <https://example.com/> .\nex:a ex:b ex:c\n",
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(error.message, "expected '.'");
+        assert_eq!(error.line, Some(3));
+        assert_eq!(
+            parse_turtle("ex:a ex:b ex:c .", None).unwrap_err().message,
+            "undefined prefix"
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! IRIs of the vocabularies used to describe a TREE document.
+
+pub const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+pub const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+
+pub const TREE_COLLECTION: &str = "https://w3id.org/tree#Collection";
+pub const TREE_NODE_CLASS: &str = "https://w3id.org/tree#Node";
+pub const TREE_RELATION_CLASS: &str = "https://w3id.org/tree#Relation";
+pub const TREE_VIEW: &str = "https://w3id.org/tree#view";
+pub const TREE_MEMBER: &str = "https://w3id.org/tree#member";
+pub const TREE_RELATION: &str = "https://w3id.org/tree#relation";
+pub const TREE_NODE: &str = "https://w3id.org/tree#node";
+pub const TREE_PATH: &str = "https://w3id.org/tree#path";
+pub const TREE_VALUE: &str = "https://w3id.org/tree#value";
+pub const TREE_REMAINING_ITEMS: &str = "https://w3id.org/tree#remainingItems";
+pub const TREE_SHAPE: &str = "https://w3id.org/tree#shape";
+
+pub const SH_NODE_SHAPE: &str = "http://www.w3.org/ns/shacl#NodeShape";
+pub const SH_PROPERTY: &str = "http://www.w3.org/ns/shacl#property";
+pub const SH_PATH: &str = "http://www.w3.org/ns/shacl#path";
+pub const SH_DATATYPE: &str = "http://www.w3.org/ns/shacl#datatype";
+pub const SH_NODE_KIND: &str = "http://www.w3.org/ns/shacl#nodeKind";
+pub const SH_IRI: &str = "http://www.w3.org/ns/shacl#IRI";
+pub const SH_MIN_COUNT: &str = "http://www.w3.org/ns/shacl#minCount";
+pub const SH_MAX_COUNT: &str = "http://www.w3.org/ns/shacl#maxCount";
+pub const TREE_SEARCH: &str = "https://w3id.org/tree#search";
+
+pub const HYDRA_IRI_TEMPLATE: &str = "http://www.w3.org/ns/hydra/core#IriTemplate";
+pub const HYDRA_IRI_TEMPLATE_MAPPING: &str = "http://www.w3.org/ns/hydra/core#IriTemplateMapping";
+pub const HYDRA_TEMPLATE: &str = "http://www.w3.org/ns/hydra/core#template";
+pub const HYDRA_VARIABLE_REPRESENTATION: &str =
+    "http://www.w3.org/ns/hydra/core#variableRepresentation";
+pub const HYDRA_BASIC_REPRESENTATION: &str = "http://www.w3.org/ns/hydra/core#BasicRepresentation";
+pub const HYDRA_MAPPING: &str = "http://www.w3.org/ns/hydra/core#mapping";
+pub const HYDRA_VARIABLE: &str = "http://www.w3.org/ns/hydra/core#variable";
+pub const HYDRA_PROPERTY: &str = "http://www.w3.org/ns/hydra/core#property";
+pub const HYDRA_REQUIRED: &str = "http://www.w3.org/ns/hydra/core#required";
+
+pub const LDES_EVENT_STREAM: &str = "https://w3id.org/ldes#EventStream";
+pub const LDES_TIMESTAMP_PATH: &str = "https://w3id.org/ldes#timestampPath";
+pub const LDES_VERSION_OF_PATH: &str = "https://w3id.org/ldes#versionOfPath";
+pub const LDES_RETENTION_POLICY: &str = "https://w3id.org/ldes#retentionPolicy";
+pub const LDES_DURATION_AGO_POLICY: &str = "https://w3id.org/ldes#DurationAgoPolicy";
+pub const LDES_LATEST_VERSION_SUBSET: &str = "https://w3id.org/ldes#LatestVersionSubset";
+pub const LDES_AMOUNT: &str = "https://w3id.org/ldes#amount";
+
+pub const GEO_WKT_LITERAL: &str = "http://www.opengis.net/ont/geosparql#wktLiteral";
+pub const GEOF_SF_WITHIN: &str = "http://www.opengis.net/def/function/geosparql/sfWithin";
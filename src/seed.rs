@@ -0,0 +1,42 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// A random number generator derived from a seed and a key (e.g. an url), so that the values
+/// drawn for a key are reproducible whatever the order in which the keys are visited.
+pub(crate) fn keyed_rng(seed: u64, key: &str) -> StdRng {
+    StdRng::seed_from_u64(fnv1a(seed, key.as_bytes()))
+}
+
+/// The [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash of the bytes mixed with a seed,
+/// unlike the hasher of the standard library it is stable across executions.
+pub(crate) fn fnv1a(seed: u64, bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in seed.to_le_bytes().iter().chain(bytes.iter()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::keyed_rng;
+    use rand::Rng;
+
+    #[test]
+    fn should_draw_the_same_values_for_the_same_seed_and_key() {
+        let a: Vec<u32> = (0..5).map(|_| keyed_rng(1, "a").gen()).collect();
+        let mut rng = keyed_rng(1, "a");
+        let first: u32 = rng.gen();
+
+        assert!(a.iter().all(|v| *v == first));
+        assert_ne!(
+            keyed_rng(1, "a").gen::<u64>(),
+            keyed_rng(2, "a").gen::<u64>()
+        );
+        assert_ne!(
+            keyed_rng(1, "a").gen::<u64>(),
+            keyed_rng(1, "b").gen::<u64>()
+        );
+    }
+}
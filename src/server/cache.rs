@@ -0,0 +1,174 @@
+use super::http::{Request, Response};
+use crate::seed::fnv1a;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use std::vec::Vec;
+
+/// Caching of the pages served by the [server](`super::Server`).
+/// Every page is immutable except the latest fragment, which can still receive members.
+#[derive(Clone, Debug)]
+pub struct CacheConfig {
+    /// Url of the latest fragment, every page being immutable when it is not defined.
+    pub latest_fragment: Option<String>,
+    /// How long the latest fragment can be cached.
+    pub latest_max_age: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            latest_fragment: None,
+            latest_max_age: Duration::from_secs(60),
+        }
+    }
+}
+
+/// The caching headers of the pages of a document.
+pub(super) struct CachePolicy {
+    latest_fragment: Option<String>,
+    latest_max_age: Duration,
+    /// `Last-Modified` header, the time at which the document started to be served.
+    last_modified: String,
+}
+
+impl CachePolicy {
+    pub fn new(config: CacheConfig, now: DateTime<Utc>) -> Self {
+        CachePolicy {
+            latest_fragment: config.latest_fragment,
+            latest_max_age: config.latest_max_age,
+            last_modified: now.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+        }
+    }
+
+    /// Add the caching headers to a successful response and replace it by a
+    /// `304 Not Modified` when the request has a matching `If-None-Match` header.
+    pub fn apply(&self, request: &Request, node_url: &str, response: Response) -> Response {
+        if response.status != 200 {
+            return response;
+        }
+        let entity_tag = format!("\"{:016x}\"", fnv1a(0, &response.body));
+        let cache_control = if self.latest_fragment.as_deref() == Some(node_url) {
+            format!("public, max-age={}", self.latest_max_age.as_secs())
+        } else {
+            String::from("public, max-age=31536000, immutable")
+        };
+        let is_not_modified = request
+            .header("if-none-match")
+            .is_some_and(|v| matches_entity_tag(v, &entity_tag));
+
+        let mut response = if is_not_modified {
+            let mut not_modified = Response::new(304, Vec::new());
+            not_modified.headers = response.headers;
+            not_modified
+        } else {
+            response
+        };
+        response = response
+            .with_header("ETag", &entity_tag)
+            .with_header("Last-Modified", &self.last_modified)
+            .with_header("Cache-Control", &cache_control);
+        response
+    }
+}
+
+/// Whether an `If-None-Match` header matches the entity tag, following the
+/// [weak comparison](https://www.rfc-editor.org/rfc/rfc9110#name-if-none-match).
+fn matches_entity_tag(if_none_match: &str, entity_tag: &str) -> bool {
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == entity_tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{matches_entity_tag, CacheConfig, CachePolicy};
+    use crate::server::http::{Request, Response};
+    use chrono::{TimeZone, Utc};
+    use std::time::Duration;
+
+    fn a_policy() -> CachePolicy {
+        CachePolicy::new(
+            CacheConfig {
+                latest_fragment: Some(String::from("http://localhost/latest")),
+                latest_max_age: Duration::from_secs(5),
+            },
+            Utc.with_ymd_and_hms(2022, 1, 2, 3, 4, 5).unwrap(),
+        )
+    }
+
+    fn a_request(if_none_match: Option<&str>) -> Request {
+        Request {
+            method: String::from("GET"),
+            target: String::from("/root"),
+            headers: if_none_match
+                .map(|v| vec![(String::from("if-none-match"), String::from(v))])
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn should_add_the_caching_headers() {
+        let policy = a_policy();
+
+        let closed = policy.apply(
+            &a_request(None),
+            "http://localhost/root",
+            Response::new(200, b"body".to_vec()),
+        );
+        let latest = policy.apply(
+            &a_request(None),
+            "http://localhost/latest",
+            Response::new(200, b"body".to_vec()),
+        );
+
+        assert_eq!(
+            closed.header("Cache-Control"),
+            Some("public, max-age=31536000, immutable")
+        );
+        assert_eq!(latest.header("Cache-Control"), Some("public, max-age=5"));
+        assert_eq!(
+            closed.header("Last-Modified"),
+            Some("Sun, 02 Jan 2022 03:04:05 GMT")
+        );
+        assert_eq!(closed.header("ETag"), latest.header("ETag"));
+        assert!(closed.header("ETag").unwrap().starts_with('"'));
+    }
+
+    #[test]
+    fn should_respond_not_modified_when_the_entity_tag_matches() {
+        let policy = a_policy();
+        let entity_tag = policy
+            .apply(
+                &a_request(None),
+                "http://localhost/root",
+                Response::new(200, b"body".to_vec()),
+            )
+            .header("ETag")
+            .unwrap()
+            .to_string();
+
+        let not_modified = policy.apply(
+            &a_request(Some(&entity_tag)),
+            "http://localhost/root",
+            Response::new(200, b"body".to_vec()),
+        );
+        let modified = policy.apply(
+            &a_request(Some(&entity_tag)),
+            "http://localhost/root",
+            Response::new(200, b"other body".to_vec()),
+        );
+
+        assert_eq!(not_modified.status, 304);
+        assert!(not_modified.body.is_empty());
+        assert_eq!(not_modified.header("ETag"), Some(entity_tag.as_str()));
+        assert_eq!(modified.status, 200);
+    }
+
+    #[test]
+    fn should_match_a_list_of_weak_entity_tags() {
+        assert!(matches_entity_tag("\"a\", W/\"b\"", "\"b\""));
+        assert!(matches_entity_tag("*", "\"b\""));
+        assert!(!matches_entity_tag("\"a\"", "\"b\""));
+    }
+}
@@ -0,0 +1,288 @@
+use crate::seed::keyed_rng;
+use rand::Rng;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+
+/// Faults injected by the [server](`super::Server`) to benchmark the robustness of clients.
+/// The faults of a node url depend only on the seed and the url, making them reproducible.
+#[derive(Clone, Debug, Default)]
+pub struct FaultConfig {
+    /// Seed of the faults.
+    pub seed: u64,
+    /// Latency added to every response.
+    pub latency: Option<Latency>,
+    /// Error statuses returned for a fraction of the node urls.
+    pub failure_rates: Vec<FailureRate>,
+    /// Fraction of the node urls whose body is truncated.
+    pub truncation_rate: f64,
+    /// Maximum number of requests accepted in a time window.
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// Distribution of the latency of a response.
+#[derive(Clone, Copy, Debug)]
+pub enum Latency {
+    Constant(Duration),
+    /// Uniform between a lower and an upper bound.
+    Uniform(Duration, Duration),
+    /// Exponential with a mean.
+    Exponential(Duration),
+    /// Normal with a mean and a standard deviation, a negative latency being zero.
+    Normal(Duration, Duration),
+}
+
+/// A fraction of the node urls always responding with an error status (e.g. 404, 500 or 429).
+#[derive(Clone, Copy, Debug)]
+pub struct FailureRate {
+    pub status: u16,
+    pub fraction: f64,
+}
+
+/// Maximum number of requests accepted in a fixed time window,
+/// the exceeding requests receive a `429 Too Many Requests`.
+#[derive(Clone, Copy, Debug)]
+pub struct RateLimit {
+    pub max_requests: usize,
+    pub window: Duration,
+}
+
+impl FaultConfig {
+    /// The error status of the node url, the urls being spread following the failure rates.
+    pub fn failure_status(&self, node_url: &str) -> Option<u16> {
+        let draw: f64 = keyed_rng(self.seed, &format!("failure {}", node_url)).gen();
+        let mut cumulated_fraction = 0.0;
+        for failure_rate in self.failure_rates.iter() {
+            cumulated_fraction += failure_rate.fraction;
+            if draw < cumulated_fraction {
+                return Some(failure_rate.status);
+            }
+        }
+        None
+    }
+
+    /// Whether the body of the node url is truncated.
+    pub fn is_truncated(&self, node_url: &str) -> bool {
+        let draw: f64 = keyed_rng(self.seed, &format!("truncation {}", node_url)).gen();
+        draw < self.truncation_rate
+    }
+
+    /// The latency of the `nth_request` to the node url.
+    pub fn latency(&self, node_url: &str, nth_request: u64) -> Duration {
+        let latency = match self.latency {
+            Some(v) => v,
+            None => return Duration::ZERO,
+        };
+        let mut rng = keyed_rng(self.seed, &format!("latency {} {}", nth_request, node_url));
+        let seconds = match latency {
+            Latency::Constant(v) => v.as_secs_f64(),
+            Latency::Uniform(lower, upper) if lower < upper => {
+                rng.gen_range(lower.as_secs_f64()..upper.as_secs_f64())
+            }
+            Latency::Uniform(lower, _) => lower.as_secs_f64(),
+            Latency::Exponential(mean) => {
+                let draw: f64 = rng.gen();
+                -mean.as_secs_f64() * (1.0 - draw).ln()
+            }
+            Latency::Normal(mean, standard_deviation) => {
+                // Box-Muller transform
+                let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+                let standard_normal =
+                    (-2.0 * (1.0 - u1).ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                mean.as_secs_f64() + standard_deviation.as_secs_f64() * standard_normal
+            }
+        };
+        Duration::from_secs_f64(seconds.max(0.0))
+    }
+}
+
+/// Maximum number of node urls whose requests are counted, so that the counts of a long-running
+/// server requested with many distinct urls stay bounded.
+const MAX_COUNTED_URLS: usize = 100_000;
+
+/// The state of the faults shared by the connections of the server.
+pub(super) struct FaultInjector {
+    pub config: FaultConfig,
+    /// Number of requests received by node url, at most [`MAX_COUNTED_URLS`] urls.
+    request_counts: Mutex<HashMap<String, u64>>,
+    /// Start of the current window of the rate limit and the number of requests in it.
+    window: Mutex<(Instant, usize)>,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultConfig) -> Self {
+        FaultInjector {
+            config,
+            request_counts: Mutex::new(HashMap::new()),
+            window: Mutex::new((Instant::now(), 0)),
+        }
+    }
+
+    /// Count a request to the node url and return its rank among the requests to the url.
+    /// When a new url would exceed [`MAX_COUNTED_URLS`] every count starts again from zero.
+    pub fn count_request(&self, node_url: &str) -> u64 {
+        let mut request_counts = self
+            .request_counts
+            .lock()
+            .expect("the request counts should not be poisoned");
+        if request_counts.len() >= MAX_COUNTED_URLS && !request_counts.contains_key(node_url) {
+            request_counts.clear();
+        }
+        let count = request_counts.entry(String::from(node_url)).or_insert(0);
+        *count += 1;
+        *count - 1
+    }
+
+    /// Count a request in the rate limit and return the time until the end of the window
+    /// when the request exceeds the limit.
+    pub fn throttle(&self, now: Instant) -> Option<Duration> {
+        let rate_limit = self.config.rate_limit?;
+        let mut window = self
+            .window
+            .lock()
+            .expect("the rate limit window should not be poisoned");
+        if now.duration_since(window.0) >= rate_limit.window {
+            *window = (now, 0);
+        }
+        window.1 += 1;
+        if window.1 > rate_limit.max_requests {
+            Some(rate_limit.window - now.duration_since(window.0))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FailureRate, FaultConfig, FaultInjector, Latency, RateLimit, MAX_COUNTED_URLS};
+    use std::time::{Duration, Instant};
+
+    fn urls() -> Vec<String> {
+        (0..2000)
+            .map(|i| format!("http://localhost/{}", i))
+            .collect()
+    }
+
+    #[test]
+    fn should_fail_a_reproducible_fraction_of_the_urls() {
+        let config = FaultConfig {
+            seed: 42,
+            failure_rates: vec![
+                FailureRate {
+                    status: 404,
+                    fraction: 0.1,
+                },
+                FailureRate {
+                    status: 500,
+                    fraction: 0.2,
+                },
+            ],
+            ..Default::default()
+        };
+
+        let statuses: Vec<Option<u16>> = urls()
+            .iter()
+            .map(|url| config.failure_status(url))
+            .collect();
+        let count = |status: u16| statuses.iter().filter(|v| **v == Some(status)).count();
+
+        assert!((150..250).contains(&count(404)));
+        assert!((320..480).contains(&count(500)));
+        let same_statuses: Vec<Option<u16>> = urls()
+            .iter()
+            .map(|url| config.failure_status(url))
+            .collect();
+        assert_eq!(statuses, same_statuses);
+    }
+
+    #[test]
+    fn should_truncate_a_fraction_of_the_urls() {
+        let config = FaultConfig {
+            seed: 1,
+            truncation_rate: 0.5,
+            ..Default::default()
+        };
+
+        let n_truncated = urls().iter().filter(|url| config.is_truncated(url)).count();
+
+        assert!((850..1150).contains(&n_truncated));
+        assert!(!FaultConfig::default().is_truncated("http://localhost/0"));
+    }
+
+    #[test]
+    fn should_draw_a_reproducible_latency_in_the_distribution() {
+        let config = FaultConfig {
+            seed: 7,
+            latency: Some(Latency::Uniform(
+                Duration::from_millis(10),
+                Duration::from_millis(20),
+            )),
+            ..Default::default()
+        };
+
+        for i in 0..100 {
+            let latency = config.latency("http://localhost/root", i);
+            assert!(latency >= Duration::from_millis(10) && latency < Duration::from_millis(20));
+            assert_eq!(latency, config.latency("http://localhost/root", i));
+        }
+        assert_eq!(
+            FaultConfig::default().latency("http://localhost/root", 0),
+            Duration::ZERO
+        );
+        let normal = FaultConfig {
+            latency: Some(Latency::Normal(Duration::ZERO, Duration::from_secs(1))),
+            ..Default::default()
+        };
+        assert!((0..100).all(|i| normal.latency("http://localhost/root", i) >= Duration::ZERO));
+    }
+
+    #[test]
+    fn should_throttle_the_requests_exceeding_the_rate_limit() {
+        let injector = FaultInjector::new(FaultConfig {
+            rate_limit: Some(RateLimit {
+                max_requests: 2,
+                window: Duration::from_secs(10),
+            }),
+            ..Default::default()
+        });
+        let now = Instant::now();
+
+        assert!(injector.throttle(now).is_none());
+        assert!(injector.throttle(now).is_none());
+        assert!(injector.throttle(now).is_some());
+        assert!(injector.throttle(now + Duration::from_secs(10)).is_none());
+    }
+
+    #[test]
+    fn should_count_the_requests_by_url() {
+        let injector = FaultInjector::new(FaultConfig::default());
+
+        assert_eq!(injector.count_request("http://localhost/a"), 0);
+        assert_eq!(injector.count_request("http://localhost/a"), 1);
+        assert_eq!(injector.count_request("http://localhost/b"), 0);
+    }
+
+    #[test]
+    fn should_bound_the_number_of_counted_urls() {
+        let injector = FaultInjector::new(FaultConfig::default());
+
+        injector.count_request("http://localhost/a");
+        for i in 1..MAX_COUNTED_URLS {
+            injector.count_request(&format!("http://localhost/{}", i));
+        }
+        assert_eq!(injector.count_request("http://localhost/a"), 1);
+
+        assert_eq!(
+            injector
+                .request_counts
+                .lock()
+                .expect("the request counts should not be poisoned")
+                .len(),
+            MAX_COUNTED_URLS
+        );
+        assert_eq!(injector.count_request("http://localhost/b"), 0);
+        assert_eq!(injector.count_request("http://localhost/a"), 0);
+    }
+}
@@ -0,0 +1,241 @@
+use crate::rdf::format::RdfFormat;
+use std::io::{self, BufRead, Write};
+use std::vec::Vec;
+
+/// A HTTP/1.1 request, without its body.
+pub(super) struct Request {
+    /// Method of the request.
+    pub method: String,
+    /// Path and query of the requested resource.
+    pub target: String,
+    /// Headers of the request, the names being in lower case.
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Value of a header, `name` being in lower case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A HTTP/1.1 response.
+pub(super) struct Response {
+    /// Status code.
+    pub status: u16,
+    /// Headers of the response.
+    pub headers: Vec<(String, String)>,
+    /// Body of the response.
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, body: Vec<u8>) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Value of a header of the response, `name` being in the case it was set.
+    #[cfg(test)]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header_name, _)| header_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Write the response, without the body when `head_only`, and close the connection.
+    pub fn write_to(&self, stream: &mut impl Write, head_only: bool) -> io::Result<()> {
+        let body_length = if head_only { 0 } else { self.body.len() };
+        self.write_part_to(stream, body_length)
+    }
+
+    /// Write the response announcing the whole body but only sending its first half,
+    /// like a connection interrupted during the transfer.
+    pub fn write_truncated_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        self.write_part_to(stream, self.body.len() / 2)
+    }
+
+    fn write_part_to(&self, stream: &mut impl Write, body_length: usize) -> io::Result<()> {
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\n",
+            self.status,
+            reason_phrase(self.status)
+        );
+        for (name, value) in self.headers.iter() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body[..body_length])?;
+        stream.flush()
+    }
+}
+
+/// Read the request line and the headers of a request.
+/// Return `None` if the connection is closed before a complete request.
+pub(super) fn read_request(stream: &mut impl BufRead) -> io::Result<Option<Request>> {
+    let mut request_line = String::new();
+    if stream.read_line(&mut request_line)? == 0 {
+        return Ok(None);
+    }
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (String::from(method), String::from(target)),
+        _ => return Ok(None),
+    };
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_lowercase(), String::from(value.trim())));
+        }
+    }
+    Ok(Some(Request {
+        method,
+        target,
+        headers,
+    }))
+}
+
+/// Select the [`RdfFormat`] preferred by the `Accept` header of a request,
+/// following the [quality values](https://www.rfc-editor.org/rfc/rfc9110#name-accept).
+/// A request without an `Accept` header receives Turtle and `None` is returned
+/// when no format is acceptable.
+pub(super) fn negotiate(accept: Option<&str>) -> Option<RdfFormat> {
+    let accept = match accept {
+        Some(v) => v,
+        None => return Some(RdfFormat::Turtle),
+    };
+    let media_ranges: Vec<(&str, f32)> = accept
+        .split(',')
+        .filter_map(|media_range| {
+            let mut parameters = media_range.split(';');
+            let media_type = parameters.next()?.trim();
+            let quality = parameters
+                .filter_map(|parameter| parameter.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            Some((media_type, quality))
+        })
+        .collect();
+
+    let mut best: Option<(RdfFormat, f32)> = None;
+    for format in RdfFormat::ALL {
+        let quality = quality_of(format, &media_ranges);
+        if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+            best = Some((format, quality));
+        }
+    }
+    best.map(|(format, _)| format)
+}
+
+/// Quality of the most specific media range matching the format.
+fn quality_of(format: RdfFormat, media_ranges: &[(&str, f32)]) -> f32 {
+    let media_type = format.media_type();
+    let main_type = media_type.split('/').next().unwrap_or_default();
+    let mut best: Option<(u8, f32)> = None;
+    for (media_range, quality) in media_ranges {
+        let specificity = if media_range.eq_ignore_ascii_case(media_type)
+            || (format == RdfFormat::JsonLd && media_range.eq_ignore_ascii_case("application/json"))
+        {
+            3
+        } else if media_range.eq_ignore_ascii_case(&format!("{}/*", main_type)) {
+            2
+        } else if *media_range == "*/*" {
+            1
+        } else {
+            continue;
+        };
+        if best.is_none_or(|(best_specificity, _)| specificity > best_specificity) {
+            best = Some((specificity, *quality));
+        }
+    }
+    best.map_or(0.0, |(_, quality)| quality)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        304 => "Not Modified",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{negotiate, read_request};
+    use crate::rdf::format::RdfFormat;
+    use std::io::BufReader;
+
+    #[test]
+    fn should_read_the_request_line_and_the_headers() {
+        let mut stream = BufReader::new(
+            "GET /root?a=1 HTTP/1.1\r\nHost: localhost\r\nAccept: text/turtle\r\n\r\n".as_bytes(),
+        );
+
+        let request = read_request(&mut stream).unwrap().unwrap();
+
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.target, "/root?a=1");
+        assert_eq!(request.header("accept"), Some("text/turtle"));
+        assert_eq!(request.header("host"), Some("localhost"));
+    }
+
+    #[test]
+    fn should_not_return_an_incomplete_request() {
+        let mut stream = BufReader::new("GET /root HTTP/1.1\r\nHost: localhost\r\n".as_bytes());
+
+        assert!(read_request(&mut stream).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_negotiate_the_format_following_the_quality_values() {
+        assert_eq!(negotiate(None), Some(RdfFormat::Turtle));
+        assert_eq!(negotiate(Some("*/*")), Some(RdfFormat::Turtle));
+        assert_eq!(
+            negotiate(Some("application/ld+json")),
+            Some(RdfFormat::JsonLd)
+        );
+        assert_eq!(
+            negotiate(Some("text/turtle;q=0.5, application/n-triples")),
+            Some(RdfFormat::NTriples)
+        );
+        assert_eq!(
+            negotiate(Some("application/*;q=0.9, text/turtle;q=0.1")),
+            Some(RdfFormat::JsonLd)
+        );
+        assert_eq!(
+            negotiate(Some("*/*;q=0.5, text/turtle;q=0")),
+            Some(RdfFormat::JsonLd)
+        );
+        assert_eq!(negotiate(Some("text/html")), None);
+    }
+}
@@ -0,0 +1,419 @@
+pub mod cache;
+pub mod fault;
+mod http;
+pub mod page_source;
+
+use self::cache::{CacheConfig, CachePolicy};
+use self::fault::{FaultConfig, FaultInjector};
+use self::http::{negotiate, read_request, Request, Response};
+use self::page_source::PageSource;
+use crate::generate_tree_document;
+use crate::generator_argument::Args;
+use crate::lazy_tree::LazyTreeGenerator;
+use chrono::Utc;
+use std::fmt::{self, Debug};
+use std::io::{self, BufReader};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// A HTTP server hosting the pages of a [`PageSource`] at the path of their node url,
+/// in Turtle, JSON-LD or N-Triples following the `Accept` header of the requests.
+pub struct Server {
+    listener: TcpListener,
+    config: ServerConfig,
+}
+
+/// Configuration of a [`Server`].
+#[derive(Clone, Debug, Default)]
+pub struct ServerConfig {
+    /// Faults injected in the responses.
+    pub faults: FaultConfig,
+    /// Caching of the pages.
+    pub cache: CacheConfig,
+}
+
+/// Error of the server.
+#[derive(Debug)]
+pub enum ServerError {
+    /// The address cannot be bound or a connection cannot be accepted.
+    Io(io::Error),
+    /// The TREE document cannot be generated.
+    Generation(&'static str),
+}
+
+impl Server {
+    /// Bind the server to an address, the port `0` selecting a free port.
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            config: ServerConfig::default(),
+        })
+    }
+
+    /// Set the configuration of the server.
+    pub fn with_config(mut self, config: ServerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Inject faults in the responses of the server.
+    pub fn with_faults(mut self, faults: FaultConfig) -> Self {
+        self.config.faults = faults;
+        self
+    }
+
+    /// Set the caching of the pages.
+    pub fn with_cache(mut self, cache: CacheConfig) -> Self {
+        self.config.cache = cache;
+        self
+    }
+
+    /// The url of the server without the trailling "/", e.g. `http://127.0.0.1:8080`.
+    pub fn base_url(&self) -> io::Result<String> {
+        Ok(format!("http://{}", self.listener.local_addr()?))
+    }
+
+    /// Serve the pages until the listener fails, each connection being handled in its thread.
+    pub fn serve(self, source: impl PageSource + Send + Sync + 'static) -> io::Result<()> {
+        let base_url = Arc::new(self.base_url()?);
+        let cache_policy = Arc::new(CachePolicy::new(self.config.cache, Utc::now()));
+        let document = Arc::new(source);
+        let fault_injector = Arc::new(FaultInjector::new(self.config.faults));
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let document = Arc::clone(&document);
+            let base_url = Arc::clone(&base_url);
+            let fault_injector = Arc::clone(&fault_injector);
+            let cache_policy = Arc::clone(&cache_policy);
+            thread::spawn(move || {
+                handle_connection(
+                    stream,
+                    &*document,
+                    &base_url,
+                    &fault_injector,
+                    &cache_policy,
+                )
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Generate the TREE document at the address of the server and serve it with the configuration,
+/// the base url of the arguments being replaced by the url of the server.
+pub fn serve<T: num::ToPrimitive + Debug>(
+    args: &mut Args<T>,
+    address: impl ToSocketAddrs,
+    config: ServerConfig,
+) -> Result<(), ServerError> {
+    let server = Server::bind(address)?.with_config(config);
+    args.base_url = server.base_url()?;
+    let document = generate_tree_document(args).map_err(ServerError::Generation)?;
+    Ok(server.serve(document)?)
+}
+
+/// Serve the nodes of a lazily generated TREE document, each node being generated when it is requested,
+/// the base url of the arguments being replaced by the url of the server.
+/// The relations of the arguments should be [lazy](`crate::generator_argument::relation_argument::RelationGeneratorArg::Lazy`).
+pub fn serve_lazily<T: num::ToPrimitive + Debug + 'static>(
+    mut args: Args<T>,
+    address: impl ToSocketAddrs,
+    config: ServerConfig,
+) -> Result<(), ServerError> {
+    let server = Server::bind(address)?.with_config(config);
+    args.base_url = server.base_url()?;
+    let generator = LazyTreeGenerator::new(args).map_err(ServerError::Generation)?;
+    Ok(server.serve(generator)?)
+}
+
+fn handle_connection(
+    stream: TcpStream,
+    document: &dyn PageSource,
+    base_url: &str,
+    fault_injector: &FaultInjector,
+    cache_policy: &CachePolicy,
+) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let request = match read_request(&mut reader)? {
+        Some(v) => v,
+        None => return Ok(()),
+    };
+    let mut stream = stream;
+    let head_only = request.method == "HEAD";
+    let node_url = format!("{}{}", base_url, request.target);
+    let faults = &fault_injector.config;
+
+    if let Some(retry_after) = fault_injector.throttle(Instant::now()) {
+        return Response::new(429, Vec::new())
+            .with_header("Retry-After", &retry_after.as_secs().max(1).to_string())
+            .write_to(&mut stream, head_only);
+    }
+    thread::sleep(faults.latency(&node_url, fault_injector.count_request(&node_url)));
+    if let Some(status) = faults.failure_status(&node_url) {
+        return Response::new(status, Vec::new()).write_to(&mut stream, head_only);
+    }
+
+    let response = cache_policy.apply(&request, &node_url, respond(&request, document, base_url));
+    if response.status == 200 && !head_only && faults.is_truncated(&node_url) {
+        response.write_truncated_to(&mut stream)
+    } else {
+        response.write_to(&mut stream, head_only)
+    }
+}
+
+/// Build the response to a request for the page of a node.
+fn respond(request: &Request, document: &dyn PageSource, base_url: &str) -> Response {
+    if request.method != "GET" && request.method != "HEAD" {
+        return Response::new(405, Vec::new()).with_header("Allow", "GET, HEAD");
+    }
+    let node_url = format!("{}{}", base_url, request.target);
+    let page = match document.page(&node_url) {
+        Ok(Some(v)) => v,
+        Ok(None) => return Response::new(404, Vec::new()),
+        Err(_) => return Response::new(500, Vec::new()),
+    };
+    let format = match negotiate(request.header("accept")) {
+        Some(v) => v,
+        None => return Response::new(406, Vec::new()).with_header("Vary", "Accept"),
+    };
+    Response::new(200, format.serialize(&page).into_bytes())
+        .with_header("Content-Type", format.media_type())
+        .with_header("Vary", "Accept")
+}
+
+impl From<io::Error> for ServerError {
+    fn from(error: io::Error) -> Self {
+        ServerError::Io(error)
+    }
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::Io(error) => write!(f, "{}", error),
+            ServerError::Generation(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fault::{FailureRate, FaultConfig};
+    use super::http::Request;
+    use super::{respond, Server};
+    use crate::generator_argument::lazy_tree_argument::LazyTreeArg;
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::generator_argument::relation_argument::{RelationGeneratorArg, RelationTemplate};
+    use crate::generator_argument::Args;
+    use crate::lazy_tree::LazyTreeGenerator;
+    use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+    use crate::tree::collection::Collection;
+    use crate::tree::node::Node;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::ValueType;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn get(address: &str, target: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", target).as_bytes())
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    fn a_document(base_url: &str) -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                format!("{}/collection", base_url),
+                format!("{}/root", base_url),
+                Vec::new(),
+                None,
+            ),
+            vec![Node::new(
+                Vec::new(),
+                format!("{}/root", base_url),
+                Vec::new(),
+            )],
+            None,
+            None,
+        )
+    }
+
+    fn a_request(method: &str, target: &str, accept: Option<&str>) -> Request {
+        Request {
+            method: String::from(method),
+            target: String::from(target),
+            headers: accept
+                .map(|v| vec![(String::from("accept"), String::from(v))])
+                .unwrap_or_default(),
+        }
+    }
+
+    #[test]
+    fn should_respond_with_the_negotiated_format() {
+        let document = a_document("http://localhost");
+
+        let response = respond(
+            &a_request("GET", "/root", Some("application/n-triples")),
+            &document,
+            "http://localhost",
+        );
+
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.header("Content-Type"),
+            Some("application/n-triples")
+        );
+        let body = String::from_utf8(response.body).unwrap();
+        assert!(body.contains(
+            "<http://localhost/collection> <https://w3id.org/tree#view> <http://localhost/root> ."
+        ));
+    }
+
+    #[test]
+    fn should_respond_with_an_error_status() {
+        let document = a_document("http://localhost");
+
+        let not_found = respond(
+            &a_request("GET", "/unknown", None),
+            &document,
+            "http://localhost",
+        );
+        let not_acceptable = respond(
+            &a_request("GET", "/root", Some("text/html")),
+            &document,
+            "http://localhost",
+        );
+        let not_allowed = respond(
+            &a_request("POST", "/root", None),
+            &document,
+            "http://localhost",
+        );
+
+        assert_eq!(not_found.status, 404);
+        assert_eq!(not_acceptable.status, 406);
+        assert_eq!(not_allowed.status, 405);
+    }
+
+    #[test]
+    fn should_serve_the_document_over_http() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let base_url = server.base_url().unwrap();
+        let address = base_url.trim_start_matches("http://").to_string();
+        let document = a_document(&base_url);
+        thread::spawn(move || server.serve(document));
+
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream
+            .write_all(
+                b"GET /root HTTP/1.1\r\nHost: localhost\r\nAccept: application/ld+json\r\n\r\n",
+            )
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Type: application/ld+json\r\n"));
+        assert!(response.contains(&format!("\"@id\":\"{}/root\"", base_url)));
+        assert!(response.contains("Cache-Control: "));
+
+        let entity_tag = response
+            .lines()
+            .find_map(|line| line.strip_prefix("ETag: "))
+            .unwrap();
+        let mut stream = TcpStream::connect(&address).unwrap();
+        stream
+            .write_all(
+                format!(
+                    "GET /root HTTP/1.1\r\nAccept: application/ld+json\r\nIf-None-Match: {}\r\n\r\n",
+                    entity_tag
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 304 Not Modified\r\n"));
+    }
+
+    #[test]
+    fn should_serve_the_nodes_generated_lazily() {
+        let server = Server::bind("127.0.0.1:0").unwrap();
+        let base_url = server.base_url().unwrap();
+        let address = base_url.trim_start_matches("http://").to_string();
+        let generator = LazyTreeGenerator::new(Args {
+            relation: RelationGeneratorArg::Lazy(LazyTreeArg {
+                template: RelationTemplate {
+                    path: String::from("ex:p"),
+                    relation_type: RelationOperator::LessThanRelation,
+                },
+                range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                value_type: ValueType::Int,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
+                fan_out: Box::new(RandomBoundedNumberRange::new(2, 2)),
+                max_depth: 1,
+                seed: 5,
+            }),
+            members: None,
+            search_form: None,
+            topology: None,
+            base_url: base_url.clone(),
+        })
+        .unwrap();
+        thread::spawn(move || server.serve(generator));
+
+        assert!(get(&address, "/root/1").starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(get(&address, "/root/2").starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[test]
+    fn should_inject_the_faults_in_the_responses() {
+        let faults = FaultConfig {
+            seed: 3,
+            failure_rates: vec![FailureRate {
+                status: 500,
+                fraction: 1.0,
+            }],
+            ..Default::default()
+        };
+        let server = Server::bind("127.0.0.1:0").unwrap().with_faults(faults);
+        let base_url = server.base_url().unwrap();
+        let address = base_url.trim_start_matches("http://").to_string();
+        let document = a_document(&base_url);
+        thread::spawn(move || server.serve(document));
+
+        assert!(get(&address, "/root").starts_with("HTTP/1.1 500 Internal Server Error\r\n"));
+    }
+
+    #[test]
+    fn should_truncate_the_body() {
+        let faults = FaultConfig {
+            truncation_rate: 1.0,
+            ..Default::default()
+        };
+        let server = Server::bind("127.0.0.1:0").unwrap().with_faults(faults);
+        let base_url = server.base_url().unwrap();
+        let address = base_url.trim_start_matches("http://").to_string();
+        let document = a_document(&base_url);
+        thread::spawn(move || server.serve(document));
+
+        let response = get(&address, "/root");
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let content_length: usize = head
+            .lines()
+            .find_map(|line| line.strip_prefix("Content-Length: "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(body.len(), content_length / 2);
+    }
+}
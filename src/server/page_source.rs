@@ -0,0 +1,23 @@
+use crate::lazy_tree::LazyTreeGenerator;
+use crate::rdf::triple::Triple;
+use crate::tree::tree_document::TreeDocument;
+use std::fmt::Debug;
+use std::vec::Vec;
+
+/// The pages served by the [server](`super::Server`).
+pub trait PageSource {
+    /// The triples of the page of a node, `None` if there is no node at this url.
+    fn page(&self, node_url: &str) -> Result<Option<Vec<Triple>>, &'static str>;
+}
+
+impl PageSource for TreeDocument {
+    fn page(&self, node_url: &str) -> Result<Option<Vec<Triple>>, &'static str> {
+        Ok(TreeDocument::page(self, node_url))
+    }
+}
+
+impl<T: num::ToPrimitive + Debug> PageSource for LazyTreeGenerator<T> {
+    fn page(&self, node_url: &str) -> Result<Option<Vec<Triple>>, &'static str> {
+        LazyTreeGenerator::page(self, node_url)
+    }
+}
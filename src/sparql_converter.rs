@@ -1,33 +1,84 @@
 use crate::tree::value::ValueType;
+use chrono::{self, Datelike, Timelike};
 use std::fmt::Debug;
-use chrono;
 
 pub trait SparqlConverter<T> {
-    fn convert(&self, value: T, value_type: ValueType)-> Result<String, &'static str>;
+    fn convert(&self, value: T, value_type: &ValueType) -> Result<String, &'static str>;
 }
 
 /// convert a number to [SPARQL](https://www.w3.org/TR/sparql11-query/#operandDataTypes) number compatible operand
-pub struct NumberToSparqlConverter;
+#[derive(derive_new::new, Default, Clone, Copy)]
+pub struct NumberToSparqlConverter {
+    /// How to handle a number outside of the value space of an integer type.
+    out_of_range_strategy: OutOfRangeStrategy,
+    /// How to interpret and serialize a number as a temporal value.
+    temporal_format: TemporalFormat,
+}
+
+/// How to handle a number outside of the value space of an integer type
+/// (e.g. `300` for a `xsd:byte`).
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutOfRangeStrategy {
+    /// Return an error.
+    #[default]
+    Reject,
+    /// Replace the number by the closest bound of the value space.
+    Clamp,
+    /// Wrap the number around the value space like an integer overflow.
+    /// When the value space is only bounded on one side the number is clamped instead.
+    Wrap,
+}
 
-impl<T: num::ToPrimitive + Debug> SparqlConverter<T> for NumberToSparqlConverter{
-    fn convert(&self,number_value: T, value_type: ValueType) -> Result<String, &'static str> 
-    {
+impl<T: num::ToPrimitive + Debug> SparqlConverter<T> for NumberToSparqlConverter {
+    fn convert(&self, number_value: T, value_type: &ValueType) -> Result<String, &'static str> {
         match value_type {
+            ValueType::Decimal => decimal_lexical_form(&number_value),
+            // the lexical space of an arbitrary datatype is unknown, the decimal form is the least surprising
+            ValueType::Custom(_) => decimal_lexical_form(&number_value),
+            ValueType::Float => match (number_value.to_f64(), number_value.to_f32()) {
+                // a finite double too large for a float would overflow into an infinity
+                (Some(double), Some(v)) if v.is_finite() || !double.is_finite() => {
+                    Ok(floating_point_lexical_form(
+                        format!("{:e}", v),
+                        v.is_nan(),
+                        v.is_infinite(),
+                        v.is_sign_negative(),
+                    ))
+                }
+                _ => Err("the number value cannot be represented as a float"),
+            },
+            ValueType::Double => match number_value.to_f64() {
+                Some(v) => Ok(floating_point_lexical_form(
+                    format!("{:e}", v),
+                    v.is_nan(),
+                    v.is_infinite(),
+                    v.is_sign_negative(),
+                )),
+                None => Err("the number value cannot be represented as a double"),
+            },
+
             ValueType::Integer
-            | ValueType::Decimal
-            | ValueType::Float
-            | ValueType::Double
             | ValueType::NonPositiveInteger
             | ValueType::NegativeInteger
             | ValueType::Long
             | ValueType::Int
             | ValueType::Short
+            | ValueType::Byte
             | ValueType::NonNegativeInteger
             | ValueType::UnsignedLong
             | ValueType::UnsignedInt
             | ValueType::UnsignedShort
-            | ValueType::PositiveInteger => Ok(format!("{:?}", number_value)),
-    
+            | ValueType::UnsignedByte
+            | ValueType::PositiveInteger => {
+                let value = integral_value(&number_value)?;
+                let value = fit_in_integer_bounds(
+                    value,
+                    integer_bounds(value_type),
+                    self.out_of_range_strategy,
+                )?;
+                Ok(value.to_string())
+            }
+
             ValueType::Boolean => {
                 if number_value.to_u8() == Some(1u8) {
                     Ok(String::from("true"))
@@ -37,20 +88,692 @@ impl<T: num::ToPrimitive + Debug> SparqlConverter<T> for NumberToSparqlConverter
                     Err("a boolean number should be between 0 and 1")
                 }
             }
-    
-            ValueType::DateTime => {
-                let unix_time = match number_value.to_i64() {
-                    Some(v) => v,
-                    None => return Err("for a Datetime the number value should be castable to a i64 "),
-                };
-                let date = match chrono::NaiveDateTime::from_timestamp_opt(unix_time, 0) {
-                    Some(v) => v,
-                    None => return Err("should be able to cast the number value to a date time"),
-                };
-                Ok(date.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+
+            ValueType::DateTime | ValueType::Date | ValueType::Time | ValueType::GYear => {
+                let nanoseconds = self.temporal_format.nanoseconds_of(&number_value)?;
+                self.temporal_format.format_instant(nanoseconds, value_type)
+            }
+            ValueType::Duration => {
+                let nanoseconds = self.temporal_format.nanoseconds_of(&number_value)?;
+                Ok(self.temporal_format.format_duration(nanoseconds))
             }
             _ => Err("the type cannot be created from a number"),
         }
     }
 }
 
+/// convert a string to a [SPARQL](https://www.w3.org/TR/sparql11-query/#operandDataTypes) operand
+/// for the value types that are not derived from a number
+pub struct StringToSparqlConverter;
+
+impl<T: AsRef<str>> SparqlConverter<T> for StringToSparqlConverter {
+    fn convert(&self, string_value: T, value_type: &ValueType) -> Result<String, &'static str> {
+        let string_value = string_value.as_ref();
+        match value_type {
+            ValueType::String | ValueType::Custom(_) => Ok(String::from(string_value)),
+            ValueType::LangString(language) => {
+                if is_valid_language_tag(language) {
+                    Ok(String::from(string_value))
+                } else {
+                    Err("the language tag should follow BCP 47")
+                }
+            }
+            ValueType::Iri => {
+                if is_absolute_iri(string_value) {
+                    Ok(String::from(string_value))
+                } else {
+                    Err("the value should be an absolute IRI")
+                }
+            }
+            ValueType::Boolean => match string_value {
+                "true" | "1" => Ok(String::from("true")),
+                "false" | "0" => Ok(String::from("false")),
+                _ => Err("a boolean string should be true, false, 1 or 0"),
+            },
+            _ => Err("the type cannot be created from a string"),
+        }
+    }
+}
+
+/// Check the shape of a [language tag](https://www.rfc-editor.org/rfc/bcp/bcp47.txt)
+/// as accepted by [Turtle](https://www.w3.org/TR/turtle/#grammar-production-LANGTAG).
+pub(crate) fn is_valid_language_tag(language: &str) -> bool {
+    let mut subtags = language.split('-');
+    let primary = subtags.next().unwrap_or_default();
+    (1..=8).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Check that the value is an [absolute IRI](https://www.rfc-editor.org/rfc/rfc3987#section-2.2),
+/// meaning that it has a scheme and no character forbidden in an IRI reference.
+pub(crate) fn is_absolute_iri(value: &str) -> bool {
+    let (scheme, rest) = match value.split_once(':') {
+        Some(v) => v,
+        None => return false,
+    };
+    let mut scheme_characters = scheme.chars();
+    scheme_characters
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && scheme_characters.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        && !rest.is_empty()
+        && !rest.chars().any(|c| {
+            c.is_whitespace() || matches!(c, '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\')
+        })
+}
+
+/// Interpretation of a number as a temporal value and the precision of its serialization.
+#[derive(derive_new::new, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TemporalFormat {
+    /// Unit of the number, either since the unix epoch or as a duration.
+    epoch_unit: EpochUnit,
+    /// Number of digits of the fractional seconds, at most 9.
+    fractional_second_digits: u8,
+    /// Timezone of the serialized date and time.
+    timezone: Timezone,
+}
+
+/// Unit of a number interpreted as a temporal value.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EpochUnit {
+    #[default]
+    Second,
+    Millisecond,
+    Microsecond,
+}
+
+/// Timezone of a serialized date and time.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timezone {
+    /// Coordinated universal time, serialized with a `Z`.
+    #[default]
+    Utc,
+    /// Offset from UTC in seconds, serialized as `+hh:mm` or `-hh:mm`.
+    /// XSD only allows offsets of whole minutes up to 14 hours.
+    FixedOffset(i32),
+    /// Value without a timezone.
+    Unspecified,
+}
+
+impl EpochUnit {
+    /// Number of nanoseconds in the unit.
+    fn nanoseconds(&self) -> i128 {
+        match self {
+            EpochUnit::Second => 1_000_000_000,
+            EpochUnit::Millisecond => 1_000_000,
+            EpochUnit::Microsecond => 1_000,
+        }
+    }
+}
+
+impl TemporalFormat {
+    /// Convert the number into nanoseconds following the epoch unit.
+    fn nanoseconds_of<T: num::ToPrimitive>(&self, number_value: &T) -> Result<i128, &'static str> {
+        if let Ok(v) = integral_value(number_value) {
+            return v
+                .checked_mul(self.epoch_unit.nanoseconds())
+                .ok_or("the temporal value is too large");
+        }
+        match number_value.to_f64() {
+            Some(v) if v.is_finite() => {
+                Ok((v * self.epoch_unit.nanoseconds() as f64).round() as i128)
+            }
+            _ => Err("a temporal value cannot be created from a number that is not finite"),
+        }
+    }
+
+    /// Serialize a time since the unix epoch as a `xsd:dateTime`, `xsd:date`, `xsd:time` or `xsd:gYear`.
+    fn format_instant(
+        &self,
+        nanoseconds: i128,
+        value_type: &ValueType,
+    ) -> Result<String, &'static str> {
+        let offset = match self.timezone {
+            Timezone::FixedOffset(v) if v % 60 == 0 && v.abs() <= 14 * 3600 => v,
+            Timezone::FixedOffset(_) => {
+                return Err("the timezone offset should be of whole minutes and at most 14 hours")
+            }
+            Timezone::Utc | Timezone::Unspecified => 0,
+        };
+        let offset = chrono::FixedOffset::east_opt(offset)
+            .expect("the timezone offset should have been validated");
+        let seconds = i64::try_from(nanoseconds.div_euclid(1_000_000_000))
+            .map_err(|_| "the temporal value is too large")?;
+        let subsecond_nanoseconds = nanoseconds.rem_euclid(1_000_000_000) as u32;
+        let date = match chrono::DateTime::from_timestamp(seconds, subsecond_nanoseconds) {
+            Some(v) => v.with_timezone(&offset),
+            None => return Err("should be able to cast the number value to a date time"),
+        };
+
+        let year = if date.year() < 0 {
+            format!("-{:04}", -date.year())
+        } else {
+            format!("{:04}", date.year())
+        };
+        let calendar_date = format!("{}-{:02}-{:02}", year, date.month(), date.day());
+        let time = format!(
+            "{:02}:{:02}:{:02}{}",
+            date.hour(),
+            date.minute(),
+            date.second(),
+            self.fractional_seconds(date.nanosecond())
+        );
+        let lexical_form = match value_type {
+            ValueType::DateTime => format!("{}T{}", calendar_date, time),
+            ValueType::Date => calendar_date,
+            ValueType::Time => time,
+            ValueType::GYear => year,
+            _ => return Err("the type is not a date or a time"),
+        };
+        Ok(format!("{}{}", lexical_form, self.timezone_suffix()))
+    }
+
+    /// Serialize an amount of time as a
+    /// [`xsd:duration`](https://www.w3.org/TR/xmlschema11-2/#f-durationCanonicalMap)
+    /// using days, hours, minutes and seconds, the length of months and years being ambiguous.
+    fn format_duration(&self, nanoseconds: i128) -> String {
+        let sign = if nanoseconds < 0 { "-" } else { "" };
+        let nanoseconds = nanoseconds.unsigned_abs();
+        let seconds = nanoseconds / 1_000_000_000;
+        let fractional_seconds = self.fractional_seconds((nanoseconds % 1_000_000_000) as u32);
+        let (days, hours, minutes, seconds) = (
+            seconds / 86_400,
+            seconds % 86_400 / 3_600,
+            seconds % 3_600 / 60,
+            seconds % 60,
+        );
+
+        let mut time = String::new();
+        if hours != 0 {
+            time.push_str(&format!("{}H", hours));
+        }
+        if minutes != 0 {
+            time.push_str(&format!("{}M", minutes));
+        }
+        if seconds != 0 || !fractional_seconds.is_empty() || (time.is_empty() && days == 0) {
+            time.push_str(&format!("{}{}S", seconds, fractional_seconds));
+        }
+        let days = if days != 0 {
+            format!("{}D", days)
+        } else {
+            String::new()
+        };
+        if time.is_empty() {
+            format!("{}P{}", sign, days)
+        } else {
+            format!("{}P{}T{}", sign, days, time)
+        }
+    }
+
+    /// Fractional part of the seconds with the configured number of digits, including the dot.
+    fn fractional_seconds(&self, nanoseconds: u32) -> String {
+        let digits = usize::from(self.fractional_second_digits.min(9));
+        if digits == 0 {
+            return String::new();
+        }
+        format!(".{}", &format!("{:09}", nanoseconds)[..digits])
+    }
+
+    fn timezone_suffix(&self) -> String {
+        match self.timezone {
+            Timezone::Utc => String::from("Z"),
+            Timezone::FixedOffset(v) => format!(
+                "{}{:02}:{:02}",
+                if v < 0 { "-" } else { "+" },
+                v.abs() / 3600,
+                v.abs() % 3600 / 60
+            ),
+            Timezone::Unspecified => String::new(),
+        }
+    }
+}
+
+/// Inclusive lower and upper bound of the value space of an integer type,
+/// `None` meaning that the value space is unbounded on that side.
+/// https://www.w3.org/TR/xmlschema11-2/#built-in-datatypes
+pub(crate) fn integer_bounds(value_type: &ValueType) -> (Option<i128>, Option<i128>) {
+    match value_type {
+        ValueType::NonPositiveInteger => (None, Some(0)),
+        ValueType::NegativeInteger => (None, Some(-1)),
+        ValueType::Long => (Some(i64::MIN as i128), Some(i64::MAX as i128)),
+        ValueType::Int => (Some(i32::MIN as i128), Some(i32::MAX as i128)),
+        ValueType::Short => (Some(i16::MIN as i128), Some(i16::MAX as i128)),
+        ValueType::Byte => (Some(i8::MIN as i128), Some(i8::MAX as i128)),
+        ValueType::NonNegativeInteger => (Some(0), None),
+        ValueType::UnsignedLong => (Some(0), Some(u64::MAX as i128)),
+        ValueType::UnsignedInt => (Some(0), Some(u32::MAX as i128)),
+        ValueType::UnsignedShort => (Some(0), Some(u16::MAX as i128)),
+        ValueType::UnsignedByte => (Some(0), Some(u8::MAX as i128)),
+        ValueType::PositiveInteger => (Some(1), None),
+        _ => (None, None),
+    }
+}
+
+/// Apply the [`OutOfRangeStrategy`] to a value that might be outside of the bounds.
+fn fit_in_integer_bounds(
+    value: i128,
+    bounds: (Option<i128>, Option<i128>),
+    strategy: OutOfRangeStrategy,
+) -> Result<i128, &'static str> {
+    let (lower, upper) = bounds;
+    let is_too_low = lower.is_some_and(|lower| value < lower);
+    let is_too_high = upper.is_some_and(|upper| value > upper);
+    if !is_too_low && !is_too_high {
+        return Ok(value);
+    }
+    match (strategy, lower, upper) {
+        (OutOfRangeStrategy::Reject, _, _) => {
+            if is_too_low {
+                Err("the number value is lower than the minimum of the integer type")
+            } else {
+                Err("the number value is greater than the maximum of the integer type")
+            }
+        }
+        (OutOfRangeStrategy::Wrap, Some(lower), Some(upper)) => {
+            Ok(lower + (value - lower).rem_euclid(upper - lower + 1))
+        }
+        (OutOfRangeStrategy::Clamp | OutOfRangeStrategy::Wrap, Some(lower), _) if is_too_low => {
+            Ok(lower)
+        }
+        (OutOfRangeStrategy::Clamp | OutOfRangeStrategy::Wrap, _, Some(upper)) => Ok(upper),
+        _ => unreachable!("a value out of range has at least one bound"),
+    }
+}
+
+/// Get the number as an integer, failing if it has a fractional part.
+fn integral_value<T: num::ToPrimitive>(number_value: &T) -> Result<i128, &'static str> {
+    match number_value.to_f64() {
+        Some(v) if v.is_finite() && v.fract() == 0.0 => {}
+        _ => return Err("an integer type cannot be created from a number with a fractional part"),
+    }
+    match number_value.to_i128() {
+        Some(v) => Ok(v),
+        None => Err("the number value is too large to be represented as an integer"),
+    }
+}
+
+/// Canonical form of a [decimal](https://www.w3.org/TR/xmlschema11-2/#f-decimalCanonicalMap),
+/// without a decimal point for integral values and never using an exponent.
+fn decimal_lexical_form<T: num::ToPrimitive>(number_value: &T) -> Result<String, &'static str> {
+    if let Ok(v) = integral_value(number_value) {
+        return Ok(v.to_string());
+    }
+    match number_value.to_f64() {
+        Some(v) if v.is_finite() => Ok(v.to_string()),
+        _ => Err("a decimal cannot be created from a number that is not finite"),
+    }
+}
+
+/// Canonical form of a [float or a double](https://www.w3.org/TR/xmlschema11-2/#f-doubleCanonicalMap)
+/// from the rust scientific notation (e.g. `1e-7` becomes `1.0E-7`).
+fn floating_point_lexical_form(
+    scientific_notation: String,
+    is_nan: bool,
+    is_infinite: bool,
+    is_negative: bool,
+) -> String {
+    if is_nan {
+        return String::from("NaN");
+    }
+    if is_infinite {
+        return String::from(if is_negative { "-INF" } else { "INF" });
+    }
+    let (mantissa, exponent) = scientific_notation
+        .split_once('e')
+        .expect("the rust scientific notation should always have an exponent");
+    if mantissa.contains('.') {
+        format!("{}E{}", mantissa, exponent)
+    } else {
+        format!("{}.0E{}", mantissa, exponent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EpochUnit, NumberToSparqlConverter, OutOfRangeStrategy, SparqlConverter,
+        StringToSparqlConverter, TemporalFormat, Timezone,
+    };
+    use crate::tree::value::ValueType;
+
+    #[test]
+    fn should_convert_a_double_in_the_canonical_form() {
+        let cases: Vec<(f64, &str)> = vec![
+            (1e-7, "1.0E-7"),
+            (-1.5e-7, "-1.5E-7"),
+            (100.0, "1.0E2"),
+            (0.0, "0.0E0"),
+            (-0.0, "-0.0E0"),
+            (f64::NAN, "NaN"),
+            (f64::INFINITY, "INF"),
+            (f64::NEG_INFINITY, "-INF"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                NumberToSparqlConverter::default()
+                    .convert(value, &ValueType::Double)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn should_convert_a_float_with_the_float_precision() {
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(0.1f32, &ValueType::Float)
+                .unwrap(),
+            "1.0E-1"
+        );
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(3, &ValueType::Float)
+                .unwrap(),
+            "3.0E0"
+        );
+        NumberToSparqlConverter::default()
+            .convert(f64::MAX, &ValueType::Float)
+            .expect_err("a float cannot represent the maximum of a double");
+    }
+
+    #[test]
+    fn should_convert_a_decimal_without_exponent() {
+        let cases: Vec<(f64, &str)> = vec![
+            (1e-7, "0.0000001"),
+            (2.5, "2.5"),
+            (3.0, "3"),
+            (-0.0, "0"),
+            (1e21, "1000000000000000000000"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(
+                NumberToSparqlConverter::default()
+                    .convert(value, &ValueType::Decimal)
+                    .unwrap(),
+                expected
+            );
+        }
+        NumberToSparqlConverter::default()
+            .convert(f64::NAN, &ValueType::Decimal)
+            .expect_err("a decimal cannot be NaN");
+    }
+
+    #[test]
+    fn should_reject_an_integer_type_with_a_fractional_part() {
+        NumberToSparqlConverter::default()
+            .convert(1.5, &ValueType::Integer)
+            .expect_err("an integer cannot have a fractional part");
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(4.0, &ValueType::Integer)
+                .unwrap(),
+            "4"
+        );
+    }
+
+    #[test]
+    fn should_check_the_range_of_the_bounded_integer_types() {
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(-128, &ValueType::Byte)
+                .unwrap(),
+            "-128"
+        );
+        NumberToSparqlConverter::default()
+            .convert(300, &ValueType::Byte)
+            .expect_err("300 is out of the range of a byte");
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(65535, &ValueType::UnsignedShort)
+                .unwrap(),
+            "65535"
+        );
+        NumberToSparqlConverter::default()
+            .convert(65536, &ValueType::UnsignedShort)
+            .expect_err("65536 is out of the range of an unsigned short");
+        NumberToSparqlConverter::default()
+            .convert(i64::MAX as i128 + 1, &ValueType::Long)
+            .expect_err("the value is out of the range of a long");
+    }
+
+    #[test]
+    fn should_reject_negative_numbers_for_unsigned_types() {
+        for value_type in [
+            ValueType::NonNegativeInteger,
+            ValueType::UnsignedLong,
+            ValueType::UnsignedInt,
+            ValueType::UnsignedShort,
+            ValueType::UnsignedByte,
+        ] {
+            NumberToSparqlConverter::default()
+                .convert(-1, &value_type)
+                .expect_err("an unsigned type cannot be negative");
+            assert_eq!(
+                NumberToSparqlConverter::default()
+                    .convert(0, &value_type)
+                    .unwrap(),
+                "0"
+            );
+        }
+    }
+
+    #[test]
+    fn should_check_the_sign_of_the_sign_constrained_integer_types() {
+        let converter = NumberToSparqlConverter::default();
+        assert_eq!(
+            converter.convert(-1, &ValueType::NegativeInteger).unwrap(),
+            "-1"
+        );
+        converter
+            .convert(0, &ValueType::NegativeInteger)
+            .expect_err("0 is not a negative integer");
+        assert_eq!(
+            converter
+                .convert(0, &ValueType::NonPositiveInteger)
+                .unwrap(),
+            "0"
+        );
+        converter
+            .convert(1, &ValueType::NonPositiveInteger)
+            .expect_err("1 is not a non positive integer");
+        assert_eq!(
+            converter.convert(1, &ValueType::PositiveInteger).unwrap(),
+            "1"
+        );
+        converter
+            .convert(0, &ValueType::PositiveInteger)
+            .expect_err("0 is not a positive integer");
+    }
+
+    #[test]
+    fn should_clamp_the_value_given_a_clamp_strategy() {
+        let converter =
+            NumberToSparqlConverter::new(OutOfRangeStrategy::Clamp, TemporalFormat::default());
+        assert_eq!(converter.convert(300, &ValueType::Byte).unwrap(), "127");
+        assert_eq!(converter.convert(-300, &ValueType::Byte).unwrap(), "-128");
+        assert_eq!(converter.convert(-5, &ValueType::UnsignedInt).unwrap(), "0");
+        assert_eq!(
+            converter.convert(5, &ValueType::NegativeInteger).unwrap(),
+            "-1"
+        );
+        assert_eq!(converter.convert(42, &ValueType::Byte).unwrap(), "42");
+    }
+
+    #[test]
+    fn should_wrap_the_value_given_a_wrap_strategy() {
+        let converter =
+            NumberToSparqlConverter::new(OutOfRangeStrategy::Wrap, TemporalFormat::default());
+        assert_eq!(converter.convert(300, &ValueType::Byte).unwrap(), "44");
+        assert_eq!(converter.convert(128, &ValueType::Byte).unwrap(), "-128");
+        assert_eq!(
+            converter.convert(-1, &ValueType::UnsignedByte).unwrap(),
+            "255"
+        );
+        assert_eq!(
+            converter.convert(-7, &ValueType::PositiveInteger).unwrap(),
+            "1"
+        );
+    }
+
+    #[test]
+    fn should_not_fit_a_number_with_a_fractional_part_in_an_integer_type() {
+        NumberToSparqlConverter::new(OutOfRangeStrategy::Clamp, TemporalFormat::default())
+            .convert(300.5, &ValueType::Byte)
+            .expect_err("the strategy should not hide a fractional part");
+    }
+
+    #[test]
+    fn should_convert_a_date_time_in_utc_by_default() {
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(1_000_000_000, &ValueType::DateTime)
+                .unwrap(),
+            "2001-09-09T01:46:40Z"
+        );
+    }
+
+    #[test]
+    fn should_convert_a_date_time_with_an_epoch_unit_a_precision_and_an_offset() {
+        let converter = NumberToSparqlConverter::new(
+            OutOfRangeStrategy::Reject,
+            TemporalFormat::new(EpochUnit::Millisecond, 3, Timezone::FixedOffset(-5 * 3600)),
+        );
+        assert_eq!(
+            converter
+                .convert(1_000_000_000_123i64, &ValueType::DateTime)
+                .unwrap(),
+            "2001-09-08T20:46:40.123-05:00"
+        );
+
+        let converter = NumberToSparqlConverter::new(
+            OutOfRangeStrategy::Reject,
+            TemporalFormat::new(EpochUnit::Microsecond, 6, Timezone::Unspecified),
+        );
+        assert_eq!(
+            converter
+                .convert(1_000_000_000_000_001i64, &ValueType::DateTime)
+                .unwrap(),
+            "2001-09-09T01:46:40.000001"
+        );
+
+        let converter = NumberToSparqlConverter::new(
+            OutOfRangeStrategy::Reject,
+            TemporalFormat::new(EpochUnit::Second, 0, Timezone::FixedOffset(61)),
+        );
+        converter
+            .convert(0, &ValueType::DateTime)
+            .expect_err("the offset should be of whole minutes");
+    }
+
+    #[test]
+    fn should_convert_a_date_a_time_and_a_year() {
+        let converter = NumberToSparqlConverter::new(
+            OutOfRangeStrategy::Reject,
+            TemporalFormat::new(EpochUnit::Second, 2, Timezone::FixedOffset(3600)),
+        );
+        let timestamp = 1_000_000_000.5;
+        assert_eq!(
+            converter.convert(timestamp, &ValueType::Date).unwrap(),
+            "2001-09-09+01:00"
+        );
+        assert_eq!(
+            converter.convert(timestamp, &ValueType::Time).unwrap(),
+            "02:46:40.50+01:00"
+        );
+        assert_eq!(
+            converter.convert(timestamp, &ValueType::GYear).unwrap(),
+            "2001+01:00"
+        );
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(-62_198_755_200i64, &ValueType::GYear)
+                .unwrap(),
+            "-0001Z"
+        );
+    }
+
+    #[test]
+    fn should_convert_a_duration() {
+        let converter = NumberToSparqlConverter::default();
+        assert_eq!(
+            converter.convert(93_784, &ValueType::Duration).unwrap(),
+            "P1DT2H3M4S"
+        );
+        assert_eq!(
+            converter.convert(86_400, &ValueType::Duration).unwrap(),
+            "P1D"
+        );
+        assert_eq!(converter.convert(0, &ValueType::Duration).unwrap(), "PT0S");
+        assert_eq!(
+            converter.convert(-60, &ValueType::Duration).unwrap(),
+            "-PT1M"
+        );
+
+        let converter = NumberToSparqlConverter::new(
+            OutOfRangeStrategy::Reject,
+            TemporalFormat::new(EpochUnit::Millisecond, 3, Timezone::Utc),
+        );
+        assert_eq!(
+            converter.convert(1_500, &ValueType::Duration).unwrap(),
+            "PT1.500S"
+        );
+    }
+
+    #[test]
+    fn should_convert_a_number_of_a_custom_datatype() {
+        let value_type = ValueType::Custom(String::from("https://example.com/temperature"));
+        assert_eq!(
+            NumberToSparqlConverter::default()
+                .convert(21.5, &value_type)
+                .unwrap(),
+            "21.5"
+        );
+        NumberToSparqlConverter::default()
+            .convert(1, &ValueType::Iri)
+            .expect_err("an IRI cannot be created from a number");
+    }
+
+    #[test]
+    fn should_convert_a_language_tagged_string() {
+        let converter = StringToSparqlConverter;
+        assert_eq!(
+            converter
+                .convert("chat", &ValueType::LangString(String::from("fr-BE")))
+                .unwrap(),
+            "chat"
+        );
+        converter
+            .convert("chat", &ValueType::LangString(String::from("fr_BE")))
+            .expect_err("an underscore is not allowed in a language tag");
+        converter
+            .convert("chat", &ValueType::LangString(String::new()))
+            .expect_err("a language tag cannot be empty");
+    }
+
+    #[test]
+    fn should_only_convert_an_absolute_iri() {
+        let converter = StringToSparqlConverter;
+        assert_eq!(
+            converter
+                .convert("https://example.com/a", &ValueType::Iri)
+                .unwrap(),
+            "https://example.com/a"
+        );
+        for invalid_iri in ["example.com/a", "/a", "https://example.com/a b", "1a:b"] {
+            converter
+                .convert(invalid_iri, &ValueType::Iri)
+                .expect_err("the IRI is not absolute or contains a forbidden character");
+        }
+    }
+
+    #[test]
+    fn should_not_convert_a_string_into_a_number_type() {
+        StringToSparqlConverter
+            .convert(String::from("1"), &ValueType::Integer)
+            .expect_err("a number type should be created from a number");
+    }
+}
@@ -1,4 +1,9 @@
 #[cfg(test)]
+#[allow(
+    clippy::module_inception,
+    clippy::redundant_field_names,
+    clippy::single_component_path_imports
+)]
 mod tests {
     mod tests_generate_relations {
         use crate::generate_relation::generate_relations;
@@ -7,6 +12,7 @@ mod tests {
             DistributionOfRelation, RelationGeneratorArg, RelationTemplate,
             TemplateRangeVariationRelation,
         };
+        use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
         use crate::tree::relation::Relation;
         use crate::tree::relation_operator::RelationOperator;
         use crate::tree::value::ValueType;
@@ -52,7 +58,9 @@ mod tests {
                 template: template,
                 range: Box::new(RandomBoundedNumberRange::new(lower_bound, upper_bound)),
                 distribution_of_relation: relation_distribution,
-                value_type: value_type,
+                value_type: value_type.clone(),
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
             let arg = RelationGeneratorArg::ValueVariation(template_range_variation);
 
@@ -98,7 +106,9 @@ mod tests {
                 template: template,
                 range: Box::new(RandomBoundedNumberRange::new(lower_bound, upper_bound)),
                 distribution_of_relation: relation_distribution,
-                value_type: value_type,
+                value_type: value_type.clone(),
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
             };
             let arg = RelationGeneratorArg::ValueVariation(template_range_variation);
 
@@ -106,7 +116,7 @@ mod tests {
 
             assert_eq!(resp.len(), n_node);
 
-            for (i, relations_in_node) in resp.iter().enumerate() {
+            for relations_in_node in resp.iter() {
                 assert!(
                     relations_in_node.len() >= lower_number_relation
                         && relations_in_node.len() <= upper_number_relation
@@ -123,4 +133,268 @@ mod tests {
             }
         }
     }
+
+    mod tests_generate_tree_document {
+        use crate::generate_tree_document;
+        use crate::generator_argument::member_argument::{
+            DistributionOfMember, MemberGeneratorArg, PropertyTemplate,
+        };
+        use crate::generator_argument::range::RandomBoundedNumberRange;
+        use crate::generator_argument::relation_argument::{
+            DistributionOfRelation, PaginationArg, RelationGeneratorArg, RelationTemplate,
+            TemplateRangeVariationRelation,
+        };
+        use crate::generator_argument::search_form_argument::SearchFormArg;
+        use crate::generator_argument::string_index_argument::{
+            StringIndexArg, StringIndexStrategy, StringSource,
+        };
+        use crate::generator_argument::Args;
+        use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+        use crate::tree::relation_operator::RelationOperator;
+        use crate::tree::shape::PropertyShape;
+        use crate::tree::value::ValueType;
+        use std::collections::HashSet;
+
+        #[test]
+        fn given_a_value_variation_argument_should_return_a_collection_with_a_reachable_tree() {
+            let args = Args {
+                relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                    template: RelationTemplate {
+                        path: String::from("ex:g"),
+                        relation_type: RelationOperator::GreaterThanRelation,
+                    },
+                    range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                    distribution_of_relation: DistributionOfRelation::Direct(vec![2, 1, 1]),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }),
+                members: None,
+                search_form: None,
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            let document = generate_tree_document(&args).unwrap();
+
+            assert_eq!(
+                document.collection().url(),
+                "https://example.com/collection"
+            );
+            assert_eq!(document.collection().view(), "https://example.com/root");
+            assert_eq!(document.nodes()[0].node_url(), "https://example.com/root");
+            // the root, its two children and the two children of the children
+            assert_eq!(document.nodes().len(), 5);
+
+            let node_urls: HashSet<&String> = document
+                .nodes()
+                .iter()
+                .map(|node| node.node_url())
+                .collect();
+            for node in document.nodes() {
+                for relation in node.relation() {
+                    assert!(node_urls.contains(relation.node()));
+                }
+            }
+        }
+
+        #[test]
+        fn given_an_incompatible_value_type_should_return_an_error() {
+            let args = Args {
+                relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                    template: RelationTemplate {
+                        path: String::from("ex:g"),
+                        relation_type: RelationOperator::GreaterThanRelation,
+                    },
+                    range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                    distribution_of_relation: DistributionOfRelation::Direct(vec![2]),
+                    value_type: ValueType::String,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }),
+                members: None,
+                search_form: None,
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            assert!(generate_tree_document(&args).is_err());
+        }
+
+        #[test]
+        fn given_a_member_argument_should_return_members_following_the_shape() {
+            let args = Args {
+                relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                    template: RelationTemplate {
+                        path: String::from("ex:g"),
+                        relation_type: RelationOperator::GreaterThanRelation,
+                    },
+                    range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                    distribution_of_relation: DistributionOfRelation::Direct(vec![2]),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }),
+                members: Some(MemberGeneratorArg {
+                    properties: vec![PropertyTemplate {
+                        path: String::from("ex:g"),
+                        range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                        value_type: ValueType::Int,
+                        out_of_range_strategy: OutOfRangeStrategy::Reject,
+                        temporal_format: TemporalFormat::default(),
+                    }],
+                    distribution_of_member: DistributionOfMember::Direct(vec![1, 2, 3]),
+                    versioning: None,
+                }),
+                search_form: None,
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            let document = generate_tree_document(&args).unwrap();
+
+            let shape = document.shape().as_ref().unwrap();
+            assert_eq!(
+                *document.collection().shape(),
+                Some(String::from("https://example.com/shape"))
+            );
+            assert_eq!(
+                *shape.properties(),
+                vec![PropertyShape::new(String::from("ex:g"), ValueType::Int)]
+            );
+            assert_eq!(document.collection().members().len(), 6);
+            for node in document.nodes() {
+                for member in node.members() {
+                    assert_eq!(member.properties["ex:g"].value_type, ValueType::Int);
+                }
+            }
+        }
+
+        #[test]
+        fn given_a_search_form_argument_should_return_nodes_reachable_by_the_template() {
+            let args = Args {
+                relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                    template: RelationTemplate {
+                        path: String::from("ex:g"),
+                        relation_type: RelationOperator::EqualThanRelation,
+                    },
+                    range: Box::new(RandomBoundedNumberRange::new(0, 1_000_000)),
+                    distribution_of_relation: DistributionOfRelation::Direct(vec![3]),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }),
+                members: None,
+                search_form: Some(SearchFormArg {
+                    variable: String::from("g"),
+                    path: String::from("ex:g"),
+                }),
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            let document = generate_tree_document(&args).unwrap();
+
+            let search_form = document.search_form().as_ref().unwrap();
+            assert_eq!(search_form.template(), "https://example.com/node{?g}");
+            let root = &document.nodes()[0];
+            for relation in root.relation() {
+                let value = &relation.value().as_ref().unwrap().value;
+                let is_ambiguous = root
+                    .relation()
+                    .iter()
+                    .filter(|other| other.value() == relation.value())
+                    .count()
+                    > 1;
+                if !is_ambiguous {
+                    assert_eq!(*relation.node(), search_form.expand(value));
+                }
+                assert!(document
+                    .nodes()
+                    .iter()
+                    .any(|node| node.node_url() == relation.node()));
+            }
+        }
+
+        #[test]
+        fn given_a_pagination_should_return_a_linked_list_of_full_pages() {
+            let args = Args {
+                relation: RelationGeneratorArg::Pagination(PaginationArg {
+                    n_page: 4,
+                    page_size: 3,
+                    backward_links: true,
+                }),
+                members: Some(MemberGeneratorArg {
+                    properties: vec![PropertyTemplate {
+                        path: String::from("ex:p"),
+                        range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                        value_type: ValueType::Int,
+                        out_of_range_strategy: OutOfRangeStrategy::Reject,
+                        temporal_format: TemporalFormat::default(),
+                    }],
+                    distribution_of_member: DistributionOfMember::Direct(vec![1]),
+                    versioning: None,
+                }),
+                search_form: None,
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            let document = generate_tree_document(&args).unwrap();
+
+            let node_urls: Vec<&String> = document
+                .nodes()
+                .iter()
+                .map(|node| node.node_url())
+                .collect();
+            assert_eq!(
+                node_urls,
+                vec![
+                    "https://example.com/root",
+                    "https://example.com/page/1",
+                    "https://example.com/page/2",
+                    "https://example.com/page/3"
+                ]
+            );
+            for node in document.nodes() {
+                assert_eq!(node.members().len(), 3);
+            }
+            assert_eq!(document.collection().members().len(), 12);
+            assert_eq!(document.nodes()[0].relation().len(), 1);
+            assert_eq!(document.nodes()[2].relation().len(), 2);
+        }
+
+        #[test]
+        fn given_a_string_index_should_return_its_members_once() {
+            let args: Args<i32> = Args {
+                relation: RelationGeneratorArg::StringIndex(StringIndexArg {
+                    path: String::from("ex:name"),
+                    strings: StringSource::Supplied(vec![String::from("abc"), String::from("bcd")]),
+                    strategy: StringIndexStrategy::NGram { n: 2 },
+                }),
+                members: None,
+                search_form: None,
+                topology: None,
+                base_url: String::from("https://example.com"),
+            };
+
+            let document = generate_tree_document(&args).unwrap();
+
+            assert_eq!(
+                document.collection().members(),
+                &vec![
+                    String::from("https://example.com/member/0"),
+                    String::from("https://example.com/member/1")
+                ]
+            );
+            let shape = document.shape().as_ref().unwrap();
+            assert_eq!(
+                shape.properties(),
+                &vec![PropertyShape::new(
+                    String::from("ex:name"),
+                    ValueType::String
+                )]
+            );
+        }
+    }
 }
@@ -0,0 +1,67 @@
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
+use derive_getters;
+use derive_new;
+use std::vec::Vec;
+
+/// A [tree:Collection](https://treecg.github.io/specification/#collection),
+/// the entry point from which a client discovers the [nodes](`super::node::Node`).
+#[derive(derive_new::new, Clone, derive_getters::Getters, Debug, PartialEq, Eq)]
+pub struct Collection {
+    /// IRI of the collection.
+    url: String,
+    /// Url of the root node, the view of the collection.
+    view: String,
+    /// Url of all the [members](`super::member::Member`) of the collection.
+    members: Vec<String>,
+    /// IRI of the [shape](`super::shape::NodeShape`) of the members.
+    shape: Option<String>,
+}
+
+impl Collection {
+    /// The triples typing the collection and linking it to its view and its shape.
+    pub fn view_triples(&self) -> Vec<Triple> {
+        let subject = Term::NamedNode(self.url.clone());
+        let mut triples = vec![
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::TREE_COLLECTION)),
+            ),
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::TREE_VIEW),
+                Term::NamedNode(self.view.clone()),
+            ),
+        ];
+        if let Some(shape) = &self.shape {
+            triples.push(Triple::new(
+                subject,
+                String::from(vocabulary::TREE_SHAPE),
+                Term::NamedNode(shape.clone()),
+            ));
+        }
+        triples
+    }
+
+    /// The `tree:member` triples linking the collection to the given members.
+    pub fn member_triples<'a>(&self, members: impl Iterator<Item = &'a String>) -> Vec<Triple> {
+        members
+            .map(|member| {
+                Triple::new(
+                    Term::NamedNode(self.url.clone()),
+                    String::from(vocabulary::TREE_MEMBER),
+                    Term::NamedNode(member.clone()),
+                )
+            })
+            .collect()
+    }
+
+    /// The triples describing the whole collection.
+    pub fn triples(&self) -> Vec<Triple> {
+        let mut triples = self.view_triples();
+        triples.extend(self.member_triples(self.members.iter()));
+        triples
+    }
+}
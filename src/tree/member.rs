@@ -1,7 +1,9 @@
 use super::value::Value;
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
 use std::collections::HashMap;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 /// Member inside a [Node](`super::node::Node`).
 pub struct Member {
     /// Url of the member.
@@ -12,3 +14,21 @@ pub struct Member {
 
 /// A property url.
 type Property = String;
+
+impl Member {
+    /// The triples describing the properties of the member, sorted by property.
+    pub fn triples(&self) -> Vec<Triple> {
+        let mut properties: Vec<(&Property, &Value)> = self.properties.iter().collect();
+        properties.sort_by(|a, b| a.0.cmp(b.0));
+        properties
+            .into_iter()
+            .map(|(property, value)| {
+                Triple::new(
+                    Term::NamedNode(self.url.clone()),
+                    property.clone(),
+                    Term::from(value.clone()),
+                )
+            })
+            .collect()
+    }
+}
@@ -3,4 +3,10 @@ pub mod shacl_path;
 pub mod node;
 pub mod value;
 pub mod member;
-pub mod relation_operator;
\ No newline at end of file
+pub mod relation_operator;
+pub mod collection;
+pub mod tree_document;
+pub mod shape;
+pub mod search_form;
+pub mod page;
+pub mod retention_policy;
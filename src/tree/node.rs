@@ -1,8 +1,11 @@
+use super::member::Member;
 use super::relation::Relation;
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
 use derive_getters;
 use derive_new;
 use std::vec::Vec;
-use super::member::Member;
 
 /// A TREE HTTP document with relationships.
 #[derive(derive_new::new, Clone, derive_getters::Getters)]
@@ -14,3 +17,21 @@ pub struct Node {
     /// The [members](`Member`) into this node.
     members: Vec<Member>,
 }
+
+impl Node {
+    /// The triples describing the node, its [relations](`Relation`) and its [members](`Member`).
+    pub fn triples(&self) -> Vec<Triple> {
+        let mut triples = vec![Triple::new(
+            Term::NamedNode(self.node_url.clone()),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::TREE_NODE_CLASS)),
+        )];
+        for (i, relation) in self.relation.iter().enumerate() {
+            triples.extend(relation.triples(&self.node_url, &format!("relation{}", i)));
+        }
+        for member in self.members.iter() {
+            triples.extend(member.triples());
+        }
+        triples
+    }
+}
@@ -0,0 +1,303 @@
+use super::collection::Collection;
+use super::member::Member;
+use super::node::Node;
+use super::relation::Relation;
+use super::relation_operator::RelationOperator;
+use super::value::{Value, ValueType};
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+/// A TREE page read from its triples: its [node](`Node`) with its [relations](`Relation`)
+/// and its [members](`Member`), and the [collection](`Collection`) it describes.
+#[derive(Clone)]
+pub struct Page {
+    pub node: Node,
+    /// The collection, if the page links it to its view.
+    pub collection: Option<Collection>,
+}
+
+impl Page {
+    /// Read the page of the node identified by the url, like the triples parsed from an
+    /// [RDF document](`crate::rdf::format::RdfFormat::parse`).
+    /// The members are the named nodes linked with `tree:member`, with the literals and IRIs
+    /// of their properties, the first value of a property being kept.
+    /// A `tree:path` that is not a single IRI is ignored, since only direct paths are supported.
+    pub fn from_triples(triples: &[Triple], node_url: &str) -> Result<Page, &'static str> {
+        let node = Term::NamedNode(String::from(node_url));
+        if !triples
+            .iter()
+            .any(|triple| triple.subject == node || triple.object == node)
+        {
+            return Err("the page does not describe the node");
+        }
+        let triples_by_subject = triples_by_subject(triples);
+
+        let relations: Vec<Relation> =
+            objects(&triples_by_subject, &node, vocabulary::TREE_RELATION)
+                .map(|relation| read_relation(&triples_by_subject, relation))
+                .collect::<Result<Vec<Relation>, &'static str>>()?;
+
+        let member_urls: Vec<String> = triples
+            .iter()
+            .filter(|triple| triple.predicate == vocabulary::TREE_MEMBER)
+            .filter_map(|triple| match &triple.object {
+                Term::NamedNode(url) => Some(url.clone()),
+                _ => None,
+            })
+            .fold(Vec::new(), |mut urls, url| {
+                if !urls.contains(&url) {
+                    urls.push(url);
+                }
+                urls
+            });
+        let members: Vec<Member> = member_urls
+            .iter()
+            .map(|url| read_member(&triples_by_subject, url))
+            .collect();
+
+        Ok(Page {
+            collection: read_collection(triples, &triples_by_subject, member_urls),
+            node: Node::new(relations, String::from(node_url), members),
+        })
+    }
+}
+
+fn triples_by_subject(triples: &[Triple]) -> HashMap<&Term, Vec<&Triple>> {
+    let mut triples_by_subject: HashMap<&Term, Vec<&Triple>> = HashMap::new();
+    for triple in triples {
+        triples_by_subject
+            .entry(&triple.subject)
+            .or_default()
+            .push(triple);
+    }
+    triples_by_subject
+}
+
+/// The objects of the triples of a subject and a predicate.
+fn objects<'a>(
+    triples_by_subject: &'a HashMap<&Term, Vec<&Triple>>,
+    subject: &Term,
+    predicate: &'a str,
+) -> impl Iterator<Item = &'a Term> {
+    triples_by_subject
+        .get(subject)
+        .into_iter()
+        .flatten()
+        .filter(move |triple| triple.predicate == predicate)
+        .map(|triple| &triple.object)
+}
+
+fn read_relation(
+    triples_by_subject: &HashMap<&Term, Vec<&Triple>>,
+    relation: &Term,
+) -> Result<Relation, &'static str> {
+    let node = match objects(triples_by_subject, relation, vocabulary::TREE_NODE).next() {
+        Some(Term::NamedNode(url)) => url.clone(),
+        Some(_) => return Err("the tree:node of a relation should be an IRI"),
+        None => return Err("a relation has no tree:node"),
+    };
+    let relation_type =
+        objects(triples_by_subject, relation, vocabulary::RDF_TYPE).find_map(|class| match class {
+            Term::NamedNode(iri) => RelationOperator::from_iri(iri),
+            _ => None,
+        });
+    let path =
+        objects(triples_by_subject, relation, vocabulary::TREE_PATH).find_map(|path| match path {
+            Term::NamedNode(iri) => Some(iri.clone()),
+            _ => None,
+        });
+    let value = objects(triples_by_subject, relation, vocabulary::TREE_VALUE).find_map(value_of);
+    let remaining_items = objects(
+        triples_by_subject,
+        relation,
+        vocabulary::TREE_REMAINING_ITEMS,
+    )
+    .find_map(|remaining_items| match remaining_items {
+        Term::Literal(v) => v.value.parse::<i32>().ok(),
+        _ => None,
+    });
+    Ok(Relation::new(
+        remaining_items,
+        path,
+        value,
+        node,
+        relation_type,
+    ))
+}
+
+fn read_member(triples_by_subject: &HashMap<&Term, Vec<&Triple>>, url: &str) -> Member {
+    let mut properties: HashMap<String, Value> = HashMap::new();
+    for triple in triples_by_subject
+        .get(&Term::NamedNode(String::from(url)))
+        .into_iter()
+        .flatten()
+    {
+        if let Some(value) = value_of(&triple.object) {
+            properties.entry(triple.predicate.clone()).or_insert(value);
+        }
+    }
+    Member {
+        url: String::from(url),
+        properties,
+    }
+}
+
+/// The collection linked to its view by the triples, with the members of the page.
+fn read_collection(
+    triples: &[Triple],
+    triples_by_subject: &HashMap<&Term, Vec<&Triple>>,
+    members: Vec<String>,
+) -> Option<Collection> {
+    let triple = triples
+        .iter()
+        .find(|triple| triple.predicate == vocabulary::TREE_VIEW)?;
+    let (url, view) = match (&triple.subject, &triple.object) {
+        (Term::NamedNode(url), Term::NamedNode(view)) => (url.clone(), view.clone()),
+        _ => return None,
+    };
+    let shape =
+        objects(triples_by_subject, &triple.subject, vocabulary::TREE_SHAPE).find_map(|shape| {
+            match shape {
+                Term::NamedNode(iri) => Some(iri.clone()),
+                _ => None,
+            }
+        });
+    Some(Collection::new(url, view, members, shape))
+}
+
+/// The value of a literal or an IRI.
+fn value_of(term: &Term) -> Option<Value> {
+    match term {
+        Term::Literal(v) => Some(v.clone()),
+        Term::NamedNode(iri) => Some(Value {
+            value: iri.clone(),
+            value_type: ValueType::Iri,
+        }),
+        Term::BlankNode(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Page;
+    use crate::rdf::format::RdfFormat;
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::HashMap;
+
+    fn a_document() -> TreeDocument {
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                vec![String::from("https://example.com/member/0")],
+                Some(String::from("https://example.com/shape")),
+            ),
+            vec![Node::new(
+                vec![
+                    Relation::new(
+                        Some(3),
+                        Some(String::from("https://example.com/p")),
+                        Some(Value {
+                            value: String::from("5"),
+                            value_type: ValueType::Int,
+                        }),
+                        String::from("https://example.com/root/0"),
+                        Some(RelationOperator::GreaterThanRelation),
+                    ),
+                    Relation::new(
+                        None,
+                        None,
+                        None,
+                        String::from("https://example.com/root/1"),
+                        None,
+                    ),
+                ],
+                String::from("https://example.com/root"),
+                vec![Member {
+                    url: String::from("https://example.com/member/0"),
+                    properties: HashMap::from([
+                        (
+                            String::from("https://example.com/p"),
+                            Value {
+                                value: String::from("1"),
+                                value_type: ValueType::Int,
+                            },
+                        ),
+                        (
+                            String::from("https://example.com/type"),
+                            Value {
+                                value: String::from("https://example.com/Thing"),
+                                value_type: ValueType::Iri,
+                            },
+                        ),
+                    ]),
+                }],
+            )],
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn should_read_back_a_generated_page_in_every_format() {
+        let document = a_document();
+        let triples = document.page("https://example.com/root").unwrap();
+
+        for format in RdfFormat::ALL {
+            let parsed = format.parse(&format.serialize(&triples), None).unwrap();
+            let page = Page::from_triples(&parsed, "https://example.com/root").unwrap();
+
+            assert_eq!(page.node.relation(), document.nodes()[0].relation());
+            assert_eq!(page.node.members(), document.nodes()[0].members());
+            assert_eq!(page.collection.as_ref(), Some(document.collection()));
+        }
+    }
+
+    #[test]
+    fn should_read_a_page_written_by_hand() {
+        let turtle = r#"
+            @prefix tree: <https://w3id.org/tree#> .
+            @prefix ex: <https://example.com/> .
+            <> a tree:Node ;
+                tree:relation [
+                    a tree:PrefixRelation ;
+                    tree:path ( ex:a ex:b ) ;
+                    tree:value "ab" ;
+                    tree:node <1>
+                ] .
+            ex:collection tree:member ex:m .
+            ex:m ex:name "m"@en .
+        "#;
+        let triples = RdfFormat::Turtle
+            .parse(turtle, Some("https://example.com/page"))
+            .unwrap();
+
+        let page = Page::from_triples(&triples, "https://example.com/page").unwrap();
+
+        assert_eq!(
+            page.node.relation(),
+            &vec![Relation::new(
+                None,
+                None,
+                Some(Value {
+                    value: String::from("ab"),
+                    value_type: ValueType::String,
+                }),
+                String::from("https://example.com/1"),
+                Some(RelationOperator::PrefixRelation),
+            )]
+        );
+        assert_eq!(page.node.members().len(), 1);
+        assert!(page.collection.is_none());
+        assert!(Page::from_triples(&triples, "https://example.com/other").is_err());
+    }
+}
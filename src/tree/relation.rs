@@ -1,6 +1,9 @@
-use super::shacl_path::ShaclPath;
 use super::relation_operator::RelationOperator;
-use super::value::Value;
+use super::shacl_path::ShaclPath;
+use super::value::{Value, ValueType};
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
 use derive_getters;
 use derive_new;
 
@@ -18,3 +21,57 @@ pub struct Relation {
     /// The type of the relationship.
     relation_type: Option<RelationOperator>,
 }
+
+impl Relation {
+    /// The triples describing the relation of the node identified by `node_url`,
+    /// the relation itself being the blank node `blank_node`.
+    pub fn triples(&self, node_url: &str, blank_node: &str) -> Vec<Triple> {
+        let subject = Term::BlankNode(String::from(blank_node));
+        let relation_type = match &self.relation_type {
+            Some(v) => v.to_string(),
+            None => String::from(vocabulary::TREE_RELATION_CLASS),
+        };
+        let mut triples = vec![
+            Triple::new(
+                Term::NamedNode(String::from(node_url)),
+                String::from(vocabulary::TREE_RELATION),
+                subject.clone(),
+            ),
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(relation_type),
+            ),
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::TREE_NODE),
+                Term::NamedNode(self.node.clone()),
+            ),
+        ];
+        if let Some(path) = &self.path {
+            triples.push(Triple::new(
+                subject.clone(),
+                String::from(vocabulary::TREE_PATH),
+                Term::NamedNode(path.clone()),
+            ));
+        }
+        if let Some(value) = &self.value {
+            triples.push(Triple::new(
+                subject.clone(),
+                String::from(vocabulary::TREE_VALUE),
+                Term::from(value.clone()),
+            ));
+        }
+        if let Some(remaning_items) = self.remaning_items {
+            triples.push(Triple::new(
+                subject,
+                String::from(vocabulary::TREE_REMAINING_ITEMS),
+                Term::Literal(Value {
+                    value: remaning_items.to_string(),
+                    value_type: ValueType::Integer,
+                }),
+            ));
+        }
+        triples
+    }
+}
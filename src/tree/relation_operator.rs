@@ -56,3 +56,26 @@ impl fmt::Display for RelationOperator {
         write!(f, "{}", string_representation)
     }
 }
+
+impl RelationOperator {
+    /// All the operators.
+    pub const ALL: [RelationOperator; 9] = [
+        RelationOperator::PrefixRelation,
+        RelationOperator::SubstringRelation,
+        RelationOperator::SuffixRelation,
+        RelationOperator::GreaterThanRelation,
+        RelationOperator::GreaterThanOrEqualToRelation,
+        RelationOperator::LessThanRelation,
+        RelationOperator::LessThanOrEqualToRelation,
+        RelationOperator::EqualThanRelation,
+        RelationOperator::GeospatiallyContainsRelation,
+    ];
+
+    /// The operator identified by the IRI of its class, `None` if the IRI is not a TREE relation operator.
+    pub fn from_iri(iri: &str) -> Option<RelationOperator> {
+        RelationOperator::ALL
+            .iter()
+            .find(|operator| operator.to_string() == iri)
+            .cloned()
+    }
+}
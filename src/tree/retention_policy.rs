@@ -0,0 +1,373 @@
+use super::member::Member;
+use super::shacl_path::ShaclPath;
+use super::value::{Value, ValueType};
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
+use chrono::{DateTime, Duration, Months, NaiveDateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::vec::Vec;
+
+/// An [LDES retention policy](https://semiceu.github.io/LinkedDataEventStreams/#retention-policies)
+/// of the view, telling which members a server keeps.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RetentionPolicy {
+    /// Keep the members whose timestamp is more recent than the duration before now.
+    DurationAgo {
+        /// Path of the `xsd:dateTime` timestamp of the members.
+        timestamp_path: ShaclPath,
+        /// The `xsd:duration`, like `P1D`.
+        duration: String,
+    },
+    /// Keep the `amount` latest versions of every entity.
+    LatestVersionSubset {
+        /// Path of the `xsd:dateTime` timestamp of the members.
+        timestamp_path: ShaclPath,
+        /// Path of the entity of which a member is a version.
+        version_of_path: ShaclPath,
+        amount: usize,
+    },
+}
+
+impl RetentionPolicy {
+    /// Path of the `xsd:dateTime` timestamp of the members.
+    pub fn timestamp_path(&self) -> &ShaclPath {
+        match self {
+            RetentionPolicy::DurationAgo { timestamp_path, .. }
+            | RetentionPolicy::LatestVersionSubset { timestamp_path, .. } => timestamp_path,
+        }
+    }
+
+    /// Path of the entity of which a member is a version, if the policy has one.
+    pub fn version_of_path(&self) -> Option<&ShaclPath> {
+        match self {
+            RetentionPolicy::DurationAgo { .. } => None,
+            RetentionPolicy::LatestVersionSubset {
+                version_of_path, ..
+            } => Some(version_of_path),
+        }
+    }
+
+    /// The triples linking the view to the policy, the policy being the blank node `blank_node`.
+    /// The paths of the collection are described by [`collection_triples`].
+    pub fn triples(&self, view_url: &str, blank_node: &str) -> Vec<Triple> {
+        let subject = Term::BlankNode(String::from(blank_node));
+        let class = match self {
+            RetentionPolicy::DurationAgo { .. } => vocabulary::LDES_DURATION_AGO_POLICY,
+            RetentionPolicy::LatestVersionSubset { .. } => vocabulary::LDES_LATEST_VERSION_SUBSET,
+        };
+        let mut triples = vec![
+            Triple::new(
+                Term::NamedNode(String::from(view_url)),
+                String::from(vocabulary::LDES_RETENTION_POLICY),
+                subject.clone(),
+            ),
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(class)),
+            ),
+        ];
+        match self {
+            RetentionPolicy::DurationAgo { duration, .. } => triples.push(Triple::new(
+                subject,
+                String::from(vocabulary::TREE_VALUE),
+                Term::Literal(Value {
+                    value: duration.clone(),
+                    value_type: ValueType::Duration,
+                }),
+            )),
+            RetentionPolicy::LatestVersionSubset { amount, .. } => triples.push(Triple::new(
+                subject,
+                String::from(vocabulary::LDES_AMOUNT),
+                Term::Literal(Value {
+                    value: amount.to_string(),
+                    value_type: ValueType::Integer,
+                }),
+            )),
+        }
+        triples
+    }
+
+    /// The urls of the members expired at `now`. The members without a valid timestamp,
+    /// and for the latest version subset without a version, are kept.
+    pub fn expired_members(
+        &self,
+        members: &[&Member],
+        now: DateTime<Utc>,
+    ) -> Result<HashSet<String>, &'static str> {
+        match self {
+            RetentionPolicy::DurationAgo {
+                timestamp_path,
+                duration,
+            } => {
+                let (months, rest) =
+                    parse_duration(duration).ok_or("the duration of the policy is invalid")?;
+                let oldest = now
+                    .checked_sub_months(Months::new(months))
+                    .and_then(|v| v.checked_sub_signed(rest))
+                    .ok_or("the duration of the policy is out of range")?;
+                Ok(members
+                    .iter()
+                    .filter(|member| {
+                        timestamp_of(member, timestamp_path).is_some_and(|v| v < oldest)
+                    })
+                    .map(|member| member.url.clone())
+                    .collect())
+            }
+            RetentionPolicy::LatestVersionSubset {
+                timestamp_path,
+                version_of_path,
+                amount,
+            } => {
+                let mut versions: HashMap<&Value, Vec<&Member>> = HashMap::new();
+                for member in members {
+                    if let Some(entity) = member.properties.get(version_of_path) {
+                        versions.entry(entity).or_default().push(member);
+                    }
+                }
+                let mut expired: HashSet<String> = HashSet::new();
+                for versions_of_the_entity in versions.values_mut() {
+                    // the latest versions first, the versions without a timestamp being the latest
+                    versions_of_the_entity.sort_by(|a, b| {
+                        match (
+                            timestamp_of(a, timestamp_path),
+                            timestamp_of(b, timestamp_path),
+                        ) {
+                            (Some(a), Some(b)) => b.cmp(&a),
+                            (None, Some(_)) => Ordering::Less,
+                            (Some(_), None) => Ordering::Greater,
+                            (None, None) => Ordering::Equal,
+                        }
+                    });
+                    expired.extend(
+                        versions_of_the_entity
+                            .iter()
+                            .skip(*amount)
+                            .filter(|member| timestamp_of(member, timestamp_path).is_some())
+                            .map(|member| member.url.clone()),
+                    );
+                }
+                Ok(expired)
+            }
+        }
+    }
+}
+
+/// The triples describing the collection as an event stream with the timestamp path
+/// and the version path shared by the `retention_policies`.
+/// Fail if the policies disagree on one of the paths.
+pub fn collection_triples(
+    collection_url: &str,
+    retention_policies: &[RetentionPolicy],
+) -> Result<Vec<Triple>, &'static str> {
+    let Some(first) = retention_policies.first() else {
+        return Ok(Vec::new());
+    };
+    if retention_policies
+        .iter()
+        .any(|policy| policy.timestamp_path() != first.timestamp_path())
+    {
+        return Err("the retention policies have different timestamp paths");
+    }
+    let version_of_paths: HashSet<&ShaclPath> = retention_policies
+        .iter()
+        .filter_map(RetentionPolicy::version_of_path)
+        .collect();
+    if version_of_paths.len() > 1 {
+        return Err("the retention policies have different version paths");
+    }
+    let collection = Term::NamedNode(String::from(collection_url));
+    let mut triples = vec![
+        Triple::new(
+            collection.clone(),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::LDES_EVENT_STREAM)),
+        ),
+        Triple::new(
+            collection.clone(),
+            String::from(vocabulary::LDES_TIMESTAMP_PATH),
+            Term::NamedNode(first.timestamp_path().clone()),
+        ),
+    ];
+    triples.extend(version_of_paths.into_iter().map(|version_of_path| {
+        Triple::new(
+            collection.clone(),
+            String::from(vocabulary::LDES_VERSION_OF_PATH),
+            Term::NamedNode(version_of_path.clone()),
+        )
+    }));
+    Ok(triples)
+}
+
+/// The timestamp of a member, a date time without timezone being in UTC.
+fn timestamp_of(member: &Member, timestamp_path: &ShaclPath) -> Option<DateTime<Utc>> {
+    let value = member.properties.get(timestamp_path)?;
+    if value.value_type != ValueType::DateTime {
+        return None;
+    }
+    DateTime::parse_from_rfc3339(&value.value)
+        .map(|v| v.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(&value.value, "%Y-%m-%dT%H:%M:%S%.f").map(|v| v.and_utc())
+        })
+        .ok()
+}
+
+/// The number of months and the rest of a positive `xsd:duration`.
+fn parse_duration(lexical_form: &str) -> Option<(u32, Duration)> {
+    let rest = lexical_form.strip_prefix('P')?;
+    let (date, time) = match rest.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (rest, None),
+    };
+    if date.is_empty() && time.is_none() {
+        return None;
+    }
+    let mut months: u32 = 0;
+    let mut seconds: f64 = 0.0;
+    for (component, unit) in components(date)? {
+        let count: u32 = component.parse().ok()?;
+        match unit {
+            'Y' => months = months.checked_add(count.checked_mul(12)?)?,
+            'M' => months = months.checked_add(count)?,
+            'D' => seconds += f64::from(count) * 86_400.0,
+            _ => return None,
+        }
+    }
+    for (component, unit) in components(time.unwrap_or(""))? {
+        let count: f64 = if unit == 'S' {
+            component.parse().ok()?
+        } else {
+            f64::from(component.parse::<u32>().ok()?)
+        };
+        match unit {
+            'H' => seconds += count * 3600.0,
+            'M' => seconds += count * 60.0,
+            'S' => seconds += count,
+            _ => return None,
+        }
+    }
+    Some((
+        months,
+        Duration::milliseconds((seconds * 1000.0).round() as i64),
+    ))
+}
+
+/// The numbers of a part of a duration with their unit, in order.
+fn components(part: &str) -> Option<Vec<(&str, char)>> {
+    let mut components: Vec<(&str, char)> = Vec::new();
+    let mut start = 0;
+    for (i, character) in part.char_indices() {
+        if character.is_ascii_alphabetic() {
+            if i == start {
+                return None;
+            }
+            components.push((&part[start..i], character));
+            start = i + 1;
+        }
+    }
+    if start != part.len() {
+        return None;
+    }
+    Some(components)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_duration, RetentionPolicy};
+    use crate::tree::member::Member;
+    use crate::tree::value::{Value, ValueType};
+    use chrono::{Duration, TimeZone, Utc};
+    use std::collections::{HashMap, HashSet};
+
+    const TIMESTAMP_PATH: &str = "http://purl.org/dc/terms/created";
+    const VERSION_OF_PATH: &str = "http://purl.org/dc/terms/isVersionOf";
+
+    fn a_version(i: usize, entity: &str, timestamp: &str) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([
+                (
+                    String::from(TIMESTAMP_PATH),
+                    Value {
+                        value: String::from(timestamp),
+                        value_type: ValueType::DateTime,
+                    },
+                ),
+                (
+                    String::from(VERSION_OF_PATH),
+                    Value {
+                        value: format!("https://example.com/{}", entity),
+                        value_type: ValueType::Iri,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    fn urls(members: &[usize]) -> HashSet<String> {
+        members
+            .iter()
+            .map(|i| format!("https://example.com/member/{}", i))
+            .collect()
+    }
+
+    #[test]
+    fn should_parse_a_duration() {
+        assert_eq!(
+            parse_duration("P1Y2M3DT4H5M6.5S"),
+            Some((14, Duration::milliseconds(273_906_500)))
+        );
+        assert_eq!(parse_duration("PT1H"), Some((0, Duration::hours(1))));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("P1DT"), None);
+        assert_eq!(parse_duration("-P1D"), None);
+    }
+
+    #[test]
+    fn should_expire_the_old_members() {
+        let members = [
+            a_version(0, "a", "2022-01-01T00:00:00Z"),
+            a_version(1, "a", "2022-01-09T00:00:00Z"),
+            a_version(2, "b", "2022-01-09T12:00:00"),
+            a_version(3, "b", "not a date"),
+        ];
+        let policy = RetentionPolicy::DurationAgo {
+            timestamp_path: String::from(TIMESTAMP_PATH),
+            duration: String::from("P1D"),
+        };
+
+        let expired = policy
+            .expired_members(
+                &members.iter().collect::<Vec<&Member>>(),
+                Utc.with_ymd_and_hms(2022, 1, 10, 6, 0, 0).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(expired, urls(&[0, 1]));
+    }
+
+    #[test]
+    fn should_keep_the_latest_versions() {
+        let members = [
+            a_version(0, "a", "2022-01-01T00:00:00Z"),
+            a_version(1, "a", "2022-01-03T00:00:00Z"),
+            a_version(2, "a", "2022-01-02T00:00:00Z"),
+            a_version(3, "b", "2022-01-01T00:00:00Z"),
+        ];
+        let policy = RetentionPolicy::LatestVersionSubset {
+            timestamp_path: String::from(TIMESTAMP_PATH),
+            version_of_path: String::from(VERSION_OF_PATH),
+            amount: 2,
+        };
+
+        let expired = policy
+            .expired_members(&members.iter().collect::<Vec<&Member>>(), Utc::now())
+            .unwrap();
+
+        assert_eq!(expired, urls(&[0]));
+    }
+}
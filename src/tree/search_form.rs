@@ -0,0 +1,159 @@
+use super::shacl_path::ShaclPath;
+use super::value::{Value, ValueType};
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
+use derive_getters;
+use derive_new;
+use std::vec::Vec;
+
+/// A [search form](https://treecg.github.io/specification/#searching) of a node,
+/// a [hydra:IriTemplate](https://www.hydra-cg.com/spec/latest/core/#templated-links)
+/// with a single variable mapped to a property of the members.
+#[derive(derive_new::new, Clone, derive_getters::Getters, Debug, PartialEq, Eq)]
+pub struct SearchForm {
+    /// Url of the template without the query, e.g. `https://example.com/node`.
+    base_url: String,
+    /// Name of the variable of the template.
+    variable: String,
+    /// Path of the property of the members that the variable is mapped to.
+    property: ShaclPath,
+}
+
+impl SearchForm {
+    /// The [URI template](https://www.rfc-editor.org/rfc/rfc6570) of the form,
+    /// using a form-style query expansion, e.g. `https://example.com/node{?value}`.
+    pub fn template(&self) -> String {
+        format!("{}{{?{}}}", self.base_url, self.variable)
+    }
+
+    /// Expand the template with the lexical form of a value.
+    pub fn expand(&self, value: &str) -> String {
+        format!(
+            "{}?{}={}",
+            self.base_url,
+            percent_encode(&self.variable),
+            percent_encode(value)
+        )
+    }
+
+    /// The triples describing the search form of the node identified by `node_url`.
+    pub fn triples(&self, node_url: &str) -> Vec<Triple> {
+        let form = Term::BlankNode(String::from("search"));
+        let mapping = Term::BlankNode(String::from("search_mapping"));
+        let string = |value: String| {
+            Term::Literal(Value {
+                value,
+                value_type: ValueType::String,
+            })
+        };
+        vec![
+            Triple::new(
+                Term::NamedNode(String::from(node_url)),
+                String::from(vocabulary::TREE_SEARCH),
+                form.clone(),
+            ),
+            Triple::new(
+                form.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::HYDRA_IRI_TEMPLATE)),
+            ),
+            Triple::new(
+                form.clone(),
+                String::from(vocabulary::HYDRA_TEMPLATE),
+                string(self.template()),
+            ),
+            Triple::new(
+                form.clone(),
+                String::from(vocabulary::HYDRA_VARIABLE_REPRESENTATION),
+                Term::NamedNode(String::from(vocabulary::HYDRA_BASIC_REPRESENTATION)),
+            ),
+            Triple::new(
+                form,
+                String::from(vocabulary::HYDRA_MAPPING),
+                mapping.clone(),
+            ),
+            Triple::new(
+                mapping.clone(),
+                String::from(vocabulary::RDF_TYPE),
+                Term::NamedNode(String::from(vocabulary::HYDRA_IRI_TEMPLATE_MAPPING)),
+            ),
+            Triple::new(
+                mapping.clone(),
+                String::from(vocabulary::HYDRA_VARIABLE),
+                string(self.variable.clone()),
+            ),
+            Triple::new(
+                mapping.clone(),
+                String::from(vocabulary::HYDRA_PROPERTY),
+                Term::NamedNode(self.property.clone()),
+            ),
+            Triple::new(
+                mapping,
+                String::from(vocabulary::HYDRA_REQUIRED),
+                Term::Literal(Value {
+                    value: String::from("true"),
+                    value_type: ValueType::Boolean,
+                }),
+            ),
+        ]
+    }
+}
+
+/// Percent-encode every character that is not
+/// [unreserved](https://www.rfc-editor.org/rfc/rfc3986#section-2.3).
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchForm;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+
+    fn a_search_form() -> SearchForm {
+        SearchForm::new(
+            String::from("https://example.com/node"),
+            String::from("value"),
+            String::from("https://example.com/p"),
+        )
+    }
+
+    #[test]
+    fn should_expand_the_template_with_an_encoded_value() {
+        let form = a_search_form();
+
+        assert_eq!(form.template(), "https://example.com/node{?value}");
+        assert_eq!(form.expand("12"), "https://example.com/node?value=12");
+        assert_eq!(
+            form.expand("2022-01-01T00:00:00+01:00"),
+            "https://example.com/node?value=2022-01-01T00%3A00%3A00%2B01%3A00"
+        );
+    }
+
+    #[test]
+    fn should_map_the_variable_to_the_property() {
+        let triples = a_search_form().triples("https://example.com/root");
+
+        assert!(triples.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/root")),
+            String::from(vocabulary::TREE_SEARCH),
+            Term::BlankNode(String::from("search")),
+        )));
+        assert!(triples.contains(&Triple::new(
+            Term::BlankNode(String::from("search_mapping")),
+            String::from(vocabulary::HYDRA_PROPERTY),
+            Term::NamedNode(String::from("https://example.com/p")),
+        )));
+    }
+}
@@ -0,0 +1,131 @@
+use super::shacl_path::ShaclPath;
+use super::value::{Value, ValueType};
+use crate::rdf::term::Term;
+use crate::rdf::triple::Triple;
+use crate::rdf::vocabulary;
+use derive_getters;
+use derive_new;
+use std::vec::Vec;
+
+/// A [SHACL node shape](https://www.w3.org/TR/shacl/#node-shapes) describing the
+/// [members](`super::member::Member`) of a collection, the `tree:shape` of the collection.
+#[derive(derive_new::new, Clone, derive_getters::Getters, Debug, PartialEq, Eq)]
+pub struct NodeShape {
+    /// IRI of the shape.
+    url: String,
+    /// The property constraints of the members.
+    properties: Vec<PropertyShape>,
+}
+
+/// A [SHACL property shape](https://www.w3.org/TR/shacl/#property-shapes) for a property
+/// present exactly once in every member.
+#[derive(derive_new::new, Clone, derive_getters::Getters, Debug, PartialEq, Eq)]
+pub struct PropertyShape {
+    /// Path of the property.
+    path: ShaclPath,
+    /// Type of the value of the property.
+    datatype: ValueType,
+}
+
+impl NodeShape {
+    /// The triples describing the shape, the property shapes being blank nodes.
+    pub fn triples(&self) -> Vec<Triple> {
+        let subject = Term::NamedNode(self.url.clone());
+        let mut triples = vec![Triple::new(
+            subject.clone(),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::SH_NODE_SHAPE)),
+        )];
+        for (i, property) in self.properties.iter().enumerate() {
+            let property_subject = Term::BlankNode(format!("property{}", i));
+            triples.push(Triple::new(
+                subject.clone(),
+                String::from(vocabulary::SH_PROPERTY),
+                property_subject.clone(),
+            ));
+            triples.extend(property.triples(property_subject));
+        }
+        triples
+    }
+}
+
+impl PropertyShape {
+    /// The triples describing the property shape identified by `subject`.
+    pub fn triples(&self, subject: Term) -> Vec<Triple> {
+        let cardinality = |predicate: &str| {
+            Triple::new(
+                subject.clone(),
+                String::from(predicate),
+                Term::Literal(Value {
+                    value: String::from("1"),
+                    value_type: ValueType::Integer,
+                }),
+            )
+        };
+        let value_constraint = match &self.datatype {
+            ValueType::Iri => Triple::new(
+                subject.clone(),
+                String::from(vocabulary::SH_NODE_KIND),
+                Term::NamedNode(String::from(vocabulary::SH_IRI)),
+            ),
+            datatype => Triple::new(
+                subject.clone(),
+                String::from(vocabulary::SH_DATATYPE),
+                Term::NamedNode(datatype.to_string()),
+            ),
+        };
+        vec![
+            Triple::new(
+                subject.clone(),
+                String::from(vocabulary::SH_PATH),
+                Term::NamedNode(self.path.clone()),
+            ),
+            value_constraint,
+            cardinality(vocabulary::SH_MIN_COUNT),
+            cardinality(vocabulary::SH_MAX_COUNT),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NodeShape, PropertyShape};
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+    use crate::tree::value::ValueType;
+
+    #[test]
+    fn should_describe_the_datatype_and_the_path_of_the_properties() {
+        let shape = NodeShape::new(
+            String::from("https://example.com/shape"),
+            vec![
+                PropertyShape::new(String::from("https://example.com/a"), ValueType::Int),
+                PropertyShape::new(String::from("https://example.com/b"), ValueType::Iri),
+            ],
+        );
+
+        let triples = shape.triples();
+
+        assert!(triples.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/shape")),
+            String::from(vocabulary::SH_PROPERTY),
+            Term::BlankNode(String::from("property1")),
+        )));
+        assert!(triples.contains(&Triple::new(
+            Term::BlankNode(String::from("property0")),
+            String::from(vocabulary::SH_PATH),
+            Term::NamedNode(String::from("https://example.com/a")),
+        )));
+        assert!(triples.contains(&Triple::new(
+            Term::BlankNode(String::from("property0")),
+            String::from(vocabulary::SH_DATATYPE),
+            Term::NamedNode(String::from("http://www.w3.org/2001/XMLSchema#int")),
+        )));
+        assert!(triples.contains(&Triple::new(
+            Term::BlankNode(String::from("property1")),
+            String::from(vocabulary::SH_NODE_KIND),
+            Term::NamedNode(String::from(vocabulary::SH_IRI)),
+        )));
+    }
+}
@@ -0,0 +1,272 @@
+use super::collection::Collection;
+use super::node::Node;
+use super::retention_policy::{self, RetentionPolicy};
+use super::search_form::SearchForm;
+use super::shape::NodeShape;
+use crate::rdf::triple::Triple;
+use derive_getters;
+use derive_new;
+use std::vec::Vec;
+
+/// A generated TREE document, a [`Collection`] with the [nodes](`Node`) describing it.
+#[derive(derive_new::new, Clone, derive_getters::Getters)]
+pub struct TreeDocument {
+    /// The collection, entry point of the document.
+    collection: Collection,
+    /// All the nodes, starting with the root node.
+    nodes: Vec<Node>,
+    /// The shape of the members of the collection.
+    shape: Option<NodeShape>,
+    /// The search form of the root node.
+    search_form: Option<SearchForm>,
+    /// The retention policies of the view, described on the page of the root node.
+    #[new(default)]
+    retention_policies: Vec<RetentionPolicy>,
+}
+
+impl TreeDocument {
+    /// The document with the retention policies.
+    /// Fail if the policies disagree on the timestamp or the version path of the collection.
+    pub fn with_retention_policies(
+        mut self,
+        retention_policies: Vec<RetentionPolicy>,
+    ) -> Result<Self, &'static str> {
+        retention_policy::collection_triples(self.collection.url(), &retention_policies)?;
+        self.retention_policies = retention_policies;
+        Ok(self)
+    }
+
+    /// The triples of the HTTP page of a node: the node itself, the `tree:member` links
+    /// of the collection to the members of the node and, for the root node,
+    /// the description of the collection, of its shape, of the search form and of the retention policies.
+    /// Return `None` if the node does not exist.
+    pub fn page(&self, node_url: &str) -> Option<Vec<Triple>> {
+        let node = self.nodes.iter().find(|node| node.node_url() == node_url)?;
+        let mut triples = Vec::new();
+        if self.collection.view() == node_url {
+            triples.extend(self.collection.view_triples());
+            if let Some(shape) = &self.shape {
+                triples.extend(shape.triples());
+            }
+            if let Some(search_form) = &self.search_form {
+                triples.extend(search_form.triples(node_url));
+            }
+            triples.extend(
+                retention_policy::collection_triples(
+                    self.collection.url(),
+                    &self.retention_policies,
+                )
+                .expect("the retention policies are checked when they are set"),
+            );
+            for (i, retention_policy) in self.retention_policies.iter().enumerate() {
+                triples
+                    .extend(retention_policy.triples(node_url, &format!("retentionPolicy{}", i)));
+            }
+        }
+        triples.extend(
+            self.collection
+                .member_triples(node.members().iter().map(|member| &member.url)),
+        );
+        triples.extend(node.triples());
+        Some(triples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TreeDocument;
+    use crate::rdf::term::Term;
+    use crate::rdf::triple::Triple;
+    use crate::rdf::vocabulary;
+    use crate::tree::collection::Collection;
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::retention_policy::RetentionPolicy;
+    use crate::tree::search_form::SearchForm;
+    use crate::tree::shape::NodeShape;
+    use std::collections::HashMap;
+
+    fn a_document() -> TreeDocument {
+        let member = Member {
+            url: String::from("https://example.com/member"),
+            properties: HashMap::new(),
+        };
+        let root = Node::new(
+            vec![Relation::new(
+                None,
+                None,
+                None,
+                String::from("https://example.com/leaf"),
+                None,
+            )],
+            String::from("https://example.com/root"),
+            Vec::new(),
+        );
+        let leaf = Node::new(
+            Vec::new(),
+            String::from("https://example.com/leaf"),
+            vec![member],
+        );
+        TreeDocument::new(
+            Collection::new(
+                String::from("https://example.com/collection"),
+                String::from("https://example.com/root"),
+                vec![String::from("https://example.com/member")],
+                Some(String::from("https://example.com/shape")),
+            ),
+            vec![root, leaf],
+            Some(NodeShape::new(
+                String::from("https://example.com/shape"),
+                Vec::new(),
+            )),
+            Some(SearchForm::new(
+                String::from("https://example.com/node"),
+                String::from("value"),
+                String::from("https://example.com/p"),
+            )),
+        )
+    }
+
+    #[test]
+    fn should_describe_the_collection_in_the_page_of_the_root_node() {
+        let page = a_document().page("https://example.com/root").unwrap();
+
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/collection")),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::TREE_COLLECTION)),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/collection")),
+            String::from(vocabulary::TREE_VIEW),
+            Term::NamedNode(String::from("https://example.com/root")),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/collection")),
+            String::from(vocabulary::TREE_SHAPE),
+            Term::NamedNode(String::from("https://example.com/shape")),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/shape")),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::SH_NODE_SHAPE)),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/root")),
+            String::from(vocabulary::TREE_SEARCH),
+            Term::BlankNode(String::from("search")),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::BlankNode(String::from("relation0")),
+            String::from(vocabulary::RDF_TYPE),
+            Term::NamedNode(String::from(vocabulary::TREE_RELATION_CLASS)),
+        )));
+        assert!(!page
+            .iter()
+            .any(|triple| triple.predicate == vocabulary::TREE_MEMBER));
+    }
+
+    #[test]
+    fn should_link_the_collection_to_the_members_of_the_page() {
+        let page = a_document().page("https://example.com/leaf").unwrap();
+
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/collection")),
+            String::from(vocabulary::TREE_MEMBER),
+            Term::NamedNode(String::from("https://example.com/member")),
+        )));
+        assert!(!page.iter().any(|triple| {
+            triple.predicate == vocabulary::TREE_VIEW
+                || triple.predicate == vocabulary::TREE_SHAPE
+                || triple.predicate == vocabulary::TREE_SEARCH
+        }));
+    }
+
+    #[test]
+    fn should_describe_the_retention_policies_in_the_page_of_the_root_node() {
+        let document = a_document()
+            .with_retention_policies(vec![RetentionPolicy::DurationAgo {
+                timestamp_path: String::from("https://example.com/created"),
+                duration: String::from("P1D"),
+            }])
+            .unwrap();
+
+        let page = document.page("https://example.com/root").unwrap();
+
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/root")),
+            String::from(vocabulary::LDES_RETENTION_POLICY),
+            Term::BlankNode(String::from("retentionPolicy0")),
+        )));
+        assert!(page.contains(&Triple::new(
+            Term::NamedNode(String::from("https://example.com/collection")),
+            String::from(vocabulary::LDES_TIMESTAMP_PATH),
+            Term::NamedNode(String::from("https://example.com/created")),
+        )));
+        assert!(!document
+            .page("https://example.com/leaf")
+            .unwrap()
+            .iter()
+            .any(|triple| triple.predicate == vocabulary::LDES_RETENTION_POLICY));
+    }
+
+    #[test]
+    fn should_describe_the_collection_once_for_all_the_retention_policies() {
+        let document = a_document()
+            .with_retention_policies(vec![
+                RetentionPolicy::DurationAgo {
+                    timestamp_path: String::from("https://example.com/created"),
+                    duration: String::from("P1D"),
+                },
+                RetentionPolicy::LatestVersionSubset {
+                    timestamp_path: String::from("https://example.com/created"),
+                    version_of_path: String::from("https://example.com/isVersionOf"),
+                    amount: 2,
+                },
+            ])
+            .unwrap();
+
+        let page = document.page("https://example.com/root").unwrap();
+
+        for predicate in [
+            vocabulary::RDF_TYPE,
+            vocabulary::LDES_TIMESTAMP_PATH,
+            vocabulary::LDES_VERSION_OF_PATH,
+        ] {
+            assert_eq!(
+                page.iter()
+                    .filter(|triple| triple.subject
+                        == Term::NamedNode(String::from("https://example.com/collection"))
+                        && triple.predicate == predicate
+                        && (predicate != vocabulary::RDF_TYPE
+                            || triple.object
+                                == Term::NamedNode(String::from(vocabulary::LDES_EVENT_STREAM))))
+                    .count(),
+                1
+            );
+        }
+    }
+
+    #[test]
+    fn should_reject_retention_policies_with_different_timestamp_paths() {
+        assert!(a_document()
+            .with_retention_policies(vec![
+                RetentionPolicy::DurationAgo {
+                    timestamp_path: String::from("https://example.com/created"),
+                    duration: String::from("P1D"),
+                },
+                RetentionPolicy::LatestVersionSubset {
+                    timestamp_path: String::from("https://example.com/modified"),
+                    version_of_path: String::from("https://example.com/isVersionOf"),
+                    amount: 2,
+                },
+            ])
+            .is_err());
+    }
+
+    #[test]
+    fn should_not_return_the_page_of_an_unknown_node() {
+        assert!(a_document().page("https://example.com/unknown").is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
 /// Description of the value of [Relation](`super::relation::Relation`)
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Value {
     /// Raw value as presented in RDF.
     pub value: String,
@@ -9,7 +9,7 @@ pub struct Value {
     pub value_type: ValueType,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// Supported operand data type by [SPARQL](https://www.w3.org/TR/sparql11-query/#operandDataTypes)
 pub enum ValueType {
     Integer,
@@ -19,6 +19,10 @@ pub enum ValueType {
     String,
     Boolean,
     DateTime,
+    Date,
+    Time,
+    GYear,
+    Duration,
 
     NonPositiveInteger,
     NegativeInteger,
@@ -32,6 +36,13 @@ pub enum ValueType {
     UnsignedShort,
     UnsignedByte,
     PositiveInteger,
+
+    /// A string with a [language tag](https://www.rfc-editor.org/rfc/bcp/bcp47.txt).
+    LangString(String),
+    /// A literal of an arbitrary datatype identified by its IRI.
+    Custom(String),
+    /// An IRI rather than a literal.
+    Iri,
 }
 
 impl fmt::Display for ValueType {
@@ -45,6 +56,10 @@ impl fmt::Display for ValueType {
                 ValueType::String => "http://www.w3.org/2001/XMLSchema#string",
                 ValueType::Boolean => "http://www.w3.org/2001/XMLSchema#boolean",
                 ValueType::DateTime => "http://www.w3.org/2001/XMLSchema#dateTime",
+                ValueType::Date => "http://www.w3.org/2001/XMLSchema#date",
+                ValueType::Time => "http://www.w3.org/2001/XMLSchema#time",
+                ValueType::GYear => "http://www.w3.org/2001/XMLSchema#gYear",
+                ValueType::Duration => "http://www.w3.org/2001/XMLSchema#duration",
 
                 ValueType::NonPositiveInteger => {
                     "http://www.w3.org/2001/XMLSchema#nonPositiveInteger"
@@ -57,14 +72,129 @@ impl fmt::Display for ValueType {
                 ValueType::NonNegativeInteger => {
                     "http://www.w3.org/2001/XMLSchema#nonNegativeInteger"
                 }
-                ValueType::UnsignedLong => "http://www.w3.org/2001/XMLSchema#nunsignedLong",
+                ValueType::UnsignedLong => "http://www.w3.org/2001/XMLSchema#unsignedLong",
                 ValueType::UnsignedInt => "http://www.w3.org/2001/XMLSchema#unsignedInt",
                 ValueType::UnsignedShort => "http://www.w3.org/2001/XMLSchema#unsignedShort",
                 ValueType::UnsignedByte => "http://www.w3.org/2001/XMLSchema#unsignedByte",
                 ValueType::PositiveInteger => "http://www.w3.org/2001/XMLSchema#positiveInteger",
+
+                ValueType::LangString(_) => "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString",
+                ValueType::Custom(datatype) => datatype,
+                // an IRI has no datatype, it is identified by its SHACL node kind
+                ValueType::Iri => "http://www.w3.org/ns/shacl#IRI",
             }
         };
 
         write!(f, "{}", string_representation)
     }
 }
+
+/// The value types identified by an XSD datatype.
+const XSD_VALUE_TYPES: [ValueType; 23] = [
+    ValueType::Integer,
+    ValueType::Decimal,
+    ValueType::Float,
+    ValueType::Double,
+    ValueType::String,
+    ValueType::Boolean,
+    ValueType::DateTime,
+    ValueType::Date,
+    ValueType::Time,
+    ValueType::GYear,
+    ValueType::Duration,
+    ValueType::NonPositiveInteger,
+    ValueType::NegativeInteger,
+    ValueType::Long,
+    ValueType::Int,
+    ValueType::Short,
+    ValueType::Byte,
+    ValueType::NonNegativeInteger,
+    ValueType::UnsignedLong,
+    ValueType::UnsignedInt,
+    ValueType::UnsignedShort,
+    ValueType::UnsignedByte,
+    ValueType::PositiveInteger,
+];
+
+impl ValueType {
+    /// The value type of a literal of the datatype, [`ValueType::Custom`] when it is not an XSD datatype.
+    /// A language-tagged string is identified by its tag rather than by its datatype.
+    pub fn from_datatype(datatype: &str) -> ValueType {
+        XSD_VALUE_TYPES
+            .iter()
+            .find(|value_type| value_type.to_string() == datatype)
+            .cloned()
+            .unwrap_or_else(|| ValueType::Custom(String::from(datatype)))
+    }
+}
+
+impl fmt::Display for Value {
+    /// Serialize the value as an [N-Triples](https://www.w3.org/TR/n-triples/) term.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.value_type {
+            ValueType::Iri => write!(f, "<{}>", self.value),
+            ValueType::LangString(language) => {
+                write!(f, "\"{}\"@{}", escape_literal(&self.value), language)
+            }
+            value_type => write!(f, "\"{}\"^^<{}>", escape_literal(&self.value), value_type),
+        }
+    }
+}
+
+/// Escape the characters that cannot appear as is inside a quoted literal.
+pub(crate) fn escape_literal(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for character in literal.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Value, ValueType};
+
+    #[test]
+    fn should_serialize_a_typed_literal() {
+        let value = Value {
+            value: String::from("a \"quoted\"\nvalue"),
+            value_type: ValueType::String,
+        };
+        assert_eq!(
+            value.to_string(),
+            "\"a \\\"quoted\\\"\\nvalue\"^^<http://www.w3.org/2001/XMLSchema#string>"
+        );
+    }
+
+    #[test]
+    fn should_serialize_a_custom_datatype_a_language_tag_and_an_iri() {
+        let custom = Value {
+            value: String::from("POINT(4.3 50.8)"),
+            value_type: ValueType::Custom(String::from(
+                "http://www.opengis.net/ont/geosparql#wktLiteral",
+            )),
+        };
+        assert_eq!(
+            custom.to_string(),
+            "\"POINT(4.3 50.8)\"^^<http://www.opengis.net/ont/geosparql#wktLiteral>"
+        );
+
+        let lang_string = Value {
+            value: String::from("chat"),
+            value_type: ValueType::LangString(String::from("fr")),
+        };
+        assert_eq!(lang_string.to_string(), "\"chat\"@fr");
+
+        let iri = Value {
+            value: String::from("https://example.com/a"),
+            value_type: ValueType::Iri,
+        };
+        assert_eq!(iri.to_string(), "<https://example.com/a>");
+    }
+}
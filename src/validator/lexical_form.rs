@@ -0,0 +1,259 @@
+use crate::sparql_converter::{integer_bounds, is_absolute_iri, is_valid_language_tag};
+use crate::tree::value::{Value, ValueType};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Check that the lexical form of the value belongs to the
+/// [lexical space](https://www.w3.org/TR/xmlschema11-2/#lexical-space) of its value type.
+/// The strings and the custom datatypes are always valid.
+pub fn check_lexical_form(value: &Value) -> Result<(), &'static str> {
+    let lexical_form = value.value.as_str();
+    let is_valid = match &value.value_type {
+        ValueType::Integer
+        | ValueType::NonPositiveInteger
+        | ValueType::NegativeInteger
+        | ValueType::Long
+        | ValueType::Int
+        | ValueType::Short
+        | ValueType::Byte
+        | ValueType::NonNegativeInteger
+        | ValueType::UnsignedLong
+        | ValueType::UnsignedInt
+        | ValueType::UnsignedShort
+        | ValueType::UnsignedByte
+        | ValueType::PositiveInteger => {
+            return check_integer(lexical_form, &value.value_type);
+        }
+        ValueType::Decimal => is_decimal(lexical_form),
+        ValueType::Float | ValueType::Double => {
+            matches!(lexical_form, "INF" | "+INF" | "-INF" | "NaN")
+                || is_floating_point(lexical_form)
+        }
+        ValueType::Boolean => matches!(lexical_form, "true" | "false" | "1" | "0"),
+        ValueType::DateTime => {
+            DateTime::parse_from_rfc3339(lexical_form).is_ok()
+                || NaiveDateTime::parse_from_str(lexical_form, "%Y-%m-%dT%H:%M:%S%.f").is_ok()
+        }
+        ValueType::Date => {
+            NaiveDate::parse_from_str(without_timezone(lexical_form), "%Y-%m-%d").is_ok()
+        }
+        ValueType::Time => {
+            NaiveTime::parse_from_str(without_timezone(lexical_form), "%H:%M:%S%.f").is_ok()
+        }
+        ValueType::GYear => is_year(without_timezone(lexical_form)),
+        ValueType::Duration => is_duration(lexical_form),
+        ValueType::LangString(language) => {
+            if !is_valid_language_tag(language) {
+                return Err("invalid language tag");
+            }
+            true
+        }
+        ValueType::Iri => is_absolute_iri(lexical_form),
+        ValueType::String | ValueType::Custom(_) => true,
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err("the lexical form does not belong to the datatype")
+    }
+}
+
+fn check_integer(lexical_form: &str, value_type: &ValueType) -> Result<(), &'static str> {
+    let digits = lexical_form
+        .strip_prefix(['+', '-'])
+        .unwrap_or(lexical_form);
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return Err("the lexical form does not belong to the datatype");
+    }
+    let (lower, upper) = integer_bounds(value_type);
+    let is_in_bounds = match lexical_form.parse::<i128>() {
+        Ok(v) => lower.is_none_or(|lower| v >= lower) && upper.is_none_or(|upper| v <= upper),
+        // beyond the range of an i128, only the unbounded side of a type can be reached
+        Err(_) => {
+            if lexical_form.starts_with('-') {
+                lower.is_none()
+            } else {
+                upper.is_none()
+            }
+        }
+    };
+    if is_in_bounds {
+        Ok(())
+    } else {
+        Err("the value is outside of the value space of the datatype")
+    }
+}
+
+fn is_decimal(lexical_form: &str) -> bool {
+    let unsigned = lexical_form
+        .strip_prefix(['+', '-'])
+        .unwrap_or(lexical_form);
+    let (integer_part, fractional_part) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    (!integer_part.is_empty() || !fractional_part.is_empty())
+        && integer_part.chars().all(|c| c.is_ascii_digit())
+        && fractional_part.chars().all(|c| c.is_ascii_digit())
+}
+
+fn is_floating_point(lexical_form: &str) -> bool {
+    let (mantissa, exponent) = match lexical_form.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (lexical_form, None),
+    };
+    is_decimal(mantissa)
+        && exponent.is_none_or(|exponent| {
+            let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+        })
+}
+
+/// Remove the optional timezone, `Z` or an offset like `+01:00`, ending a lexical form.
+pub(crate) fn without_timezone(lexical_form: &str) -> &str {
+    if let Some(v) = lexical_form.strip_suffix('Z') {
+        return v;
+    }
+    if lexical_form.len() > 6 {
+        // a lexical form ending inside a multibyte character has no offset
+        let Some((rest, offset)) = lexical_form.split_at_checked(lexical_form.len() - 6) else {
+            return lexical_form;
+        };
+        let offset = offset.as_bytes();
+        if matches!(offset[0], b'+' | b'-')
+            && offset[3] == b':'
+            && [1, 2, 4, 5].iter().all(|i| offset[*i].is_ascii_digit())
+        {
+            return rest;
+        }
+    }
+    lexical_form
+}
+
+pub(crate) fn is_year(lexical_form: &str) -> bool {
+    let digits = lexical_form.strip_prefix('-').unwrap_or(lexical_form);
+    digits.len() >= 4 && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Check the shape `-?PnYnMnDTnHnMnS` of a duration, with at least one component
+/// and at least one component after `T`.
+fn is_duration(lexical_form: &str) -> bool {
+    let rest = lexical_form.strip_prefix('-').unwrap_or(lexical_form);
+    let rest = match rest.strip_prefix('P') {
+        Some(v) => v,
+        None => return false,
+    };
+    let (date_part, time_part) = match rest.split_once('T') {
+        Some((date_part, time_part)) => (date_part, Some(time_part)),
+        None => (rest, None),
+    };
+    let date_components = match components(date_part, &['Y', 'M', 'D'], false) {
+        Some(v) => v,
+        None => return false,
+    };
+    let time_components = match time_part {
+        Some(time_part) => match components(time_part, &['H', 'M', 'S'], true) {
+            Some(0) | None => return false,
+            Some(v) => v,
+        },
+        None => 0,
+    };
+    date_components + time_components > 0
+}
+
+/// Count the components of a part of a duration, each being a number followed by a designator
+/// in the order of the designators, `None` if the part is malformed.
+fn components(part: &str, designators: &[char], has_seconds: bool) -> Option<usize> {
+    let mut n_component = 0;
+    let mut rest = part;
+    let mut next_designator = 0;
+    while !rest.is_empty() {
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, designator) = (&rest[..end], rest[end..].chars().next()?);
+        let position = designators[next_designator..]
+            .iter()
+            .position(|v| *v == designator)?
+            + next_designator;
+        let allows_fraction = has_seconds && designator == 'S';
+        if number.is_empty() || (number.contains('.') && !(allows_fraction && is_decimal(number))) {
+            return None;
+        }
+        next_designator = position + 1;
+        n_component += 1;
+        rest = &rest[end + 1..];
+    }
+    Some(n_component)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_lexical_form;
+    use crate::tree::value::{Value, ValueType};
+
+    fn is_valid(value: &str, value_type: ValueType) -> bool {
+        check_lexical_form(&Value {
+            value: String::from(value),
+            value_type,
+        })
+        .is_ok()
+    }
+
+    #[test]
+    fn should_check_the_numbers() {
+        assert!(is_valid("-12", ValueType::Integer));
+        assert!(!is_valid("1.0", ValueType::Integer));
+        assert!(!is_valid("300", ValueType::Byte));
+        assert!(!is_valid("0", ValueType::PositiveInteger));
+        assert!(is_valid(
+            "99999999999999999999999999999999999999999",
+            ValueType::Integer
+        ));
+        assert!(is_valid(".5", ValueType::Decimal));
+        assert!(!is_valid("1e5", ValueType::Decimal));
+        assert!(is_valid("1.0E-7", ValueType::Double));
+        assert!(is_valid("-INF", ValueType::Float));
+        assert!(!is_valid("inf", ValueType::Double));
+    }
+
+    #[test]
+    fn should_check_the_temporal_values() {
+        assert!(is_valid("2022-01-02T03:04:05.6+01:00", ValueType::DateTime));
+        assert!(is_valid("2022-01-02T03:04:05", ValueType::DateTime));
+        assert!(!is_valid("2022-01-02", ValueType::DateTime));
+        assert!(is_valid("2022-01-02Z", ValueType::Date));
+        assert!(!is_valid("2022-13-02", ValueType::Date));
+        assert!(is_valid("03:04:05-05:00", ValueType::Time));
+        assert!(is_valid("-0044", ValueType::GYear));
+        assert!(!is_valid("44", ValueType::GYear));
+        assert!(is_valid("P1Y2M3DT4H5M6.7S", ValueType::Duration));
+        assert!(is_valid("-PT0S", ValueType::Duration));
+        assert!(!is_valid("P1Y2MT", ValueType::Duration));
+        assert!(!is_valid("P", ValueType::Duration));
+        assert!(!is_valid("P1D1Y", ValueType::Duration));
+        assert!(!is_valid("P1.5Y", ValueType::Duration));
+    }
+
+    #[test]
+    fn should_reject_the_multibyte_temporal_values() {
+        assert!(!is_valid("日本語x", ValueType::Date));
+        assert!(!is_valid("12:00:00é", ValueType::Time));
+        assert!(!is_valid("２０２２", ValueType::GYear));
+        assert!(!is_valid("2022+日:00", ValueType::GYear));
+    }
+
+    #[test]
+    fn should_check_the_other_values() {
+        assert!(is_valid("1", ValueType::Boolean));
+        assert!(!is_valid("yes", ValueType::Boolean));
+        assert!(is_valid(
+            "chat",
+            ValueType::LangString(String::from("fr-BE"))
+        ));
+        assert!(!is_valid(
+            "chat",
+            ValueType::LangString(String::from("fr_BE"))
+        ));
+        assert!(is_valid("https://example.com/a", ValueType::Iri));
+        assert!(!is_valid("example", ValueType::Iri));
+        assert!(is_valid(
+            "anything",
+            ValueType::Custom(String::from("https://example.com/t"))
+        ));
+    }
+}
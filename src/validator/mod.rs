@@ -0,0 +1,556 @@
+//! Check the conformance of TREE nodes to the specification and their internal consistency.
+pub mod lexical_form;
+
+use self::lexical_form::check_lexical_form;
+use crate::profile::count_reachable_members;
+use crate::query::filter::Filter;
+use crate::query::geo::{bounding_box_of, point_of, points_of_wkt};
+use crate::tree::member::Member;
+use crate::tree::node::Node;
+use crate::tree::relation::Relation;
+use crate::tree::relation_operator::RelationOperator;
+use crate::tree::value::Value;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::vec::Vec;
+
+/// The result of a validation, serializable in JSON.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub struct ValidationReport {
+    /// Number of nodes checked.
+    pub n_node: usize,
+    /// Number of relations checked.
+    pub n_relation: usize,
+    pub violations: Vec<Violation>,
+}
+
+/// A violation of the specification or an inconsistency between nodes.
+/// A relation is identified by the url of its node and the url of the node it targets.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Violation {
+    /// The node targeted by a relation is not among the nodes.
+    #[serde(rename_all = "camelCase")]
+    UnresolvedRelation { node: String, target: String },
+    /// A relation with an operator lacks its path or its value.
+    #[serde(rename_all = "camelCase")]
+    IncompleteRelation { node: String, target: String },
+    /// A member reachable through a relation does not respect its operator and value.
+    #[serde(rename_all = "camelCase")]
+    MemberOutsideOfRelation {
+        node: String,
+        target: String,
+        member: String,
+    },
+    /// The `tree:remainingItems` of a relation differs from the number of reachable members.
+    #[serde(rename_all = "camelCase")]
+    InaccurateRemainingItems {
+        node: String,
+        target: String,
+        announced: i32,
+        actual: usize,
+    },
+    /// A value of a relation or of a member does not belong to its datatype.
+    #[serde(rename_all = "camelCase")]
+    InvalidValue {
+        node: String,
+        /// The url of the member or of the node targeted by the relation holding the value.
+        subject: String,
+        value: String,
+        datatype: String,
+        reason: &'static str,
+    },
+    /// A node reachable from itself while the fragmentation claims to be a tree.
+    #[serde(rename_all = "camelCase")]
+    Cycle { nodes: Vec<String> },
+    /// A node targeted by several nodes while the fragmentation claims to be a tree.
+    #[serde(rename_all = "camelCase")]
+    SharedNode { node: String, parents: Vec<String> },
+    /// A node that cannot be reached from the root.
+    #[serde(rename_all = "camelCase")]
+    UnreachableNode { node: String },
+}
+
+impl ValidationReport {
+    /// Whether no violation was found.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// The report in JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("the report should be serializable")
+    }
+}
+
+/// Validate the nodes of a fragmentation starting at the root node. When `is_tree` is set,
+/// the fragmentation claims to be a tree, so the cycles and the nodes with several parents are violations.
+pub fn validate(nodes: &[Node], root_url: &str, is_tree: bool) -> ValidationReport {
+    let nodes_by_url: HashMap<&str, &Node> = nodes
+        .iter()
+        .map(|node| (node.node_url().as_str(), node))
+        .collect();
+    let mut violations: Vec<Violation> = Vec::new();
+    let mut reachable_members: HashMap<&str, Vec<&Member>> = HashMap::new();
+    let mut reachable_member_counts: HashMap<&str, usize> = HashMap::new();
+
+    for node in nodes {
+        let node_url = node.node_url();
+        for relation in node.relation() {
+            let target = relation.node();
+            if !nodes_by_url.contains_key(target.as_str()) {
+                violations.push(Violation::UnresolvedRelation {
+                    node: node_url.clone(),
+                    target: target.clone(),
+                });
+            }
+            if relation.relation_type().is_some()
+                && (relation.path().is_none() || relation.value().is_none())
+            {
+                violations.push(Violation::IncompleteRelation {
+                    node: node_url.clone(),
+                    target: target.clone(),
+                });
+            }
+            if let Some(value) = relation.value() {
+                violations.extend(check_value(node_url, target, value));
+            }
+            if let Some(announced) = relation.remaning_items() {
+                let actual = *reachable_member_counts
+                    .entry(target.as_str())
+                    .or_insert_with(|| count_reachable_members(&nodes_by_url, target));
+                if *announced < 0 || *announced as usize != actual {
+                    violations.push(Violation::InaccurateRemainingItems {
+                        node: node_url.clone(),
+                        target: target.clone(),
+                        announced: *announced,
+                        actual,
+                    });
+                }
+            }
+            let members = reachable_members
+                .entry(target.as_str())
+                .or_insert_with(|| members_reachable_from(&nodes_by_url, target));
+            for member in members.iter() {
+                if !respects(member, relation) {
+                    violations.push(Violation::MemberOutsideOfRelation {
+                        node: node_url.clone(),
+                        target: target.clone(),
+                        member: member.url.clone(),
+                    });
+                }
+            }
+        }
+        for member in node.members() {
+            let mut properties: Vec<&Value> = member.properties.values().collect();
+            properties.sort_by(|a, b| a.value.cmp(&b.value));
+            for value in properties {
+                violations.extend(check_value(node_url, &member.url, value));
+            }
+        }
+    }
+
+    violations.extend(check_topology(nodes, &nodes_by_url, root_url, is_tree));
+
+    ValidationReport {
+        n_node: nodes.len(),
+        n_relation: nodes.iter().map(|node| node.relation().len()).sum(),
+        violations,
+    }
+}
+
+fn check_value(node_url: &str, subject: &str, value: &Value) -> Option<Violation> {
+    check_lexical_form(value)
+        .err()
+        .map(|reason| Violation::InvalidValue {
+            node: String::from(node_url),
+            subject: String::from(subject),
+            value: value.value.clone(),
+            datatype: value.value_type.to_string(),
+            reason,
+        })
+}
+
+/// Whether a member reachable through the relation respects it, a member without the path
+/// of the relation being unconstrained.
+fn respects(member: &Member, relation: &Relation) -> bool {
+    let path = match relation.path() {
+        Some(v) => v,
+        None => return true,
+    };
+    let member_value = match member.properties.get(path) {
+        Some(v) => v,
+        None => return true,
+    };
+    if relation.relation_type() == &Some(RelationOperator::GeospatiallyContainsRelation) {
+        // the geometry is approximated by its bounding box, boundary included
+        let area = relation
+            .value()
+            .as_ref()
+            .and_then(|value| points_of_wkt(&value.value))
+            .and_then(|points| bounding_box_of(&points));
+        return match (area, point_of(member_value)) {
+            (Some(area), Some(point)) => {
+                point.x >= area.min.x
+                    && point.x <= area.max.x
+                    && point.y >= area.min.y
+                    && point.y <= area.max.y
+            }
+            _ => true,
+        };
+    }
+    match Filter::of_relation(relation) {
+        Some(filter) => filter.matches(member),
+        None => true,
+    }
+}
+
+/// The distinct members of the node and of the nodes reachable from it.
+fn members_reachable_from<'a>(
+    nodes_by_url: &HashMap<&str, &'a Node>,
+    node_url: &str,
+) -> Vec<&'a Member> {
+    let mut members: Vec<&Member> = Vec::new();
+    let mut seen_members: HashSet<&str> = HashSet::new();
+    let mut visited: HashSet<&str> = HashSet::from([node_url]);
+    let mut pending: VecDeque<&str> = VecDeque::from([node_url]);
+    while let Some(url) = pending.pop_front() {
+        let node = match nodes_by_url.get(url) {
+            Some(v) => *v,
+            None => continue,
+        };
+        for member in node.members() {
+            if seen_members.insert(&member.url) {
+                members.push(member);
+            }
+        }
+        for relation in node.relation() {
+            if visited.insert(relation.node().as_str()) {
+                pending.push_back(relation.node().as_str());
+            }
+        }
+    }
+    members
+}
+
+/// Find the unreachable nodes and, for a tree, the nodes with several parents and the cycles.
+fn check_topology(
+    nodes: &[Node],
+    nodes_by_url: &HashMap<&str, &Node>,
+    root_url: &str,
+    is_tree: bool,
+) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = Vec::new();
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for node in nodes {
+        for relation in node.relation() {
+            let parents_of_target = parents.entry(relation.node().as_str()).or_default();
+            if !parents_of_target.contains(&node.node_url().as_str()) {
+                parents_of_target.push(node.node_url());
+            }
+        }
+    }
+
+    // depth-first search keeping the path from the root, to find the relations going back into it
+    let mut visited: HashSet<&str> = HashSet::new();
+    let mut cycles: Vec<Vec<String>> = Vec::new();
+    if nodes_by_url.contains_key(root_url) {
+        visited.insert(root_url);
+        let mut path: Vec<(&str, usize)> = vec![(root_url, 0)];
+        while let Some((url, next_relation)) = path.last().copied() {
+            let relations = nodes_by_url[url].relation();
+            if next_relation == relations.len() {
+                path.pop();
+                continue;
+            }
+            path.last_mut().expect("the path should not be empty").1 += 1;
+            let target = relations[next_relation].node().as_str();
+            if let Some(start) = path.iter().position(|(v, _)| *v == target) {
+                cycles.push(
+                    path[start..]
+                        .iter()
+                        .map(|(v, _)| String::from(*v))
+                        .collect(),
+                );
+            } else if nodes_by_url.contains_key(target) && visited.insert(target) {
+                path.push((target, 0));
+            }
+        }
+    }
+
+    for node in nodes {
+        let node_url = node.node_url().as_str();
+        if !visited.contains(node_url) {
+            violations.push(Violation::UnreachableNode {
+                node: String::from(node_url),
+            });
+        }
+        if !is_tree {
+            continue;
+        }
+        if let Some(parents_of_node) = parents.get(node_url).filter(|v| v.len() > 1) {
+            violations.push(Violation::SharedNode {
+                node: String::from(node_url),
+                parents: parents_of_node.iter().map(|v| String::from(*v)).collect(),
+            });
+        }
+    }
+    if is_tree {
+        violations.extend(cycles.into_iter().map(|nodes| Violation::Cycle { nodes }));
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, Violation};
+    use crate::generate_tree_document;
+    use crate::generator_argument::member_argument::{
+        DistributionOfMember, MemberGeneratorArg, PropertyTemplate,
+    };
+    use crate::generator_argument::range::RandomBoundedNumberRange;
+    use crate::generator_argument::relation_argument::{
+        DistributionOfRelation, RelationGeneratorArg, RelationTemplate,
+        TemplateRangeVariationRelation,
+    };
+    use crate::generator_argument::Args;
+    use crate::sparql_converter::{OutOfRangeStrategy, TemporalFormat};
+    use crate::tree::member::Member;
+    use crate::tree::node::Node;
+    use crate::tree::relation::Relation;
+    use crate::tree::relation_operator::RelationOperator;
+    use crate::tree::tree_document::TreeDocument;
+    use crate::tree::value::{Value, ValueType};
+    use std::collections::HashMap;
+
+    const A_PATH: &str = "https://example.com/p";
+
+    fn an_int(value: &str) -> Value {
+        Value {
+            value: String::from(value),
+            value_type: ValueType::Int,
+        }
+    }
+
+    fn a_relation(target: &str, value: &str, remaining_items: Option<i32>) -> Relation {
+        Relation::new(
+            remaining_items,
+            Some(String::from(A_PATH)),
+            Some(an_int(value)),
+            format!("https://example.com/{}", target),
+            Some(RelationOperator::GreaterThanRelation),
+        )
+    }
+
+    fn a_member(i: usize, value: &str) -> Member {
+        Member {
+            url: format!("https://example.com/member/{}", i),
+            properties: HashMap::from([(String::from(A_PATH), an_int(value))]),
+        }
+    }
+
+    fn a_node(url: &str, relations: Vec<Relation>, members: Vec<Member>) -> Node {
+        Node::new(relations, format!("https://example.com/{}", url), members)
+    }
+
+    #[test]
+    fn should_validate_consistent_nodes() {
+        let nodes = vec![
+            a_node(
+                "root",
+                vec![a_relation("a", "10", Some(2))],
+                vec![a_member(0, "1")],
+            ),
+            a_node(
+                "a",
+                vec![a_relation("b", "20", Some(1))],
+                vec![a_member(1, "15")],
+            ),
+            a_node("b", Vec::new(), vec![a_member(2, "25")]),
+        ];
+
+        let report = validate(&nodes, "https://example.com/root", true);
+
+        assert!(report.is_valid(), "{:?}", report.violations);
+        assert_eq!(report.n_node, 3);
+        assert_eq!(report.n_relation, 2);
+    }
+
+    #[test]
+    fn should_report_the_inconsistencies() {
+        let nodes = vec![
+            a_node(
+                "root",
+                vec![
+                    a_relation("a", "10", Some(1)),
+                    a_relation("missing", "0", None),
+                    Relation::new(
+                        None,
+                        None,
+                        None,
+                        String::from("https://example.com/a"),
+                        Some(RelationOperator::LessThanRelation),
+                    ),
+                ],
+                Vec::new(),
+            ),
+            a_node(
+                "a",
+                vec![a_relation("root", "0", None)],
+                vec![a_member(0, "5"), a_member(1, "300000000000")],
+            ),
+            a_node("orphan", Vec::new(), Vec::new()),
+        ];
+
+        let report = validate(&nodes, "https://example.com/root", true);
+
+        let violations = &report.violations;
+        assert!(violations.contains(&Violation::UnresolvedRelation {
+            node: String::from("https://example.com/root"),
+            target: String::from("https://example.com/missing"),
+        }));
+        assert!(violations.contains(&Violation::IncompleteRelation {
+            node: String::from("https://example.com/root"),
+            target: String::from("https://example.com/a"),
+        }));
+        assert!(violations.contains(&Violation::MemberOutsideOfRelation {
+            node: String::from("https://example.com/root"),
+            target: String::from("https://example.com/a"),
+            member: String::from("https://example.com/member/0"),
+        }));
+        assert!(violations.contains(&Violation::InaccurateRemainingItems {
+            node: String::from("https://example.com/root"),
+            target: String::from("https://example.com/a"),
+            announced: 1,
+            actual: 2,
+        }));
+        assert!(violations.iter().any(|violation| matches!(
+            violation,
+            Violation::InvalidValue { subject, .. } if subject == "https://example.com/member/1"
+        )));
+        assert!(violations.contains(&Violation::Cycle {
+            nodes: vec![
+                String::from("https://example.com/root"),
+                String::from("https://example.com/a")
+            ],
+        }));
+        assert!(violations.contains(&Violation::UnreachableNode {
+            node: String::from("https://example.com/orphan"),
+        }));
+        assert!(report
+            .to_json()
+            .contains("{\"kind\":\"unreachableNode\",\"node\":\"https://example.com/orphan\"}"));
+    }
+
+    #[test]
+    fn should_only_report_the_shared_nodes_of_a_tree() {
+        let nodes = vec![
+            a_node(
+                "root",
+                vec![a_relation("a", "0", None), a_relation("b", "0", None)],
+                Vec::new(),
+            ),
+            a_node("a", vec![a_relation("b", "0", None)], Vec::new()),
+            a_node("b", Vec::new(), Vec::new()),
+        ];
+
+        assert!(validate(&nodes, "https://example.com/root", false).is_valid());
+        assert_eq!(
+            validate(&nodes, "https://example.com/root", true).violations,
+            vec![Violation::SharedNode {
+                node: String::from("https://example.com/b"),
+                parents: vec![
+                    String::from("https://example.com/root"),
+                    String::from("https://example.com/a")
+                ],
+            }]
+        );
+    }
+
+    /// A generated tree whose relations require a value greater than their value on the path of the members.
+    fn a_generated_document() -> TreeDocument {
+        let args = Args {
+            relation: RelationGeneratorArg::ValueVariation(TemplateRangeVariationRelation {
+                template: RelationTemplate {
+                    path: String::from(A_PATH),
+                    relation_type: RelationOperator::GreaterThanRelation,
+                },
+                range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                distribution_of_relation: DistributionOfRelation::Direct(vec![3, 1, 2]),
+                value_type: ValueType::Int,
+                out_of_range_strategy: OutOfRangeStrategy::Reject,
+                temporal_format: TemporalFormat::default(),
+            }),
+            members: Some(MemberGeneratorArg {
+                properties: vec![PropertyTemplate {
+                    path: String::from(A_PATH),
+                    range: Box::new(RandomBoundedNumberRange::new(0, 100)),
+                    value_type: ValueType::Int,
+                    out_of_range_strategy: OutOfRangeStrategy::Reject,
+                    temporal_format: TemporalFormat::default(),
+                }],
+                distribution_of_member: DistributionOfMember::Direct(vec![1, 2, 3]),
+                versioning: None,
+            }),
+            search_form: None,
+            topology: None,
+            base_url: String::from("https://example.com"),
+        };
+        generate_tree_document(&args).unwrap()
+    }
+
+    #[test]
+    fn should_validate_a_generated_document() {
+        let document = a_generated_document();
+
+        let report = validate(document.nodes(), document.collection().view(), true);
+
+        assert!(report.is_valid(), "{:?}", report.violations);
+    }
+
+    #[test]
+    fn should_report_the_generated_members_outside_of_their_relations() {
+        let document = a_generated_document();
+        // no value is greater than the value of a relation once the values of the members are 0
+        let nodes: Vec<Node> = document
+            .nodes()
+            .iter()
+            .map(|node| {
+                let members = node
+                    .members()
+                    .iter()
+                    .cloned()
+                    .map(|mut member| {
+                        member.properties.insert(
+                            String::from(A_PATH),
+                            Value {
+                                value: String::from("0"),
+                                value_type: ValueType::Int,
+                            },
+                        );
+                        member
+                    })
+                    .collect();
+                Node::new(node.relation().clone(), node.node_url().clone(), members)
+            })
+            .collect();
+
+        let report = validate(&nodes, document.collection().view(), true);
+
+        let mut members_outside: Vec<&String> = report
+            .violations
+            .iter()
+            .map(|violation| match violation {
+                Violation::MemberOutsideOfRelation { member, .. } => member,
+                violation => panic!("unexpected violation {:?}", violation),
+            })
+            .collect();
+        members_outside.sort();
+        let mut members_of_the_children: Vec<&String> = nodes[1..]
+            .iter()
+            .flat_map(|node| node.members().iter().map(|member| &member.url))
+            .collect();
+        members_of_the_children.sort();
+        assert_eq!(members_of_the_children.len(), 5);
+        assert_eq!(members_outside, members_of_the_children);
+    }
+}